@@ -96,10 +96,13 @@ where
         payment: &Credential,
         delegation: Option<&Credential>,
     ) -> anyhow::Result<BTreeMap<Input, Output>> {
-        let mut addr = Address::new(NetworkId::from(self.network), payment.clone());
-        if let Some(delegation) = delegation {
-            addr = addr.with_delegation(delegation.clone());
-        }
+        // The backend forwards bare payment credentials as is, matching any delegation.
+        let addr = match delegation {
+            Some(delegation) => Address::new(NetworkId::from(self.network), payment.clone())
+                .with_delegation(delegation.clone())
+                .to_string(),
+            None => payment.to_bech32(),
+        };
 
         let utxos = self
             .http_client
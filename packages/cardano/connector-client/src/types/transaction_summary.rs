@@ -2,7 +2,9 @@ use crate::{
     helpers::try_into_array,
     types::asset_object::{AssetObject, from_asset_objects},
 };
-use cardano_sdk::{Address, Hash, Input, Output, address::kind, cbor, cbor as minicbor};
+use cardano_sdk::{
+    Address, Hash, Input, Output, PlutusData, address::kind, cbor, cbor as minicbor,
+};
 
 /// A synthetic representation of a transaction used by the Connector.
 #[derive(Debug, Clone, cbor::Encode, cbor::Decode)]
@@ -19,6 +21,9 @@ pub struct TransactionSummary {
     pub outputs: Vec<(Output, Option<Hash<28>>)>,
     #[n(5)]
     pub timestamp_secs: u64,
+    /// Redeemers of spent script inputs, alongside the input they spend.
+    #[n(6)]
+    pub redeemers: Vec<(Input, PlutusData<'static>)>,
 }
 
 impl<'de> serde::Deserialize<'de> for TransactionSummary {
@@ -34,6 +39,15 @@ impl<'de> serde::Deserialize<'de> for TransactionSummary {
             inputs: Vec<JsonInput<'a>>,
             outputs: Vec<JsonOutput<'a>>,
             timestamp: u64,
+            #[serde(default, borrow)]
+            redeemers: Vec<JsonRedeemer<'a>>,
+        }
+
+        #[derive(serde::Deserialize)]
+        pub struct JsonRedeemer<'a> {
+            purpose: &'a str,
+            index: usize,
+            data: &'a str,
         }
 
         #[derive(serde::Deserialize)]
@@ -92,16 +106,39 @@ impl<'de> serde::Deserialize<'de> for TransactionSummary {
 
         let json = JsonTransaction::deserialize(deserializer)?;
 
+        let inputs = json
+            .inputs
+            .into_iter()
+            .map(JsonInput::try_into)
+            .collect::<Result<Vec<(Input, Output, Option<Hash<28>>)>, _>>()
+            .map_err(serde::de::Error::custom)?;
+
+        // Spend redeemers point into the (lexicographically) sorted list of spent inputs.
+        let mut spent = inputs
+            .iter()
+            .map(|(input, _, _)| input.clone())
+            .collect::<Vec<_>>();
+        spent.sort();
+
+        let redeemers = json
+            .redeemers
+            .into_iter()
+            .filter(|r| r.purpose == "spend")
+            .map(|r| {
+                let input = spent.get(r.index).cloned().ok_or_else(|| {
+                    anyhow::anyhow!("redeemer points to unknown input at index {}", r.index)
+                })?;
+                let data: PlutusData<'static> = cbor::decode(&hex::decode(r.data)?)?;
+                Ok((input, data))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(serde::de::Error::custom)?;
+
         Ok(TransactionSummary {
             id: Hash::<32>::try_from(json.id).map_err(serde::de::Error::custom)?,
             index: json.index,
             depth: json.depth,
-            inputs: json
-                .inputs
-                .into_iter()
-                .map(JsonInput::try_into)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(serde::de::Error::custom)?,
+            inputs,
             outputs: json
                 .outputs
                 .into_iter()
@@ -109,6 +146,7 @@ impl<'de> serde::Deserialize<'de> for TransactionSummary {
                 .collect::<Result<Vec<_>, _>>()
                 .map_err(serde::de::Error::custom)?,
             timestamp_secs: json.timestamp,
+            redeemers,
         })
     }
 }
@@ -196,4 +234,53 @@ mod tests {
 
         assert!(dbg!(serde_json::from_str::<TransactionSummary>(json)).is_ok())
     }
+
+    #[test]
+    fn deserialize_json_with_redeemers() {
+        let json: &str = r#"{
+          "id": "9cde01ec0abab4a0ea623b1151b97fd54dfb6b12dca52fcd3d23a86c4cd5c08a",
+          "index": 0,
+          "depth": 1,
+          "timestamp": 1771459333,
+          "inputs": [
+            {
+              "transaction_id": "a815ffd09fb6d98b574a8caf58b75949002eab2572b705763db3093cd60e8e66",
+              "output_index": 1,
+              "address": "addr_test1vqe4hywyz43w8tw4ddwgjpzvn3j970dtth30ua5qa3wqd8cz2rwa8",
+              "value": [{ "unit": "lovelace", "quantity": "851296162" }]
+            },
+            {
+              "transaction_id": "5bbe52cff47e3a3a903f224060447fd7e9bc0babca701719a91c20482101529a",
+              "output_index": 0,
+              "address": "addr_test1wp5085l2ll4e8n9v0l799gu9eqk3speag5h92q4mys355zguytrmh",
+              "value": [{ "unit": "lovelace", "quantity": "16000000" }]
+            }
+          ],
+          "outputs": [],
+          "redeemers": [
+            { "purpose": "spend", "index": 0, "data": "d87980" },
+            { "purpose": "mint", "index": 0, "data": "d87980" }
+          ]
+        }"#;
+
+        let summary = serde_json::from_str::<TransactionSummary>(json).unwrap();
+
+        assert_eq!(summary.redeemers.len(), 1);
+        assert_eq!(summary.redeemers[0].0, summary.inputs[1].0);
+    }
+
+    #[test]
+    fn deserialize_json_with_dangling_redeemer() {
+        let json: &str = r#"{
+          "id": "9cde01ec0abab4a0ea623b1151b97fd54dfb6b12dca52fcd3d23a86c4cd5c08a",
+          "index": 0,
+          "depth": 1,
+          "timestamp": 1771459333,
+          "inputs": [],
+          "outputs": [],
+          "redeemers": [{ "purpose": "spend", "index": 0, "data": "d87980" }]
+        }"#;
+
+        assert!(serde_json::from_str::<TransactionSummary>(json).is_err());
+    }
 }
@@ -1,7 +1,7 @@
 use anyhow::anyhow;
 use blockfrost::{BlockfrostAPI, BlockfrostError, Pagination};
 use blockfrost_openapi::models::{
-    address_utxo_content_inner::AddressUtxoContentInner,
+    address_utxo_content_inner::AddressUtxoContentInner, tx_content_cbor::TxContentCbor,
     tx_content_output_amount_inner::TxContentOutputAmountInner,
 };
 use cardano_connector::CardanoConnector;
@@ -39,6 +39,17 @@ impl Blockfrost {
         }
     }
 
+    /// The CBOR of a transaction, if the chain knows of it.
+    pub async fn transaction_cbor(&self, id: &Hash<32>) -> anyhow::Result<Option<Vec<u8>>> {
+        match self.api.transactions_cbor(&id.to_string()).await {
+            Err(BlockfrostError::Response { url: _, reason }) if reason.status_code == 404 => {
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+            Ok(TxContentCbor { cbor }) => Ok(Some(hex::decode(cbor)?)),
+        }
+    }
+
     pub async fn resolve_datum_hash(
         &self,
         datum_hash: &str,
@@ -191,12 +202,7 @@ impl CardanoConnector for Blockfrost {
         //             })
         //             .collect::<anyhow::Result<Vec<i64>>>()?,
         //     );
-        let pp = match self.network {
-            Network::Mainnet => ProtocolParameters::mainnet(),
-            Network::Preview => ProtocolParameters::preview(),
-            Network::Preprod => ProtocolParameters::preprod(),
-        };
-        Ok(pp)
+        Ok(ProtocolParameters::from(self.network))
     }
 
     async fn utxos_at(
@@ -204,16 +210,15 @@ impl CardanoConnector for Blockfrost {
         payment: &Credential,
         delegation: Option<&Credential>,
     ) -> anyhow::Result<BTreeMap<Input, Output>> {
-        let mut addr = Address::new(self.network().into(), payment.clone());
-
-        if let Some(delegation) = delegation {
-            addr = addr.with_delegation(delegation.clone());
-        }
+        // Blockfrost also accepts bare payment credentials, matching any delegation.
+        let addr = match delegation {
+            Some(delegation) => Address::new(self.network().into(), payment.clone())
+                .with_delegation(delegation.clone())
+                .to_string(),
+            None => payment.to_bech32(),
+        };
 
-        let response = self
-            .api
-            .addresses_utxos(&format!("{}", addr), Pagination::all())
-            .await;
+        let response = self.api.addresses_utxos(&addr, Pagination::all()).await;
 
         let response = match response {
             Err(BlockfrostError::Response { url: _, reason }) if reason.status_code == 404 => {
@@ -238,6 +243,23 @@ impl CardanoConnector for Blockfrost {
         self.api.transactions_submit(bytes).await?;
         Ok(())
     }
+
+    async fn confirmations(&self, id: &Hash<32>) -> anyhow::Result<Option<u64>> {
+        let tx = match self.api.transaction_by_hash(&id.to_string()).await {
+            Err(BlockfrostError::Response { url: _, reason }) if reason.status_code == 404 => {
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+            Ok(tx) => tx,
+        };
+        let tip = self
+            .api
+            .blocks_latest()
+            .await?
+            .height
+            .ok_or(anyhow!("latest block without height"))?;
+        Ok(Some((tip - tx.block_height + 1).max(1) as u64))
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -1,4 +1,4 @@
-import { toTransaction } from "../helpers/index.mjs";
+import { fetchRedeemers, toTransaction } from "../helpers/index.mjs";
 
 const DEFAULT_VALUE = null;
 
@@ -9,6 +9,7 @@ export async function endpointTransaction(ctx) {
       await ctx.blockfrost(`/txs/${ctx.req.param("id")}`),
       await ctx.blockfrost(`/txs/${ctx.req.param("id")}/utxos`),
     ]);
-    return toTransaction(tip, meta, utxos);
+    const redeemers = await fetchRedeemers(ctx, meta);
+    return toTransaction(tip, meta, utxos, redeemers);
   });
 }
@@ -1,4 +1,4 @@
-import { toTransaction } from "../helpers/index.mjs";
+import { fetchRedeemers, toTransaction } from "../helpers/index.mjs";
 
 const DEFAULT_VALUE = [];
 
@@ -15,7 +15,8 @@ export async function endpointTransactions(ctx) {
       transactions.map(async (tx) => {
         const meta = await ctx.blockfrost(`/txs/${tx.tx_hash}`);
         const utxos = await ctx.blockfrost(`/txs/${tx.tx_hash}/utxos`);
-        return toTransaction(tip, meta, utxos);
+        const redeemers = await fetchRedeemers(ctx, meta);
+        return toTransaction(tip, meta, utxos, redeemers);
       }),
     );
   });
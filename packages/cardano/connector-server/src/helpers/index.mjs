@@ -21,7 +21,7 @@ export function guessPlutusVersion(scriptHash, script) {
 /**
  * Aggregate pieces of information from various blockfrost endpoint into a cardano-connector-server's Transaction
  */
-export function toTransaction(tip, meta, utxos) {
+export function toTransaction(tip, meta, utxos, redeemers = []) {
   return {
     id: meta.hash,
     index: meta.index,
@@ -34,9 +34,35 @@ export function toTransaction(tip, meta, utxos) {
       invalid_after: Number(meta.invalid_after),
     }),
     ...partitionInputsOutputs(meta, utxos),
+    ...(redeemers.length > 0 && { redeemers }),
   };
 }
 
+/**
+ * Fetch redeemers of a transaction, resolving their data. Blockfrost only returns a hash of the
+ * redeemer data, so this costs an extra request per redeemer; hence only done when needed.
+ *
+ * @param {any} ctx The request context, providing access to blockfrost.
+ * @param {any} meta The result from the /txs/:id Blockfrost endpoint.
+ * @returns {Promise<{ purpose: string, index: number, data: string }[]>}
+ */
+export async function fetchRedeemers(ctx, meta) {
+  if (!meta.redeemer_count) {
+    return [];
+  }
+
+  const redeemers = await ctx.blockfrost(`/txs/${meta.hash}/redeemers`);
+
+  return await Promise.all(
+    redeemers.map(async (r) => {
+      const { cbor } = await ctx.blockfrost(
+        `/scripts/datum/${r.redeemer_data_hash}/cbor`,
+      );
+      return { purpose: r.purpose, index: r.tx_index, data: cbor };
+    }),
+  );
+}
+
 /**
  * Convert a blockfrost output to a connector-server output.
  */
@@ -7,7 +7,7 @@ pub use config::Config;
 use anyhow::{Context, anyhow};
 use cardano_connector::CardanoConnector;
 use cardano_sdk::{
-    Credential, Input, Network, Output, ProtocolParameters, Transaction, cbor::ToCbor,
+    Credential, Hash, Input, Network, Output, ProtocolParameters, Transaction, cbor::ToCbor,
     transaction::state,
 };
 use std::collections::BTreeMap;
@@ -45,6 +45,23 @@ impl UtxoRpc {
         &self.config
     }
 
+    /// The CBOR of a transaction, if the chain knows of it.
+    pub async fn transaction_cbor(&self, id: &Hash<32>) -> anyhow::Result<Option<Vec<u8>>> {
+        let mut query = self.query.lock().await;
+        let tx = query
+            .read_tx(id.as_ref().to_vec().into())
+            .await
+            .map_err(|error| anyhow!(error))
+            .with_context(|| {
+                format!(
+                    "failed to read transaction {id} from {}",
+                    self.config.endpoint()
+                )
+            })?;
+
+        Ok(tx.map(|tx| tx.native.to_vec()))
+    }
+
     async fn read_tip(&self) -> anyhow::Result<utxorpc::spec::sync::BlockRef> {
         let mut sync = ClientBuilder::new()
             .uri(self.config.endpoint())
@@ -169,7 +186,9 @@ pub fn ensure_network_matches(
     live: Network,
     endpoint: &str,
 ) -> anyhow::Result<()> {
-    if configured == live {
+    // The slot configuration of a custom network is whatever was configured: only the magic
+    // identifies it.
+    if configured == live || (configured.is_custom() && configured.magic() == live.magic()) {
         return Ok(());
     }
 
@@ -183,21 +202,22 @@ fn network_from_genesis(genesis: &utxorpc::spec::cardano::Genesis) -> anyhow::Re
         Network::MAINNET_MAGIC => Network::Mainnet,
         Network::PREPROD_MAGIC => Network::Preprod,
         Network::PREVIEW_MAGIC => Network::Preview,
-        other => {
-            return Err(anyhow!(
-                "unsupported Cardano network magic {other} in Dolos genesis"
-            ));
-        }
+        magic => Network::Custom {
+            magic,
+            system_start: genesis.start_time,
+            slot_length: match genesis.slot_length {
+                0 => Network::SLOT_LENGTH,
+                seconds => u64::from(seconds) * 1000,
+            },
+        },
     };
 
     let raw_network_id = genesis.network_id.trim();
     let normalized_network_id = raw_network_id.to_ascii_lowercase();
 
     match (normalized_network_id.as_str(), network) {
-        ("mainnet", Network::Mainnet)
-        | ("testnet", Network::Preprod)
-        | ("testnet", Network::Preview)
-        | ("", _) => Ok(network),
+        ("mainnet", Network::Mainnet) | ("", _) => Ok(network),
+        ("testnet", network) if network.is_testnet() => Ok(network),
         (normalized, derived) => Err(anyhow!(
             "Dolos genesis network_id {raw_network_id} (normalized: {normalized}) is inconsistent with network magic for {derived}"
         )),
@@ -223,7 +243,10 @@ impl CardanoConnector for UtxoRpc {
 
     async fn protocol_parameters(&self) -> anyhow::Result<ProtocolParameters> {
         let mut query = self.query.lock().await;
-        params::read(&mut query).await
+        // Era summaries say when slots start, not how long they last.
+        Ok(params::read(&mut query)
+            .await?
+            .with_slot_length(self.config.network().slot_length()))
     }
 
     async fn utxos_at(
@@ -248,6 +271,28 @@ impl CardanoConnector for UtxoRpc {
 
         Ok(())
     }
+
+    async fn confirmations(&self, id: &Hash<32>) -> anyhow::Result<Option<u64>> {
+        let block = {
+            let mut query = self.query.lock().await;
+            query
+                .read_tx(id.as_ref().to_vec().into())
+                .await
+                .map_err(|error| anyhow!(error))
+                .with_context(|| {
+                    format!(
+                        "failed to read transaction {id} from {}",
+                        self.config.endpoint()
+                    )
+                })?
+                .and_then(|tx| tx.block_ref)
+        };
+        let Some(block) = block else {
+            return Ok(None);
+        };
+        let tip = self.read_tip().await?;
+        Ok(Some(tip.height.saturating_sub(block.height) + 1))
+    }
 }
 
 #[cfg(test)]
@@ -369,15 +414,27 @@ mod tests {
     }
 
     #[test]
-    fn network_from_genesis_rejects_unsupported_magic() {
-        let error =
-            network_from_genesis(&genesis("testnet", 999)).expect_err("unknown magic should fail");
+    fn network_from_genesis_maps_unknown_magic_to_custom_network() {
+        let network =
+            network_from_genesis(&genesis("testnet", 42)).expect("unknown magic should map");
 
-        assert!(
-            error
-                .to_string()
-                .contains("unsupported Cardano network magic")
-        );
+        assert_eq!(network.magic(), 42);
+        assert!(network.is_custom());
+
+        ensure_network_matches(
+            Network::try_from("custom:42:1700000000:200").unwrap(),
+            network,
+            "http://127.0.0.1:1337",
+        )
+        .expect("custom networks should match on their magic");
+    }
+
+    #[test]
+    fn network_from_genesis_rejects_custom_magic_with_mainnet_id() {
+        let error = network_from_genesis(&genesis("mainnet", 42))
+            .expect_err("inconsistent genesis should fail");
+
+        assert!(error.to_string().contains("inconsistent"));
     }
 
     #[test]
@@ -1,5 +1,5 @@
 use cardano_sdk::{
-    Credential, Input, Network, Output, ProtocolParameters, Transaction, transaction::state,
+    Credential, Hash, Input, Network, Output, ProtocolParameters, Transaction, transaction::state,
 };
 use std::collections::BTreeMap;
 
@@ -22,4 +22,15 @@ pub trait CardanoConnector {
         &self,
         transaction: &Transaction<state::ReadyForSigning>,
     ) -> impl Future<Output = anyhow::Result<()>>;
+
+    /// How deep a transaction is on-chain, in blocks: 1 once in the tip block, and None while not
+    /// on-chain at all. Backends that cannot tell fail.
+    fn confirmations(&self, id: &Hash<32>) -> impl Future<Output = anyhow::Result<Option<u64>>> {
+        let id = *id;
+        async move {
+            Err(anyhow::anyhow!(
+                "cannot tell confirmations of {id} with this backend"
+            ))
+        }
+    }
 }
@@ -3,6 +3,7 @@
 //  file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 pub mod address;
+pub mod certificate;
 pub mod credential;
 pub mod crypto;
 pub mod datum;
@@ -10,6 +11,7 @@ pub mod execution_units;
 pub mod hash;
 pub mod input;
 pub mod macros;
+pub mod metadatum;
 pub mod network;
 pub mod network_id;
 pub mod output;
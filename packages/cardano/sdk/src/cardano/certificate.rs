@@ -0,0 +1,251 @@
+//  This Source Code Form is subject to the terms of the Mozilla Public
+//  License, v. 2.0. If a copy of the MPL was not distributed with this
+//  file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{Credential, Hash, cbor, pallas};
+use std::fmt;
+
+/// A certificate, as published with
+/// [`Transaction::with_certificates`](crate::Transaction::with_certificates).
+///
+/// Only stake certificates can be constructed, in their Conway form which carries the deposit
+/// explicitly:
+///
+/// - [`Self::register`]
+/// - [`Self::deregister`]
+/// - [`Self::delegate`]
+/// - [`Self::register_and_delegate`]
+///
+/// Any other certificate may still be found when inspecting a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Certificate(pallas::Certificate);
+
+impl fmt::Display for Certificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            pallas::Certificate::Reg(credential, deposit) => {
+                write!(
+                    f,
+                    "Register({}, {deposit})",
+                    Credential::from(credential.clone())
+                )
+            }
+            pallas::Certificate::UnReg(credential, refund) => {
+                write!(
+                    f,
+                    "Deregister({}, {refund})",
+                    Credential::from(credential.clone())
+                )
+            }
+            pallas::Certificate::StakeDelegation(credential, pool) => write!(
+                f,
+                "Delegate({}, {})",
+                Credential::from(credential.clone()),
+                Hash::from(pool)
+            ),
+            pallas::Certificate::StakeRegDeleg(credential, pool, deposit) => write!(
+                f,
+                "RegisterAndDelegate({}, {}, {deposit})",
+                Credential::from(credential.clone()),
+                Hash::from(pool)
+            ),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------- Building
+
+impl Certificate {
+    /// Register a stake credential, against the deposit currently set by the protocol.
+    pub fn register(credential: Credential, deposit: u64) -> Self {
+        Self(pallas::Certificate::Reg(credential.into(), deposit))
+    }
+
+    /// Deregister a stake credential, reclaiming the deposit made at registration.
+    pub fn deregister(credential: Credential, refund: u64) -> Self {
+        Self(pallas::Certificate::UnReg(credential.into(), refund))
+    }
+
+    /// Delegate a registered stake credential to a stake pool.
+    pub fn delegate(credential: Credential, pool: Hash<28>) -> Self {
+        Self(pallas::Certificate::StakeDelegation(
+            credential.into(),
+            pallas::Hash::from(pool),
+        ))
+    }
+
+    /// Register a stake credential and delegate it, in a single certificate.
+    pub fn register_and_delegate(credential: Credential, pool: Hash<28>, deposit: u64) -> Self {
+        Self(pallas::Certificate::StakeRegDeleg(
+            credential.into(),
+            pallas::Hash::from(pool),
+            deposit,
+        ))
+    }
+}
+
+// ------------------------------------------------------------------ Inspecting
+
+impl Certificate {
+    /// The stake credential the certificate is about, if a stake certificate.
+    pub fn credential(&self) -> Option<Credential> {
+        match &self.0 {
+            pallas::Certificate::StakeRegistration(credential)
+            | pallas::Certificate::StakeDeregistration(credential)
+            | pallas::Certificate::StakeDelegation(credential, _)
+            | pallas::Certificate::Reg(credential, _)
+            | pallas::Certificate::UnReg(credential, _)
+            | pallas::Certificate::VoteDeleg(credential, _)
+            | pallas::Certificate::StakeVoteDeleg(credential, _, _)
+            | pallas::Certificate::StakeRegDeleg(credential, _, _)
+            | pallas::Certificate::VoteRegDeleg(credential, _, _)
+            | pallas::Certificate::StakeVoteRegDeleg(credential, _, _, _) => {
+                Some(Credential::from(credential.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// The stake pool delegated to, if any.
+    pub fn pool(&self) -> Option<Hash<28>> {
+        match &self.0 {
+            pallas::Certificate::StakeDelegation(_, pool)
+            | pallas::Certificate::StakeVoteDeleg(_, pool, _)
+            | pallas::Certificate::StakeRegDeleg(_, pool, _)
+            | pallas::Certificate::StakeVoteRegDeleg(_, pool, _, _) => Some(Hash::from(pool)),
+            _ => None,
+        }
+    }
+
+    /// Lovelace the certificate locks as deposit, when stated in the certificate.
+    pub fn deposit(&self) -> u64 {
+        match &self.0 {
+            pallas::Certificate::Reg(_, deposit)
+            | pallas::Certificate::StakeRegDeleg(_, _, deposit)
+            | pallas::Certificate::VoteRegDeleg(_, _, deposit)
+            | pallas::Certificate::StakeVoteRegDeleg(_, _, _, deposit)
+            | pallas::Certificate::RegDRepCert(_, deposit, _) => *deposit,
+            _ => 0,
+        }
+    }
+
+    /// Lovelace the certificate gives back from a deposit, when stated in the certificate.
+    pub fn refund(&self) -> u64 {
+        match &self.0 {
+            pallas::Certificate::UnReg(_, refund)
+            | pallas::Certificate::UnRegDRepCert(_, refund) => *refund,
+            _ => 0,
+        }
+    }
+
+    /// Whether the deposit or refund is implied by the protocol parameters rather than stated in
+    /// the certificate; as for pre-Conway stake (de)registrations and pool registrations.
+    pub fn has_implicit_deposit(&self) -> bool {
+        matches!(
+            self.0,
+            pallas::Certificate::StakeRegistration(..)
+                | pallas::Certificate::StakeDeregistration(..)
+                | pallas::Certificate::PoolRegistration { .. }
+        )
+    }
+
+    /// The credential that must witness the certificate: through a signature for keys, or a
+    /// redeemer for scripts. Only pre-Conway stake registrations need none.
+    pub fn witness(&self) -> Option<Credential> {
+        match &self.0 {
+            pallas::Certificate::StakeRegistration(..) => None,
+            pallas::Certificate::PoolRegistration { operator, .. }
+            | pallas::Certificate::PoolRetirement(operator, _) => {
+                Some(Credential::from_key(Hash::from(operator)))
+            }
+            pallas::Certificate::AuthCommitteeHot(credential, _)
+            | pallas::Certificate::ResignCommitteeCold(credential, _)
+            | pallas::Certificate::RegDRepCert(credential, _, _)
+            | pallas::Certificate::UnRegDRepCert(credential, _)
+            | pallas::Certificate::UpdateDRepCert(credential, _) => {
+                Some(Credential::from(credential.clone()))
+            }
+            _ => self.credential(),
+        }
+    }
+}
+
+// ----------------------------------------------------------- Converting (from)
+
+impl From<pallas::Certificate> for Certificate {
+    fn from(certificate: pallas::Certificate) -> Self {
+        Self(certificate)
+    }
+}
+
+// ------------------------------------------------------------- Converting (to)
+
+impl From<Certificate> for pallas::Certificate {
+    fn from(certificate: Certificate) -> Self {
+        certificate.0
+    }
+}
+
+// -------------------------------------------------------------------- Encoding
+
+impl<C> cbor::Encode<C> for Certificate {
+    fn encode<W: cbor::encode::write::Write>(
+        &self,
+        e: &mut cbor::Encoder<W>,
+        ctx: &mut C,
+    ) -> Result<(), cbor::encode::Error<W::Error>> {
+        e.encode_with(&self.0, ctx)?;
+        Ok(())
+    }
+}
+
+impl<'d, C> cbor::Decode<'d, C> for Certificate {
+    fn decode(d: &mut cbor::Decoder<'d>, ctx: &mut C) -> Result<Self, cbor::decode::Error> {
+        Ok(Self(d.decode_with(ctx)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{key_credential, script_credential};
+
+    #[test]
+    fn only_legacy_registrations_need_no_witness() {
+        let credential =
+            script_credential!("bd3ae991b5aafccafe5ca70758bd36a9b2f872f57f6d3a1ffa0eb777");
+        let pool = Hash::from([1; 28]);
+
+        assert_eq!(
+            Certificate::register(credential.clone(), 2_000_000).witness(),
+            Some(credential.clone())
+        );
+        assert_eq!(
+            Certificate::delegate(credential.clone(), pool).witness(),
+            Some(credential.clone())
+        );
+        assert_eq!(
+            Certificate::from(pallas::Certificate::StakeRegistration(credential.into())).witness(),
+            None,
+        );
+    }
+
+    #[test]
+    fn states_deposits_and_refunds() {
+        let credential =
+            key_credential!("bd3ae991b5aafccafe5ca70758bd36a9b2f872f57f6d3a1ffa0eb777");
+        let pool = Hash::from([1; 28]);
+
+        let certificate = Certificate::register_and_delegate(credential.clone(), pool, 2_000_000);
+        assert_eq!(certificate.deposit(), 2_000_000);
+        assert_eq!(certificate.refund(), 0);
+        assert_eq!(certificate.pool(), Some(pool));
+
+        let certificate = Certificate::deregister(credential, 2_000_000);
+        assert_eq!(certificate.deposit(), 0);
+        assert_eq!(certificate.refund(), 2_000_000);
+        assert!(!certificate.has_implicit_deposit());
+    }
+}
@@ -142,6 +142,29 @@ impl Credential {
     pub fn as_script(&self) -> Option<Hash<28>> {
         self.select(|_| None, Some)
     }
+
+    /// The bech32 representation of the bare credential, as per CIP-0005 (i.e. 'addr_vkh' for
+    /// keys, and 'script' for scripts). Unlike addresses, it carries no network.
+    ///
+    /// # examples
+    ///
+    /// ```rust
+    /// # use cardano_sdk::{script_credential};
+    /// assert!(
+    ///   script_credential!("bd3ae991b5aafccafe5ca70758bd36a9b2f872f57f6d3a1ffa0eb777")
+    ///     .to_bech32()
+    ///     .starts_with("script1")
+    /// );
+    /// ```
+    pub fn to_bech32(&self) -> String {
+        let (hrp, hash) = self.select(|hash| ("addr_vkh", hash), |hash| ("script", hash));
+        bech32::encode(
+            hrp,
+            bech32::ToBase32::to_base32(&hash.as_ref()),
+            bech32::Variant::Bech32,
+        )
+        .expect("invalid bech32 string")
+    }
 }
 
 // ----------------------------------------------------------- Converting (from)
@@ -1 +1,3 @@
+pub mod bip32;
+pub mod bip39;
 pub mod ed25519;
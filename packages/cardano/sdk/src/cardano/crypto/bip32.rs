@@ -0,0 +1,333 @@
+//  This Source Code Form is subject to the terms of the Mozilla Public
+//  License, v. 2.0. If a copy of the MPL was not distributed with this
+//  file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{Mnemonic, SigningKey, VerificationKey, pallas::Scrubbed};
+use anyhow::{Context, anyhow};
+use cryptoxide::{ed25519, hmac::Hmac, mac::Mac, pbkdf2::pbkdf2, sha2::Sha512};
+use std::{fmt, str::FromStr};
+
+/// Offset of hardened indices in a [`DerivationPath`].
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// A BIP32-Ed25519 extended signing key: an extended ed25519 key along with its chain code. That
+/// is, a node of an HD wallet, from which child keys are derived; as Cardano wallets do.
+#[derive(Clone)]
+pub struct ExtendedSigningKey([u8; 96]);
+
+impl fmt::Debug for ExtendedSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedSigningKey").finish_non_exhaustive()
+    }
+}
+
+impl Drop for ExtendedSigningKey {
+    fn drop(&mut self) {
+        self.0.scrub()
+    }
+}
+
+// -------------------------------------------------------------------- Building
+
+impl ExtendedSigningKey {
+    /// The root key of the wallet seeded by a mnemonic, as Icarus (and thereby most Cardano
+    /// wallets) computes it. The passphrase is empty unless the user explicitly set one.
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        let mut bytes = [0; 96];
+        pbkdf2(
+            &mut Hmac::new(Sha512::new(), passphrase.as_bytes()),
+            mnemonic.entropy(),
+            4096,
+            &mut bytes,
+        );
+        bytes[0] &= 0b1111_1000;
+        bytes[31] &= 0b0001_1111;
+        bytes[31] |= 0b0100_0000;
+        Self(bytes)
+    }
+
+    /// Derive the child key at the given index; hardened from [`HARDENED`] on.
+    pub fn derive(&self, index: u32) -> Self {
+        let (key, chain_code) = self.0.split_at(64);
+
+        let mut z = Hmac::new(Sha512::new(), chain_code);
+        let mut c = Hmac::new(Sha512::new(), chain_code);
+        if index >= HARDENED {
+            z.input(&[0x00]);
+            z.input(key);
+            c.input(&[0x01]);
+            c.input(key);
+        } else {
+            let public_key = self.public_key();
+            z.input(&[0x02]);
+            z.input(&public_key);
+            c.input(&[0x03]);
+            c.input(&public_key);
+        }
+        z.input(&index.to_le_bytes());
+        c.input(&index.to_le_bytes());
+
+        let mut z_bytes = [0; 64];
+        z.raw_result(&mut z_bytes);
+        let mut c_bytes = [0; 64];
+        c.raw_result(&mut c_bytes);
+
+        let mut child = [0; 96];
+        child[..32].copy_from_slice(&add_28_mul8(&key[..32], &z_bytes[..28]));
+        child[32..64].copy_from_slice(&add_256(&key[32..], &z_bytes[32..]));
+        child[64..].copy_from_slice(&c_bytes[32..]);
+        z_bytes.scrub();
+
+        Self(child)
+    }
+
+    /// Derive the key at the end of a path, from this one.
+    pub fn derive_path(&self, path: &DerivationPath) -> Self {
+        path.0
+            .iter()
+            .fold(self.clone(), |key, index| key.derive(*index))
+    }
+}
+
+// ------------------------------------------------------------- Converting (to)
+
+impl ExtendedSigningKey {
+    /// The signing key, without its chain code; so that it cannot derive further.
+    pub fn to_signing_key(&self) -> SigningKey {
+        let mut key = [0; 64];
+        key.copy_from_slice(&self.0[..64]);
+        SigningKey::try_from(key).expect("derived keys have valid bit tweaks")
+    }
+
+    pub fn to_verification_key(&self) -> VerificationKey {
+        VerificationKey::from(self.public_key())
+    }
+
+    pub fn chain_code(&self) -> [u8; 32] {
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(&self.0[64..]);
+        chain_code
+    }
+
+    fn public_key(&self) -> [u8; 32] {
+        let mut key = [0; 64];
+        key.copy_from_slice(&self.0[..64]);
+        let public_key = ed25519::extended_to_public(&key);
+        key.scrub();
+        public_key
+    }
+}
+
+/// `x + 8 * y`, on 256 bits, where `y` is 28 bytes long; so that the lower 3 bits of `x` remain
+/// cleared, and its highest bit does too for any realistic depth.
+fn add_28_mul8(x: &[u8], y: &[u8]) -> [u8; 32] {
+    let mut out = [0; 32];
+    let mut carry = 0u16;
+    for (ix, out) in out.iter_mut().enumerate() {
+        let y = y.get(ix).map(|y| u16::from(*y) << 3).unwrap_or_default();
+        let sum = u16::from(x[ix]) + y + carry;
+        *out = (sum & 0xFF) as u8;
+        carry = sum >> 8;
+    }
+    out
+}
+
+/// `x + y`, modulo 2^256.
+fn add_256(x: &[u8], y: &[u8]) -> [u8; 32] {
+    let mut out = [0; 32];
+    let mut carry = 0u16;
+    for (ix, out) in out.iter_mut().enumerate() {
+        let sum = u16::from(x[ix]) + u16::from(y[ix]) + carry;
+        *out = (sum & 0xFF) as u8;
+        carry = sum >> 8;
+    }
+    out
+}
+
+/// A path of derivation indices from a root key, such as `m/1852'/1815'/0'/0/0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+/// The role of keys within a CIP-1852 account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Keys of addresses shared with others, to receive funds.
+    External = 0,
+    /// Keys of change addresses.
+    Internal = 1,
+    /// Staking keys.
+    Stake = 2,
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+        for index in &self.0 {
+            if *index >= HARDENED {
+                write!(f, "/{}'", index - HARDENED)?;
+            } else {
+                write!(f, "/{index}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DerivationPath {
+    pub const PURPOSE: u32 = 1852;
+    pub const COIN_TYPE: u32 = 1815;
+
+    /// The path of a key as per CIP-1852: `m/1852'/1815'/<account>'/<role>/<index>`.
+    ///
+    /// # Panics
+    ///
+    /// When the account or the index is [`HARDENED`] or more.
+    pub fn cip1852(account: u32, role: Role, index: u32) -> Self {
+        assert!(account < HARDENED, "account out of range: {account}");
+        assert!(index < HARDENED, "index out of range: {index}");
+        Self(vec![
+            HARDENED + Self::PURPOSE,
+            HARDENED + Self::COIN_TYPE,
+            HARDENED + account,
+            role as u32,
+            index,
+        ])
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut segments = s.split('/');
+        if segments.next() != Some("m") {
+            return Err(anyhow!(
+                "invalid derivation path: {s}; should start with m/"
+            ));
+        }
+        segments
+            .map(|segment| {
+                let (index, hardened) = match segment.strip_suffix(['\'', 'h']) {
+                    Some(index) => (index, HARDENED),
+                    None => (segment, 0),
+                };
+                index
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|index| *index < HARDENED)
+                    .map(|index| index + hardened)
+                    .with_context(|| format!("invalid derivation path: {s}; at {segment}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NetworkId;
+
+    fn root() -> ExtendedSigningKey {
+        let mnemonic = Mnemonic::from_str(
+            "eight country switch draw meat scout mystery blade tip drift useless good keep usage title",
+        )
+        .unwrap();
+        ExtendedSigningKey::from_mnemonic(&mnemonic, "")
+    }
+
+    #[test]
+    fn derives_icarus_root_keys() {
+        // Test vector from CIP-0003.
+        assert_eq!(
+            hex::encode(root().0),
+            "c065afd2832cd8b087c4d9ab7011f481ee1e0721e78ea5dd609f3ab3f156d245\
+             d176bd8fd4ec60b4731c3918a2a72a0226c0cd119ec35b47e4d55884667f552a\
+             23f7fdcd4a10c6cd2c7393ac61d877873e248f417634aa3d812af327ffe9d620"
+        );
+    }
+
+    #[test]
+    fn derives_cip1852_addresses() {
+        // Test vectors from CIP-0019, whose keys are derived along CIP-1852 paths.
+        let mnemonic = Mnemonic::from_str(
+            "test walk nut penalty hip pave soap entry language right filter choice",
+        )
+        .unwrap();
+        let root = ExtendedSigningKey::from_mnemonic(&mnemonic, "");
+        let payment = root
+            .derive_path(&DerivationPath::cip1852(0, Role::External, 0))
+            .to_verification_key();
+        let stake = root
+            .derive_path(&DerivationPath::cip1852(0, Role::Stake, 0))
+            .to_verification_key();
+        assert_eq!(
+            payment.to_address(NetworkId::TESTNET).to_string(),
+            "addr_test1vz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzerspjrlsz"
+        );
+        assert_eq!(
+            payment
+                .to_address(NetworkId::TESTNET)
+                .with_delegation(stake.to_credential())
+                .to_string(),
+            "addr_test1qz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3jcu5d8ps7zex2k2xt3uqxgjqnnj83ws8lhrn648jjxtwq2ytjqp"
+        );
+    }
+
+    #[test]
+    fn derived_keys_sign() {
+        let path = DerivationPath::cip1852(0, Role::External, 0);
+        let key = root().derive_path(&path);
+        let signing_key = key.to_signing_key();
+        assert_eq!(signing_key.to_verification_key(), key.to_verification_key());
+        assert!(
+            key.to_verification_key()
+                .verify(b"konduit", &signing_key.sign(b"konduit"))
+        );
+        assert_ne!(
+            root()
+                .derive_path(&DerivationPath::cip1852(0, Role::Stake, 0))
+                .to_verification_key(),
+            key.to_verification_key(),
+        );
+    }
+
+    #[test]
+    fn soft_derivation_matches_public_derivation() {
+        use cryptoxide::curve25519::{Ge, Scalar};
+
+        let key = root().derive_path(&DerivationPath::cip1852(0, Role::External, 0));
+        let parent = key.public_key();
+
+        let mut z = Hmac::new(Sha512::new(), &key.chain_code());
+        z.input(&[0x02]);
+        z.input(&parent);
+        z.input(&42u32.to_le_bytes());
+        let mut z_bytes = [0; 64];
+        z.raw_result(&mut z_bytes);
+
+        let tweak =
+            Ge::scalarmult_base(&Scalar::from_bytes(&add_28_mul8(&[0; 32], &z_bytes[..28])));
+        // Decoding yields the opposite point; hence a subtraction.
+        let child = (&tweak - &Ge::from_bytes(&parent).unwrap().to_cached()).to_full();
+
+        assert_eq!(key.derive(42).public_key(), child.to_bytes());
+    }
+
+    #[test]
+    fn derivation_paths_round_trip_through_text() {
+        let path = DerivationPath::cip1852(3, Role::Stake, 7);
+        assert_eq!(path.to_string(), "m/1852'/1815'/3'/2/7");
+        assert_eq!(DerivationPath::from_str(&path.to_string()).unwrap(), path);
+        assert_eq!(
+            DerivationPath::from_str("m/1852h/1815h/3h/2/7").unwrap(),
+            path
+        );
+        assert!(DerivationPath::from_str("1852'/1815'").is_err());
+        assert!(DerivationPath::from_str("m/2147483648").is_err());
+    }
+}
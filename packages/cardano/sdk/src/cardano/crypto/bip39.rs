@@ -0,0 +1,189 @@
+//  This Source Code Form is subject to the terms of the Mozilla Public
+//  License, v. 2.0. If a copy of the MPL was not distributed with this
+//  file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::pallas::Scrubbed;
+use anyhow::anyhow;
+use cryptoxide::hashing::sha256;
+use rand_core::RngCore;
+use std::{fmt, str::FromStr, sync::LazyLock};
+
+/// The BIP39 English wordlist, in alphabetical order.
+static WORDLIST: LazyLock<Vec<&'static str>> =
+    LazyLock::new(|| include_str!("bip39/english.txt").lines().collect());
+
+/// A BIP39 mnemonic: 12 to 24 words from the English wordlist, encoding some entropy along with a
+/// checksum. The entropy is the seed of an HD wallet; see
+/// [`ExtendedSigningKey::from_mnemonic`](crate::ExtendedSigningKey::from_mnemonic).
+///
+/// The phrase is obtained through [`fmt::Display`], and parsed back through [`FromStr`]. It is
+/// as sensitive as a signing key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic(Vec<u8>);
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnemonic").finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (ix, word) in self.words().enumerate() {
+            if ix > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(word)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.0.as_mut_slice().scrub()
+    }
+}
+
+// -------------------------------------------------------------------- Building
+
+impl Mnemonic {
+    /// Sizes of entropy, in bytes, that BIP39 allows; for 12, 15, 18, 21 and 24 words.
+    pub const ENTROPY_SIZES: [usize; 5] = [16, 20, 24, 28, 32];
+
+    /// Generate a new 24-word mnemonic using available system entropy.
+    pub fn new() -> Self {
+        let mut entropy = vec![0u8; 32];
+        rand_core::OsRng.fill_bytes(&mut entropy);
+        Self(entropy)
+    }
+
+    pub fn from_entropy(entropy: &[u8]) -> anyhow::Result<Self> {
+        if !Self::ENTROPY_SIZES.contains(&entropy.len()) {
+            return Err(anyhow!(
+                "invalid mnemonic entropy: {} bytes, should be one of {:?}",
+                entropy.len(),
+                Self::ENTROPY_SIZES
+            ));
+        }
+        Ok(Self(entropy.to_vec()))
+    }
+}
+
+impl Default for Mnemonic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ------------------------------------------------------------------ Inspecting
+
+impl Mnemonic {
+    pub fn entropy(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &'static str> + '_ {
+        let checksum = checksum(&self.0);
+        let bits = move |ix: usize| -> usize {
+            let byte = self.0.get(ix / 8).copied().unwrap_or(checksum);
+            usize::from((byte >> (7 - ix % 8)) & 1)
+        };
+        (0..self.0.len() * 3 / 4).map(move |word| {
+            let index = (0..11).fold(0, |index, bit| (index << 1) | bits(word * 11 + bit));
+            WORDLIST[index]
+        })
+    }
+}
+
+// ----------------------------------------------------------- Converting (from)
+
+impl FromStr for Mnemonic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let indices = s
+            .split_whitespace()
+            .enumerate()
+            .map(|(ix, word)| {
+                WORDLIST
+                    .binary_search(&word.to_lowercase().as_str())
+                    .map_err(|_| anyhow!("invalid mnemonic: unknown word at position {}", ix + 1))
+            })
+            .collect::<anyhow::Result<Vec<usize>>>()?;
+
+        let size = indices.len() * 4 / 3;
+        if indices.len() % 3 != 0 || !Self::ENTROPY_SIZES.contains(&size) {
+            return Err(anyhow!(
+                "invalid mnemonic: {} words, should be 12, 15, 18, 21 or 24",
+                indices.len()
+            ));
+        }
+
+        let mut bytes = vec![0u8; size + 1];
+        for (ix, bit) in indices
+            .iter()
+            .flat_map(|index| (0..11).rev().map(move |bit| (index >> bit) & 1 == 1))
+            .enumerate()
+        {
+            if bit {
+                bytes[ix / 8] |= 1 << (7 - ix % 8);
+            }
+        }
+
+        let expected = bytes.pop().unwrap_or_default();
+        let mnemonic = Self(bytes);
+        if checksum(&mnemonic.0) != expected {
+            return Err(anyhow!("invalid mnemonic: checksum mismatch"));
+        }
+
+        Ok(mnemonic)
+    }
+}
+
+/// The checksum bits, left-aligned in a byte: the first bits of the SHA-256 of the entropy. There
+/// are at most 8 of them, one per 4 bytes of entropy.
+fn checksum(entropy: &[u8]) -> u8 {
+    sha256(entropy)[0] & (0xFF << (8 - entropy.len() / 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wordlist_is_complete_and_sorted() {
+        assert_eq!(WORDLIST.len(), 2048);
+        assert!(WORDLIST.is_sorted());
+    }
+
+    #[test]
+    fn round_trips_through_words() {
+        let mnemonic = Mnemonic::from_entropy(&[0; 16]).unwrap();
+        assert_eq!(
+            mnemonic.to_string(),
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        );
+
+        let phrase = "eight country switch draw meat scout mystery blade tip drift useless good keep usage title";
+        let mnemonic = Mnemonic::from_str(phrase).unwrap();
+        assert_eq!(
+            hex::encode(mnemonic.entropy()),
+            "46e62370a138a182a498b8e2885bc032379ddf38"
+        );
+        assert_eq!(mnemonic.to_string(), phrase);
+
+        let mnemonic = Mnemonic::new();
+        assert_eq!(mnemonic.words().count(), 24);
+        assert_eq!(Mnemonic::from_str(&mnemonic.to_string()).unwrap(), mnemonic);
+    }
+
+    #[test]
+    fn rejects_invalid_phrases() {
+        let phrase = "abandon ".repeat(11);
+        assert!(Mnemonic::from_str(&format!("{phrase} about")).is_ok());
+        assert!(Mnemonic::from_str(&format!("{phrase} abandon")).is_err());
+        assert!(Mnemonic::from_str(&format!("{phrase} konduit")).is_err());
+        assert!(Mnemonic::from_str(&phrase).is_err());
+    }
+}
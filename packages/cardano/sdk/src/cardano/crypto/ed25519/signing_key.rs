@@ -2,32 +2,51 @@
 //  License, v. 2.0. If a copy of the MPL was not distributed with this
 //  file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::{Signature, VerificationKey, pallas::ed25519};
+use crate::{
+    DerivationPath, ExtendedSigningKey, Mnemonic, Signature, VerificationKey,
+    crypto::bip32::{HARDENED, Role},
+    pallas::ed25519,
+};
 use anyhow::anyhow;
 use rand_core::RngCore;
 use std::str::FromStr;
 
-/// An ed25519 signing key (non-extended).
+/// An ed25519 signing key: either a standard one, or an extended one as derived in HD wallets; see
+/// [`ExtendedSigningKey`](crate::ExtendedSigningKey).
 #[derive(Debug, Clone)]
 #[repr(transparent)]
-pub struct SigningKey(ed25519::SecretKey);
+pub struct SigningKey(Inner);
+
+#[derive(Debug, Clone)]
+enum Inner {
+    Standard(ed25519::SecretKey),
+    Extended(ed25519::SecretKeyExtended),
+}
 
 // ----------------------------------------------------------------------- Using
 
 impl SigningKey {
     pub const SIZE: usize = ed25519::SecretKey::SIZE;
+    pub const EXTENDED_SIZE: usize = ed25519::SecretKeyExtended::SIZE;
 
-    /// Generate a new signing key using available system entropy.
+    /// Generate a new (standard) signing key using available system entropy.
     pub fn new() -> Self {
         let mut bytes = [0u8; 32];
         rand_core::OsRng.fill_bytes(&mut bytes);
         Self::from(bytes)
     }
 
+    pub fn is_extended(&self) -> bool {
+        matches!(self.0, Inner::Extended(..))
+    }
+
     /// Convert the [`SecretKey`] into its compressed byte composition, and leak its bytes into
     /// memory. Only use for storing the key securely or for testing. Additional precautions are
     /// needed to ensure that the leaked bytes are properly de-allocated and cleared.
     ///
+    /// The bytes are [`Self::SIZE`] long for standard keys, and [`Self::EXTENDED_SIZE`] long for
+    /// extended ones.
+    ///
     /// # Safety
     ///
     /// This function is not safe because:
@@ -42,17 +61,25 @@ impl SigningKey {
     /// # use cardano_sdk::SigningKey;
     /// let key: SigningKey = // ...
     /// # [0; SigningKey::SIZE].into() ;
-    /// let _: [u8; SigningKey::SIZE] = unsafe { SigningKey::leak(key) };
+    /// let _: Vec<u8> = unsafe { SigningKey::leak(key) };
     #[inline]
-    pub unsafe fn leak(key: Self) -> [u8; 32] {
-        unsafe { ed25519::SecretKey::leak_into_bytes(key.0) }
+    pub unsafe fn leak(key: Self) -> Vec<u8> {
+        match key.0 {
+            Inner::Standard(key) => unsafe { ed25519::SecretKey::leak_into_bytes(key) }.to_vec(),
+            Inner::Extended(key) => {
+                unsafe { ed25519::SecretKeyExtended::leak_into_bytes(key) }.to_vec()
+            }
+        }
     }
 
     pub fn sign<T>(&self, msg: T) -> Signature
     where
         T: AsRef<[u8]>,
     {
-        Signature::from(self.0.sign(msg))
+        match &self.0 {
+            Inner::Standard(key) => Signature::from(key.sign(msg)),
+            Inner::Extended(key) => Signature::from(key.sign(msg)),
+        }
     }
 }
 
@@ -64,18 +91,54 @@ impl Default for SigningKey {
 
 // ----------------------------------------------------------- Converting (from)
 
+impl SigningKey {
+    /// The payment key of an account of the wallet seeded by a mnemonic; that is, the first
+    /// external key of the account as per CIP-1852, at `m/1852'/1815'/<account>'/0/0`. Other
+    /// Cardano wallets restored from the same mnemonic hold that key too.
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        account: u32,
+    ) -> anyhow::Result<Self> {
+        if account >= HARDENED {
+            return Err(anyhow!(
+                "invalid account: {account}; should be below {HARDENED}"
+            ));
+        }
+        Ok(ExtendedSigningKey::from_mnemonic(mnemonic, passphrase)
+            .derive_path(&DerivationPath::cip1852(account, Role::External, 0))
+            .to_signing_key())
+    }
+}
+
 impl From<[u8; 32]> for SigningKey {
     fn from(bytes: [u8; 32]) -> Self {
-        Self(ed25519::SecretKey::from(bytes))
+        Self(Inner::Standard(ed25519::SecretKey::from(bytes)))
+    }
+}
+
+impl TryFrom<[u8; 64]> for SigningKey {
+    type Error = anyhow::Error;
+
+    fn try_from(bytes: [u8; 64]) -> anyhow::Result<Self> {
+        let key = ed25519::SecretKeyExtended::from_bytes(bytes)
+            .map_err(|e| anyhow!(e).context("invalid extended signing key"))?;
+        Ok(Self(Inner::Extended(key)))
     }
 }
 
 impl TryFrom<Vec<u8>> for SigningKey {
     type Error = anyhow::Error;
 
+    /// A standard key from 32 bytes, or an extended one from 64 bytes.
     fn try_from(bytes: Vec<u8>) -> anyhow::Result<Self> {
-        let array = <[u8; 32]>::try_from(bytes).map_err(|_| anyhow!("invalid signing key"))?;
-        Ok(Self::from(array))
+        match bytes.len() {
+            Self::SIZE => Ok(Self::from(<[u8; Self::SIZE]>::try_from(bytes.as_slice())?)),
+            Self::EXTENDED_SIZE => {
+                Self::try_from(<[u8; Self::EXTENDED_SIZE]>::try_from(bytes.as_slice())?)
+            }
+            _ => Err(anyhow!("invalid signing key")),
+        }
     }
 }
 
@@ -86,9 +149,7 @@ impl FromStr for SigningKey {
         let bytes =
             hex::decode(s).map_err(|e| anyhow!(e).context("malformed base16 signing key"))?;
 
-        let array = <[u8; 32]>::try_from(bytes).map_err(|_| anyhow!("invalid signing key"))?;
-
-        Ok(Self::from(array))
+        Self::try_from(bytes)
     }
 }
 
@@ -102,6 +163,9 @@ impl SigningKey {
 
 impl From<&SigningKey> for VerificationKey {
     fn from(key: &SigningKey) -> Self {
-        VerificationKey::from(key.0.public_key())
+        match &key.0 {
+            Inner::Standard(key) => VerificationKey::from(key.public_key()),
+            Inner::Extended(key) => VerificationKey::from(key.public_key()),
+        }
     }
 }
@@ -0,0 +1,284 @@
+//  This Source Code Form is subject to the terms of the Mozilla Public
+//  License, v. 2.0. If a copy of the MPL was not distributed with this
+//  file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{cbor, pallas};
+use std::fmt;
+
+/// A value of transaction metadata, as attached under a label with
+/// [`Transaction::with_metadata`](crate::Transaction::with_metadata).
+///
+/// It can be constructed directly from integers, text or bytes, and combined using:
+///
+/// - [`Self::list`]
+/// - [`Self::map`]
+///
+/// <div class="warning">The ledger caps text and bytes at 64 bytes each; see
+/// [`Self::MAX_CHUNK_SIZE`]. Longer texts are better split using [`Self::chunks`].</div>
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct Metadatum(pallas::Metadatum);
+
+impl fmt::Display for Metadatum {
+    /// A JSON-like rendering, with bytes in hexadecimal.
+    ///
+    /// # examples
+    ///
+    /// ```rust
+    /// # use cardano_sdk::Metadatum;
+    /// assert_eq!(
+    ///     Metadatum::map([("msg", Metadatum::list(["hello", "world"]))]).to_string(),
+    ///     r#"{"msg": ["hello", "world"]}"#,
+    /// );
+    ///
+    /// assert_eq!(
+    ///     Metadatum::list([Metadatum::from(42), Metadatum::bytes(b"foo")]).to_string(),
+    ///     "[42, 0x666f6f]",
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            pallas::Metadatum::Int(i) => write!(f, "{}", i128::from(*i)),
+            pallas::Metadatum::Bytes(bytes) => write!(f, "0x{}", hex::encode(&bytes[..])),
+            pallas::Metadatum::Text(text) => write!(f, "{text:?}"),
+            pallas::Metadatum::Array(elems) => {
+                write!(f, "[")?;
+                for (ix, elem) in elems.iter().enumerate() {
+                    if ix > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", Self(elem.clone()))?;
+                }
+                write!(f, "]")
+            }
+            pallas::Metadatum::Map(kvs) => {
+                write!(f, "{{")?;
+                for (ix, (k, v)) in kvs.iter().enumerate() {
+                    if ix > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", Self(k.clone()), Self(v.clone()))?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------- Building
+
+impl Metadatum {
+    /// The maximum size, in bytes, of text and bytes values.
+    pub const MAX_CHUNK_SIZE: usize = 64;
+
+    pub fn text(text: impl Into<String>) -> Self {
+        Self(pallas::Metadatum::Text(text.into()))
+    }
+
+    pub fn bytes(bytes: impl AsRef<[u8]>) -> Self {
+        Self(pallas::Metadatum::Bytes(bytes.as_ref().to_vec().into()))
+    }
+
+    pub fn list<T: Into<Self>>(elems: impl IntoIterator<Item = T>) -> Self {
+        Self(pallas::Metadatum::Array(
+            elems
+                .into_iter()
+                .map(|elem| elem.into().0)
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    pub fn map<K: Into<Self>, V: Into<Self>>(kvs: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self(pallas::Metadatum::Map(pallas::KeyValuePairs::from(
+            kvs.into_iter()
+                .map(|(k, v)| (k.into().0, v.into().0))
+                .collect::<Vec<_>>(),
+        )))
+    }
+
+    /// A text of any length, as a list of texts within [`Self::MAX_CHUNK_SIZE`], split on
+    /// character boundaries.
+    ///
+    /// # examples
+    ///
+    /// ```rust
+    /// # use cardano_sdk::Metadatum;
+    /// let text = "é".repeat(40);
+    /// let chunks = Metadatum::chunks(&text);
+    /// assert_eq!(chunks.as_list().unwrap().count(), 2);
+    /// assert_eq!(chunks.as_chunks().unwrap(), text);
+    /// ```
+    pub fn chunks(text: &str) -> Self {
+        let mut chunks = vec![];
+        let mut rest = text;
+        while !rest.is_empty() {
+            let mut end = rest.len().min(Self::MAX_CHUNK_SIZE);
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+            let (chunk, tail) = rest.split_at(end);
+            chunks.push(Self::text(chunk));
+            rest = tail;
+        }
+        Self::list(chunks)
+    }
+}
+
+// ------------------------------------------------------------------ Inspecting
+
+impl Metadatum {
+    pub fn as_integer(&self) -> Option<i128> {
+        match &self.0 {
+            pallas::Metadatum::Int(i) => Some(i128::from(*i)),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match &self.0 {
+            pallas::Metadatum::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match &self.0 {
+            pallas::Metadatum::Bytes(bytes) => Some(&bytes[..]),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<impl Iterator<Item = Self> + '_> {
+        match &self.0 {
+            pallas::Metadatum::Array(elems) => Some(elems.iter().cloned().map(Self)),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<impl Iterator<Item = (Self, Self)> + '_> {
+        match &self.0 {
+            pallas::Metadatum::Map(kvs) => {
+                Some(kvs.iter().map(|(k, v)| (Self(k.clone()), Self(v.clone()))))
+            }
+            _ => None,
+        }
+    }
+
+    /// The text split by [`Self::chunks`], or a mere text.
+    pub fn as_chunks(&self) -> Option<String> {
+        if let Some(text) = self.as_text() {
+            return Some(text.to_string());
+        }
+        self.as_list()?
+            .map(|chunk| chunk.as_text().map(str::to_string))
+            .collect()
+    }
+
+    /// Whether texts and bytes are all within [`Self::MAX_CHUNK_SIZE`], as the ledger requires.
+    pub fn is_within_limits(&self) -> bool {
+        match &self.0 {
+            pallas::Metadatum::Int(_) => true,
+            pallas::Metadatum::Bytes(bytes) => bytes.len() <= Self::MAX_CHUNK_SIZE,
+            pallas::Metadatum::Text(text) => text.len() <= Self::MAX_CHUNK_SIZE,
+            pallas::Metadatum::Array(_) => self
+                .as_list()
+                .into_iter()
+                .flatten()
+                .all(|elem| elem.is_within_limits()),
+            pallas::Metadatum::Map(_) => self
+                .as_map()
+                .into_iter()
+                .flatten()
+                .all(|(k, v)| k.is_within_limits() && v.is_within_limits()),
+        }
+    }
+}
+
+// ----------------------------------------------------------- Converting (from)
+
+impl From<pallas::Metadatum> for Metadatum {
+    fn from(metadatum: pallas::Metadatum) -> Self {
+        Self(metadatum)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl From<$t> for Metadatum {
+                #[inline]
+                fn from(int: $t) -> Self {
+                    Self(pallas::Metadatum::Int(
+                        pallas::Int::try_from(int as i128).expect("fits in a CBOR integer"),
+                    ))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl From<&str> for Metadatum {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for Metadatum {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<Vec<u8>> for Metadatum {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::bytes(bytes)
+    }
+}
+
+impl From<&[u8]> for Metadatum {
+    fn from(bytes: &[u8]) -> Self {
+        Self::bytes(bytes)
+    }
+}
+
+// ------------------------------------------------------------- Converting (to)
+
+impl From<Metadatum> for pallas::Metadatum {
+    fn from(metadatum: Metadatum) -> Self {
+        metadatum.0
+    }
+}
+
+// -------------------------------------------------------------------- Encoding
+
+impl<C> cbor::Encode<C> for Metadatum {
+    fn encode<W: cbor::encode::write::Write>(
+        &self,
+        e: &mut cbor::Encoder<W>,
+        ctx: &mut C,
+    ) -> Result<(), cbor::encode::Error<W::Error>> {
+        e.encode_with(&self.0, ctx)?;
+        Ok(())
+    }
+}
+
+impl<'d, C> cbor::Decode<'d, C> for Metadatum {
+    fn decode(d: &mut cbor::Decoder<'d>, ctx: &mut C) -> Result<Self, cbor::decode::Error> {
+        Ok(Self(d.decode_with(ctx)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enforces_the_ledger_chunk_size() {
+        assert!(Metadatum::text("a".repeat(64)).is_within_limits());
+        assert!(!Metadatum::text("a".repeat(65)).is_within_limits());
+        assert!(!Metadatum::map([("msg", Metadatum::bytes([0; 65]))]).is_within_limits());
+        assert!(Metadatum::chunks(&"a".repeat(200)).is_within_limits());
+    }
+}
@@ -1,8 +1,12 @@
 use crate::{NetworkId, ProtocolParameters, cbor, cbor as minicbor};
-use anyhow::anyhow;
-use std::fmt;
+use anyhow::{Context, anyhow};
+use std::{fmt, str::FromStr};
 
-#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+/// A Cardano network: one of the public networks, or a custom one such as a local devnet.
+///
+/// Custom networks carry their own slot configuration. They are assumed to start directly in the
+/// Shelley era (or later), as devnets do: slot 0 begins at the system start. Their textual form is
+/// `custom:<magic>:<system start, in POSIX seconds>[:<slot length, in milliseconds>]`.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, cbor::Encode, cbor::Decode)]
@@ -13,21 +17,40 @@ pub enum Network {
     Preprod,
     #[n(2)]
     Preview,
+    #[n(3)]
+    Custom {
+        #[n(0)]
+        magic: u64,
+        /// The POSIX time of slot 0, in seconds.
+        #[n(1)]
+        system_start: u64,
+        /// The length of a slot, in milliseconds.
+        #[n(2)]
+        slot_length: u64,
+    },
 }
 
 impl Network {
     pub const MAINNET_MAGIC: u64 = 764824073;
     pub const PREPROD_MAGIC: u64 = 1;
     pub const PREVIEW_MAGIC: u64 = 2;
+
+    /// The slot length of public networks since Shelley, in milliseconds.
+    pub const SLOT_LENGTH: u64 = 1000;
 }
 
 impl fmt::Display for Network {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str(match self {
-            Self::Mainnet => "mainnet",
-            Self::Preprod => "preprod",
-            Self::Preview => "preview",
-        })
+        match self {
+            Self::Mainnet => f.write_str("mainnet"),
+            Self::Preprod => f.write_str("preprod"),
+            Self::Preview => f.write_str("preview"),
+            Self::Custom {
+                magic,
+                system_start,
+                slot_length,
+            } => write!(f, "custom:{magic}:{system_start}:{slot_length}"),
+        }
     }
 }
 
@@ -37,16 +60,27 @@ impl From<Network> for u64 {
             Network::Mainnet => Network::MAINNET_MAGIC,
             Network::Preprod => Network::PREPROD_MAGIC,
             Network::Preview => Network::PREVIEW_MAGIC,
+            Network::Custom { magic, .. } => magic,
         }
     }
 }
 
 impl From<Network> for ProtocolParameters {
+    /// Default parameters for the network. Those of custom networks are Mainnet's, with their own
+    /// slot configuration.
     fn from(network: Network) -> ProtocolParameters {
         match network {
             Network::Mainnet => Self::mainnet(),
             Network::Preprod => Self::preprod(),
             Network::Preview => Self::preview(),
+            Network::Custom {
+                system_start,
+                slot_length,
+                ..
+            } => Self::mainnet()
+                .with_start_time(system_start)
+                .with_first_shelley_slot(0)
+                .with_slot_length(slot_length),
         }
     }
 }
@@ -78,8 +112,16 @@ impl TryFrom<&str> for Network {
             mainnet if match_str(mainnet, Self::Mainnet) => Ok(Self::Mainnet),
             preprod if match_str(preprod, Self::Preprod) => Ok(Self::Preprod),
             preview if match_str(preview, Self::Preview) => Ok(Self::Preview),
+            custom if custom.to_lowercase().starts_with("custom:") => Self::custom_from_str(
+                &custom["custom:".len()..],
+            )
+            .with_context(|| {
+                format!(
+                    "invalid custom network: {text}; should be custom:<magic>:<system start>[:<slot length>]"
+                )
+            }),
             _ => Err(anyhow!(
-                "unsupported network: {text}; should be one of {}, {}, {}",
+                "unsupported network: {text}; should be one of {}, {}, {} or custom:<magic>:<system start>[:<slot length>]",
                 Self::Mainnet,
                 Self::Preprod,
                 Self::Preview
@@ -96,6 +138,66 @@ impl TryFrom<String> for Network {
     }
 }
 
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Self> {
+        Self::try_from(text)
+    }
+}
+
+#[cfg(feature = "clap")]
+impl clap::builder::ValueParserFactory for Network {
+    type Parser = clap::builder::ValueParser;
+
+    fn value_parser() -> Self::Parser {
+        clap::builder::ValueParser::new(|text: &str| Self::try_from(text))
+    }
+}
+
+impl Network {
+    fn custom_from_str(text: &str) -> anyhow::Result<Self> {
+        let mut parts = text.split(':');
+        let mut next = |what: &str| {
+            parts.next().map(|part| {
+                part.parse::<u64>()
+                    .with_context(|| format!("invalid {what}"))
+            })
+        };
+
+        let magic = next("magic").ok_or_else(|| anyhow!("missing magic"))??;
+        let system_start =
+            next("system start").ok_or_else(|| anyhow!("missing system start"))??;
+        let slot_length = next("slot length")
+            .transpose()?
+            .unwrap_or(Self::SLOT_LENGTH);
+
+        if slot_length == 0 {
+            return Err(anyhow!("slot length must be positive"));
+        }
+
+        if parts.next().is_some() {
+            return Err(anyhow!("unexpected trailing segments"));
+        }
+
+        if [
+            Self::MAINNET_MAGIC,
+            Self::PREPROD_MAGIC,
+            Self::PREVIEW_MAGIC,
+        ]
+        .contains(&magic)
+        {
+            return Err(anyhow!("magic {magic} is that of a public network"));
+        }
+
+        Ok(Self::Custom {
+            magic,
+            system_start,
+            slot_length,
+        })
+    }
+}
+
 // ------------------------------------------------------------------ Inspecting
 
 impl Network {
@@ -106,4 +208,53 @@ impl Network {
     pub fn is_testnet(&self) -> bool {
         !self.is_mainnet()
     }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Network::Custom { .. })
+    }
+
+    pub fn magic(&self) -> u64 {
+        u64::from(*self)
+    }
+
+    /// The slot length, in milliseconds.
+    pub fn slot_length(&self) -> u64 {
+        match self {
+            Network::Custom { slot_length, .. } => *slot_length,
+            _ => Self::SLOT_LENGTH,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_networks_round_trip_through_text() {
+        let network = Network::try_from("custom:42:1700000000:200").unwrap();
+        assert_eq!(
+            network,
+            Network::Custom {
+                magic: 42,
+                system_start: 1700000000,
+                slot_length: 200,
+            }
+        );
+        assert_eq!(Network::try_from(network.to_string()).unwrap(), network);
+        assert_eq!(NetworkId::from(network), NetworkId::TESTNET);
+    }
+
+    #[test]
+    fn custom_slot_length_defaults_to_a_second() {
+        assert_eq!(
+            Network::try_from("custom:42:1700000000")
+                .unwrap()
+                .slot_length(),
+            1000
+        );
+        assert!(Network::try_from("custom:42").is_err());
+        assert!(Network::try_from("custom:42:1700000000:0").is_err());
+        assert!(Network::try_from("custom:1:1700000000").is_err());
+    }
 }
@@ -53,6 +53,9 @@ pub struct ProtocolParameters {
     /// The first (not-necessarily active) slot of the Shelley era.
     #[n(10)]
     first_shelley_slot: u64,
+    /// The length of slots since the Shelley era, in milliseconds; a second when unspecified.
+    #[n(11)]
+    slot_length: Option<u64>,
 }
 type PlutusV3CostModel = Vec<i64>;
 
@@ -121,6 +124,7 @@ impl Default for ProtocolParameters {
             plutus_v3_cost_model: vec![],
             start_time: 0,
             first_shelley_slot: 0,
+            slot_length: None,
         }
     }
 }
@@ -171,12 +175,22 @@ impl ProtocolParameters {
 
     /// Translate a posix time to a slot number for that particular network.
     pub fn posix_to_slot(&self, posix: std::time::Duration) -> u64 {
-        let delta = posix.as_secs() - self.start_time;
+        let slot_config = uplc::tx::SlotConfig::from(self);
 
-        let byron_duration = 20 * self.first_shelley_slot;
-        let since_shelley_duration = delta - byron_duration;
+        let since_shelley = posix.as_millis() as u64 - slot_config.zero_time;
 
-        self.first_shelley_slot + since_shelley_duration
+        slot_config.zero_slot + since_shelley / slot_config.slot_length as u64
+    }
+
+    /// Translate a slot number to the posix time at which it starts, for that particular network.
+    pub fn slot_to_posix(&self, slot: u64) -> std::time::Duration {
+        let slot_config = uplc::tx::SlotConfig::from(self);
+
+        let since_shelley = slot.saturating_sub(slot_config.zero_slot);
+
+        std::time::Duration::from_millis(
+            slot_config.zero_time + since_shelley * slot_config.slot_length as u64,
+        )
     }
 
     /// Specify the multiplier fee coefficient on the size of transactions, in lovelace/bytes
@@ -248,6 +262,12 @@ impl ProtocolParameters {
         self
     }
 
+    /// Specify the length of slots since the Shelley era, in milliseconds.
+    pub fn with_slot_length(mut self, slot_length: u64) -> Self {
+        self.slot_length = Some(slot_length);
+        self
+    }
+
     /// Specify the cost model for Plutus version 3, ordered according to the [_"spec"_](https://github.com/IntersectMBO/plutus/blob/b12c894833cae725bab11577c845701aeb05dc97/plutus-ledger-api/CostModel/Params/CostModelParams/costModelParamNames.golden.txt)
     pub fn with_plutus_v3_cost_model(mut self, cost_model: PlutusV3CostModel) -> Self {
         self.plutus_v3_cost_model = cost_model;
@@ -297,6 +317,11 @@ impl ProtocolParameters {
     pub fn plutus_v3_cost_model(&self) -> &PlutusV3CostModel {
         &self.plutus_v3_cost_model
     }
+
+    /// The length of slots since the Shelley era, in milliseconds.
+    pub fn slot_length(&self) -> u64 {
+        self.slot_length.unwrap_or(1000)
+    }
 }
 
 // -------------------------------------------------------------- Converting (to)
@@ -305,7 +330,7 @@ impl From<&ProtocolParameters> for uplc::tx::SlotConfig {
     fn from(params: &ProtocolParameters) -> Self {
         let byron_slot_length = 20; // in seconds
         Self {
-            slot_length: 1000, // Shelley slot length, in milliseconds
+            slot_length: params.slot_length() as u32,
             zero_slot: params.first_shelley_slot,
             zero_time: (params.start_time + byron_slot_length * params.first_shelley_slot) * 1000,
         }
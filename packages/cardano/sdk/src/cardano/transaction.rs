@@ -3,9 +3,10 @@
 //  file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::{
-    Address, BoxedIterator, ChangeStrategy, ExecutionUnits, Hash, Input, NetworkId, Output,
-    PlutusData, PlutusScript, PlutusVersion, ProtocolParameters, RedeemerPointer, Signature,
-    SigningKey, SlotBound, Value, VerificationKey, cbor, pallas, pretty,
+    Address, BoxedIterator, Certificate, ChangeStrategy, Credential, ExecutionUnits, Hash, Input,
+    Metadatum, NetworkId, Output, PlutusData, PlutusScript, PlutusVersion, ProtocolParameters,
+    RedeemerPointer, Signature, SigningKey, SlotBound, Value, VerificationKey, cbor, pallas,
+    pretty,
 };
 use anyhow::anyhow;
 use itertools::Itertools;
@@ -16,6 +17,7 @@ use std::{
     marker::PhantomData,
     mem,
     ops::Deref,
+    str::FromStr,
 };
 
 #[cfg(feature = "serde")]
@@ -23,6 +25,10 @@ use serde::{Deserialize, Serialize};
 
 mod builder;
 pub mod state;
+
+/// The metadata label of transaction messages, as per
+/// [CIP-20](https://cips.cardano.org/cip/CIP-0020).
+pub const MESSAGE_LABEL: u64 = 674;
 pub use state::IsTransactionBodyState;
 
 /// A transaction, either under construction or fully signed.
@@ -35,8 +41,8 @@ pub use state::IsTransactionBodyState;
 /// Then it reaches the [`state::ReadyForSigning`] which enables the method [`Self::sign`], and
 /// forbid any method that modifies the transaction body.
 ///
-/// Note that [`Self::build`] is currently the only way by which one can get a transaction in the
-/// [`state::ReadyForSigning`].
+/// A transaction reaches the [`state::ReadyForSigning`] either through [`Self::build`], or by
+/// decoding one built elsewhere from its CBOR, through `TryFrom<&[u8]>` or [`FromStr`].
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Transaction<State: IsTransactionBodyState> {
@@ -198,6 +204,66 @@ impl Transaction<state::InConstruction> {
         self
     }
 
+    /// Publish certificates, in the given order. Those witnessed by a script must come with a
+    /// redeemer.
+    pub fn with_certificates(
+        &mut self,
+        certificates: impl IntoIterator<Item = (Certificate, Option<PlutusData<'static>>)>,
+    ) -> &mut Self {
+        let mut redeemers = BTreeMap::new();
+
+        self.inner.transaction_body.certificates = pallas::NonEmptySet::from_vec(
+            certificates
+                .into_iter()
+                .enumerate()
+                .map(|(ix, (certificate, redeemer))| {
+                    if let Some(data) = redeemer {
+                        redeemers.insert(RedeemerPointer::from_publish(ix as u32), data);
+                    }
+
+                    pallas::Certificate::from(certificate)
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        self.with_redeemers(|tag| matches!(tag, pallas::RedeemerTag::Cert), redeemers);
+
+        self
+    }
+
+    /// Withdraw rewards from the reward accounts of stake credentials, on the given network. The
+    /// ledger only accepts withdrawals of the whole balance of an account. Those of scripts must
+    /// come with a redeemer.
+    pub fn with_withdrawals(
+        &mut self,
+        network_id: NetworkId,
+        withdrawals: impl IntoIterator<Item = (Credential, u64, Option<PlutusData<'static>>)>,
+    ) -> &mut Self {
+        let mut redeemers = BTreeMap::new();
+
+        self.inner.transaction_body.withdrawals = pallas::NonEmptyKeyValuePairs::from_vec(
+            withdrawals
+                .into_iter()
+                // Redeemers point at reward accounts as the ledger orders them: scripts first.
+                .sorted_by_key(|(credential, _, _)| {
+                    credential.select(|hash| (1, hash), |hash| (0, hash))
+                })
+                .enumerate()
+                .map(|(ix, (credential, amount, redeemer))| {
+                    if let Some(data) = redeemer {
+                        redeemers.insert(RedeemerPointer::from_withdraw(ix as u32), data);
+                    }
+
+                    (into_reward_account(network_id, &credential), amount)
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        self.with_redeemers(|tag| matches!(tag, pallas::RedeemerTag::Reward), redeemers);
+
+        self
+    }
+
     pub fn with_validity_interval(&mut self, from: SlotBound, until: SlotBound) -> &mut Self {
         // In Conway, the lower-bound is *inclusive* while the upper-bound is *exclusive*; so we
         // must match what the user set to get the right serialisation.
@@ -225,6 +291,63 @@ impl Transaction<state::InConstruction> {
         self
     }
 
+    /// Attach a metadatum under a label, replacing any already under it. The auxiliary data hash
+    /// is kept in sync with the metadata.
+    pub fn with_metadata(&mut self, label: u64, metadatum: impl Into<Metadatum>) -> &mut Self {
+        let mut metadata = self.metadata();
+        metadata.insert(label, metadatum.into());
+        let metadata = pallas::Metadata::from(
+            metadata
+                .into_iter()
+                .map(|(label, metadatum)| (label, pallas::Metadatum::from(metadatum)))
+                .collect::<Vec<_>>(),
+        );
+
+        let auxiliary_data =
+            match mem::replace(&mut self.inner.auxiliary_data, pallas::Nullable::Null) {
+                pallas::Nullable::Some(pallas::AuxiliaryData::ShelleyMa(data)) => {
+                    pallas::AuxiliaryData::ShelleyMa(pallas::alonzo::ShelleyMaAuxiliaryData {
+                        transaction_metadata: metadata,
+                        ..data
+                    })
+                }
+                pallas::Nullable::Some(pallas::AuxiliaryData::PostAlonzo(data)) => {
+                    pallas::AuxiliaryData::PostAlonzo(pallas::alonzo::PostAlonzoAuxiliaryData {
+                        metadata: Some(metadata),
+                        ..data
+                    })
+                }
+                _ => pallas::AuxiliaryData::Shelley(metadata),
+            };
+
+        let mut bytes = Vec::new();
+        let _ = cbor::encode(&auxiliary_data, &mut bytes);
+        self.inner.transaction_body.auxiliary_data_hash = Some(pallas::Bytes::from(
+            pallas::Hasher::<256>::hash(&bytes).to_vec(),
+        ));
+        self.inner.auxiliary_data = pallas::Nullable::Some(auxiliary_data);
+        self
+    }
+
+    /// Attach a message, as per [CIP-20](https://cips.cardano.org/cip/CIP-0020): each line under
+    /// the label 674, split in chunks the ledger accepts.
+    pub fn with_message<'a>(&mut self, lines: impl IntoIterator<Item = &'a str>) -> &mut Self {
+        let chunks = lines
+            .into_iter()
+            .flat_map(|line| {
+                Metadatum::chunks(line)
+                    .as_list()
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        self.with_metadata(
+            MESSAGE_LABEL,
+            Metadatum::map([("msg", Metadatum::list(chunks))]),
+        )
+    }
+
     pub fn with_datums(
         &mut self,
         datums: impl IntoIterator<Item = PlutusData<'static>>,
@@ -286,11 +409,19 @@ impl Transaction<state::InConstruction> {
 // -------------------------------------------------------------------- Signing
 
 impl Transaction<state::ReadyForSigning> {
+    /// Sign with a standard or an extended key; the latter being how keys derived from a
+    /// [`Mnemonic`](crate::Mnemonic) sign.
     pub fn sign(&mut self, signing_key: &SigningKey) -> &mut Self {
         self.sign_with(|msg| (signing_key.to_verification_key(), signing_key.sign(msg)))
     }
 
-    /// Like 'sign', but allows signing through a callback to avoid leaking the signing key.
+    /// Like 'sign', but allows signing through a callback to avoid leaking the signing key. Any
+    /// ed25519 signature works, including those of BIP32-Ed25519 extended keys; for example, with
+    /// an [`ExtendedSigningKey`](crate::ExtendedSigningKey):
+    ///
+    /// ```rust,ignore
+    /// transaction.sign_with(|msg| (key.to_verification_key(), key.to_signing_key().sign(msg)));
+    /// ```
     pub fn sign_with<
         VerificationKeyLike: Borrow<VerificationKey>,
         SignatureLike: Borrow<Signature>,
@@ -349,6 +480,65 @@ impl Transaction<state::ReadyForSigning> {
 
         self
     }
+
+    /// Attach the key witnesses of a CBOR-encoded witness set, such as what a
+    /// [CIP-30](https://cips.cardano.org/cip/CIP-0030) wallet returns from `signTx`, or
+    /// [`Self::witness_set`] from another party. Other kinds of witnesses are ignored. Fails,
+    /// attaching none, should any signature be invalid.
+    pub fn merge_witness_set(&mut self, witness_set: &[u8]) -> anyhow::Result<&mut Self> {
+        let witness_set: pallas::WitnessSet =
+            cbor::decode(witness_set).map_err(|e| anyhow!(e).context("malformed witness set"))?;
+
+        let witnesses = witness_set
+            .vkeywitness
+            .map(|witnesses| witnesses.to_vec())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|witness| {
+                let verification_key = VerificationKey::try_from(witness.vkey.to_vec())
+                    .map_err(|_| anyhow!("malformed verification key in witness set"))?;
+                let signature = Signature::try_from(witness.signature.to_vec())
+                    .map_err(|_| anyhow!("malformed signature in witness set"))?;
+                Ok((verification_key, signature))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        self.merge_vkey_witnesses(witnesses)
+    }
+
+    /// Attach the key witnesses of another copy of the same transaction, as signed by another
+    /// party. Fails, attaching none, if the bodies differ or should any signature be invalid.
+    pub fn merge(&mut self, other: &Self) -> anyhow::Result<&mut Self> {
+        if other.id() != self.id() {
+            return Err(anyhow!(
+                "cannot merge witnesses of a different transaction: {} != {}",
+                other.id(),
+                self.id()
+            ));
+        }
+        self.merge_vkey_witnesses(other.vkey_witnesses().collect())
+    }
+
+    fn merge_vkey_witnesses(
+        &mut self,
+        witnesses: Vec<(VerificationKey, Signature)>,
+    ) -> anyhow::Result<&mut Self> {
+        let id = self.id();
+        if let Some((verification_key, _)) = witnesses
+            .iter()
+            .find(|(verification_key, signature)| !verification_key.verify(id, signature))
+        {
+            return Err(anyhow!(
+                "invalid signature from {verification_key} for transaction {id}"
+            ));
+        }
+
+        for (verification_key, signature) in witnesses {
+            self.add_witness(verification_key, signature);
+        }
+
+        Ok(self)
+    }
 }
 
 // ------------------------------------------------------------------ Inspecting
@@ -408,20 +598,43 @@ impl<State: IsTransactionBodyState> fmt::Display for Transaction<State> {
                     &pretty::ViaDisplay(format!("{valid_from}; {valid_until}")),
                 );
 
-                debug_assert!(
-                    body.certificates.is_none(),
-                    "found certificates in transaction; not yet supported"
-                );
+                if body.certificates.is_some() {
+                    debug_struct.field(
+                        "certificates",
+                        &pretty::Fmt(|f: &mut fmt::Formatter<'_>| {
+                            f.debug_list()
+                                .entries(self.certificates().map(pretty::ViaDisplay))
+                                .finish()
+                        }),
+                    );
+                }
 
-                debug_assert!(
-                    body.withdrawals.is_none(),
-                    "found withdrawals in transaction; not yet supported"
-                );
+                if body.withdrawals.is_some() {
+                    debug_struct.field(
+                        "withdrawals",
+                        &pretty::Fmt(|f: &mut fmt::Formatter<'_>| {
+                            f.debug_map()
+                                .entries(self.withdrawals().map(|(credential, amount)| {
+                                    (pretty::ViaDisplay(credential), amount)
+                                }))
+                                .finish()
+                        }),
+                    );
+                }
 
-                debug_assert!(
-                    body.auxiliary_data_hash.is_none(),
-                    "found auxiliary_data_hash in transaction; not yet supported"
-                );
+                let metadata = self.metadata();
+                if !metadata.is_empty() {
+                    debug_struct.field(
+                        "metadata",
+                        &pretty::Fmt(|f: &mut fmt::Formatter<'_>| {
+                            f.debug_map()
+                                .entries(metadata.iter().map(|(label, metadatum)| {
+                                    (label, pretty::ViaDisplay(metadatum))
+                                }))
+                                .finish()
+                        }),
+                    );
+                }
 
                 if body.mint.is_some() {
                     debug_struct.field("mint", &pretty::ViaDisplay(self.mint()));
@@ -654,6 +867,39 @@ impl<State: IsTransactionBodyState> Transaction<State> {
         self.inner.transaction_body.fee
     }
 
+    /// The metadata attached to the transaction, by label.
+    pub fn metadata(&self) -> BTreeMap<u64, Metadatum> {
+        let metadata = match &self.inner.auxiliary_data {
+            pallas::Nullable::Some(pallas::AuxiliaryData::Shelley(metadata)) => Some(metadata),
+            pallas::Nullable::Some(pallas::AuxiliaryData::ShelleyMa(data)) => {
+                Some(&data.transaction_metadata)
+            }
+            pallas::Nullable::Some(pallas::AuxiliaryData::PostAlonzo(data)) => {
+                data.metadata.as_ref()
+            }
+            _ => None,
+        };
+        metadata
+            .into_iter()
+            .flat_map(|metadata| metadata.iter())
+            .map(|(label, metadatum)| (*label, Metadatum::from(metadatum.clone())))
+            .collect()
+    }
+
+    /// The message attached as per [CIP-20](https://cips.cardano.org/cip/CIP-0020), if any, one
+    /// line per chunk.
+    pub fn message(&self) -> Option<Vec<String>> {
+        let metadata = self.metadata();
+        let (_, chunks) = metadata
+            .get(&MESSAGE_LABEL)?
+            .as_map()?
+            .find(|(key, _)| key.as_text() == Some("msg"))?;
+        chunks
+            .as_list()?
+            .map(|chunk| chunk.as_text().map(str::to_string))
+            .collect()
+    }
+
     pub fn total_collateral(&self) -> u64 {
         self.inner
             .transaction_body
@@ -704,6 +950,39 @@ impl<State: IsTransactionBodyState> Transaction<State> {
             .unwrap_or_default()
     }
 
+    /// The published certificates, in order.
+    pub fn certificates(&self) -> Box<dyn Iterator<Item = Certificate> + '_> {
+        self.inner
+            .transaction_body
+            .certificates
+            .as_ref()
+            .map(|xs| {
+                Box::new(xs.iter().cloned().map(Certificate::from))
+                    as BoxedIterator<'_, Certificate>
+            })
+            .unwrap_or_else(|| Box::new(iter::empty()) as BoxedIterator<'_, Certificate>)
+    }
+
+    /// The reward withdrawals, as the stake credential of each reward account along with the
+    /// amount withdrawn; in the order redeemers point at them.
+    pub fn withdrawals(&self) -> Box<dyn Iterator<Item = (Credential, u64)> + '_> {
+        self.inner
+            .transaction_body
+            .withdrawals
+            .as_ref()
+            .map(|xs| {
+                Box::new(xs.iter().filter_map(|(account, amount)| {
+                    match pallas::Address::from_bytes(account).ok()? {
+                        pallas::Address::Stake(stake) => {
+                            Some((Credential::from(stake.payload()), *amount))
+                        }
+                        pallas::Address::Byron(..) | pallas::Address::Shelley(..) => None,
+                    }
+                })) as BoxedIterator<'_, (Credential, u64)>
+            })
+            .unwrap_or_else(|| Box::new(iter::empty()) as BoxedIterator<'_, (Credential, u64)>)
+    }
+
     /// The declared transaction outputs, which are produced in case of successful transaction.
     pub fn outputs(&self) -> Box<dyn Iterator<Item = Output> + '_> {
         Box::new(
@@ -810,6 +1089,28 @@ impl<State: IsTransactionBodyState> Transaction<State> {
             .unwrap_or_else(move || Box::new(iter::empty()))
     }
 
+    /// The execution units of all redeemers, summed up: the budget the transaction claims against
+    /// the per-transaction limit.
+    pub fn total_execution_units(&self) -> ExecutionUnits {
+        self.inner
+            .transaction_witness_set
+            .redeemer
+            .as_ref()
+            .map(|redeemers| match redeemers {
+                pallas::Redeemers::Map(kv) => {
+                    kv.iter().fold((0, 0), |(mem, cpu), (_, redeemer)| {
+                        (mem + redeemer.ex_units.mem, cpu + redeemer.ex_units.steps)
+                    })
+                }
+                pallas::Redeemers::List(_) => {
+                    unreachable!("found redeemers encoded as list: impossible with this library.")
+                }
+            })
+            .map_or_else(ExecutionUnits::default, |(mem, cpu)| {
+                ExecutionUnits::new(mem, cpu)
+            })
+    }
+
     /// The inline datums provided in the witness set. These are used by scripts
     /// that require datums not already present on-chain in the UTxO set.
     pub fn datums(&self) -> Box<dyn Iterator<Item = PlutusData<'_>> + '_> {
@@ -860,39 +1161,41 @@ impl<State: IsTransactionBodyState> Transaction<State> {
         Box::new(v1.chain(v2).chain(v3))
     }
 
-    // /// The verification key witnesses attached to this transaction. Each entry
-    // /// is a (verification_key, signature) pair. Not yet implemented
-    // TODO: pub fn vkey_witnesses(&self) -> Box<dyn Iterator<Item = (VerificationKey, Signature)> + '_>
+    /// The verification key witnesses attached to this transaction, in order. Malformed ones, if
+    /// any, are skipped; signatures aren't checked.
+    pub fn vkey_witnesses(&self) -> Box<dyn Iterator<Item = (VerificationKey, Signature)> + '_> {
+        self.inner
+            .transaction_witness_set
+            .vkeywitness
+            .as_ref()
+            .map(|witnesses| {
+                Box::new(witnesses.iter().filter_map(|witness| {
+                    Some((
+                        VerificationKey::try_from(witness.vkey.to_vec()).ok()?,
+                        Signature::try_from(witness.signature.to_vec()).ok()?,
+                    ))
+                })) as BoxedIterator<'_, (VerificationKey, Signature)>
+            })
+            .unwrap_or_else(|| Box::new(iter::empty()))
+    }
 }
 
 // -------------------------------------------------------------------- Internal
 
 impl<State: IsTransactionBodyState> Transaction<State> {
-    /// The list of required signatories on the transaction, solely inferred from inputs,
-    /// collaterals and explicitly specified signers.
+    /// The list of required signatories on the transaction, inferred from inputs, collaterals,
+    /// certificates, withdrawals and explicitly specified signers.
     ///
     /// FIXME:
     ///
     /// - account for signers from native scripts
-    /// - account for signers from certificates
     /// - account for signers from votes
-    /// - account for signers from withdrawals
     fn required_signatories(
         &self,
         resolved_inputs: &BTreeMap<Input, Output>,
     ) -> anyhow::Result<BTreeSet<Hash<28>>> {
         let body = &self.inner.transaction_body;
 
-        debug_assert!(
-            body.certificates.is_none(),
-            "found certificates in transaction: not supported yet",
-        );
-
-        debug_assert!(
-            body.withdrawals.is_none(),
-            "found withdrawals in transaction: not supported yet",
-        );
-
         debug_assert!(
             body.voting_procedures.is_none(),
             "found votes in transaction: not supported yet",
@@ -920,6 +1223,14 @@ impl<State: IsTransactionBodyState> Transaction<State> {
                         address.payment().as_key()
                     }),
             )
+            .chain(
+                self.certificates()
+                    .filter_map(|certificate| certificate.witness()?.as_key()),
+            )
+            .chain(
+                self.withdrawals()
+                    .filter_map(|(credential, _)| credential.as_key()),
+            )
             .collect::<BTreeSet<_>>())
     }
 
@@ -940,8 +1251,7 @@ impl<State: IsTransactionBodyState> Transaction<State> {
     /// - proposals (for any defined constitution guardrails)
     /// - votes (for script-based credentials)
     ///
-    /// FIXME: the function is currently partial, as certificates, withdrawals, votes and proposals
-    /// aren't implemented.
+    /// FIXME: the function is currently partial, as votes and proposals aren't implemented.
     fn required_scripts(
         &self,
         resolved_inputs: &BTreeMap<Input, Output>,
@@ -974,17 +1284,19 @@ impl<State: IsTransactionBodyState> Transaction<State> {
                     as Box<dyn Iterator<Item = (RedeemerPointer, Hash<28>)>>
             });
 
-        let body = &self.inner.transaction_body;
+        let from_certificates = self
+            .certificates()
+            .enumerate()
+            .filter_map(|(index, certificate)| Some((index, certificate.witness()?.as_script()?)))
+            .map(|(index, hash)| (RedeemerPointer::from_publish(index as u32), hash));
 
-        debug_assert!(
-            body.certificates.is_none(),
-            "found certificates in transaction: not supported yet",
-        );
+        let from_withdrawals = self
+            .withdrawals()
+            .enumerate()
+            .filter_map(|(index, (credential, _))| Some((index, credential.as_script()?)))
+            .map(|(index, hash)| (RedeemerPointer::from_withdraw(index as u32), hash));
 
-        debug_assert!(
-            body.withdrawals.is_none(),
-            "found withdrawals in transaction: not supported yet",
-        );
+        let body = &self.inner.transaction_body;
 
         debug_assert!(
             body.voting_procedures.is_none(),
@@ -999,6 +1311,8 @@ impl<State: IsTransactionBodyState> Transaction<State> {
         std::iter::empty()
             .chain(from_inputs)
             .chain(from_mint)
+            .chain(from_certificates)
+            .chain(from_withdrawals)
             .collect()
     }
 
@@ -1220,6 +1534,33 @@ impl Transaction<state::InConstruction> {
         // Add minted tokens to the change balance
         change.add(&Value::default().with_assets(mint));
 
+        // Add withdrawn rewards and refunded deposits to the change balance
+        change.add(&Value::new(
+            self.withdrawals().map(|(_, amount)| amount).sum::<u64>()
+                + self
+                    .certificates()
+                    .map(|certificate| certificate.refund())
+                    .sum::<u64>(),
+        ));
+
+        // Subtract deposits from the change balance
+        if let Some(certificate) = self
+            .certificates()
+            .find(|certificate| certificate.has_implicit_deposit())
+        {
+            return Err(anyhow!("certificate={certificate}").context(
+                "unsupported certificate: its deposit isn't stated; use Conway-era certificates",
+            ));
+        }
+
+        change
+            .checked_sub(&Value::new(
+                self.certificates()
+                    .map(|certificate| certificate.deposit())
+                    .sum::<u64>(),
+            ))
+            .map_err(|e| e.context("insufficient balance; not enough to cover deposits"))?;
+
         // Subtract burned tokens from the change balance
         change
             .checked_sub(&Value::default().with_assets(burn))
@@ -1239,16 +1580,6 @@ impl Transaction<state::InConstruction> {
 
         let body = &self.inner.transaction_body;
 
-        debug_assert!(
-            body.certificates.is_none(),
-            "found certificates in transaction: not supported yet",
-        );
-
-        debug_assert!(
-            body.withdrawals.is_none(),
-            "found withdrawals in transaction: not supported yet",
-        );
-
         debug_assert!(
             body.treasury_value.is_none(),
             "found treasury donation in transaction: not supported yet",
@@ -1327,6 +1658,12 @@ fn without_existing_redeemers(
     }
 }
 
+/// The reward account of a stake credential: a reward address, as raw bytes.
+fn into_reward_account(network_id: NetworkId, credential: &Credential) -> pallas::RewardAccount {
+    let header = (credential.select(|_| 0b1110, |_| 0b1111) << 4) | u8::from(network_id);
+    pallas::Bytes::from([&[header], Hash::<28>::from(credential).as_ref()].concat())
+}
+
 fn into_pallas_redeemers(
     redeemers: BTreeMap<RedeemerPointer, PlutusData>,
 ) -> impl Iterator<Item = (pallas::RedeemersKey, pallas::RedeemersValue)> {
@@ -1375,12 +1712,71 @@ impl<'d, C> cbor::Decode<'d, C> for Transaction<state::ReadyForSigning> {
     }
 }
 
+impl Transaction<state::ReadyForSigning> {
+    /// The key witnesses only, as a CBOR-encoded witness set; for another party to
+    /// [`merge`](Self::merge_witness_set) into its own copy of the transaction.
+    pub fn witness_set(&self) -> Vec<u8> {
+        let witness_set = pallas::WitnessSet {
+            vkeywitness: self.inner.transaction_witness_set.vkeywitness.clone(),
+            native_script: None,
+            bootstrap_witness: None,
+            plutus_v1_script: None,
+            plutus_data: None,
+            redeemer: None,
+            plutus_v2_script: None,
+            plutus_v3_script: None,
+        };
+        cbor::ToCbor::to_cbor(&witness_set)
+    }
+}
+
+/// Decode a transaction, signed or not, such as one built and exported elsewhere. Unlike the
+/// plain [`cbor::Decode`], this fails if the body wouldn't encode back to the very same bytes:
+/// the transaction id would then differ from the one other parties sign.
+impl TryFrom<&[u8]> for Transaction<state::ReadyForSigning> {
+    type Error = anyhow::Error;
+
+    fn try_from(bytes: &[u8]) -> anyhow::Result<Self> {
+        let transaction: Self =
+            cbor::decode(bytes).map_err(|e| anyhow!(e).context("malformed transaction"))?;
+
+        let mut d = cbor::Decoder::new(bytes);
+        d.array()
+            .and_then(|_| {
+                let start = d.position();
+                d.skip()?;
+                Ok(&bytes[start..d.position()])
+            })
+            .map_err(|e| anyhow!(e).context("malformed transaction"))
+            .and_then(|body| {
+                if cbor::ToCbor::to_cbor(&transaction.inner.transaction_body) != body {
+                    return Err(anyhow!(
+                        "unsupported transaction: its body doesn't encode back identically"
+                    ));
+                }
+                Ok(transaction)
+            })
+    }
+}
+
+/// Hex-encoded CBOR, decoded as through `TryFrom<&[u8]>`.
+impl FromStr for Transaction<state::ReadyForSigning> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let bytes = hex::decode(s.trim()).map_err(|e| anyhow!(e).context("malformed hex"))?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
 // ------------------------------------------------------------------------ WASM
 
 #[cfg(test)]
 mod tests {
-    use crate::{SigningKey, Transaction, cbor, transaction::state::*};
+    use crate::{Output, SigningKey, Transaction, Value, any, cbor, pallas, transaction::state::*};
     use indoc::indoc;
+    use proptest::{option, prelude::*};
+    use std::str::FromStr;
 
     #[test]
     fn display_transaction_1() {
@@ -1437,6 +1833,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn metadata_round_trips_with_its_hash() {
+        let mut transaction = Transaction::default();
+        let long_line = "konduit ".repeat(10);
+        transaction
+            .with_message(["konduit: sub", long_line.as_str()])
+            .with_metadata(1, 42_u64);
+        let transaction: Transaction<ReadyForSigning> =
+            cbor::decode(&cbor::ToCbor::to_cbor(&transaction)).unwrap();
+
+        let message = transaction.message().unwrap();
+        assert_eq!(message.len(), 3);
+        assert_eq!(message[1..].concat(), long_line);
+        assert_eq!(
+            transaction.metadata().get(&1).and_then(|m| m.as_integer()),
+            Some(42)
+        );
+
+        let pallas::Nullable::Some(auxiliary_data) = &transaction.inner.auxiliary_data else {
+            panic!("no auxiliary data");
+        };
+        let mut bytes = Vec::new();
+        cbor::encode(auxiliary_data, &mut bytes).unwrap();
+        assert_eq!(
+            transaction.inner.transaction_body.auxiliary_data_hash,
+            Some(pallas::Bytes::from(
+                pallas::Hasher::<256>::hash(&bytes).to_vec()
+            ))
+        );
+    }
+
+    #[test]
+    fn merges_valid_witness_sets_only() {
+        let mut transaction = Transaction::default();
+        transaction.with_message(["konduit"]);
+        let unsigned: Transaction<ReadyForSigning> =
+            cbor::decode(&cbor::ToCbor::to_cbor(&transaction)).unwrap();
+
+        let signing_key = SigningKey::from([42; 32]);
+        let mut signed = unsigned.clone();
+        signed.sign(&signing_key);
+        let witness_set = signed.witness_set();
+
+        let mut merged = unsigned.clone();
+        merged.merge_witness_set(&witness_set).unwrap();
+        assert_eq!(merged.to_string(), signed.to_string());
+        assert_eq!(
+            merged
+                .vkey_witnesses()
+                .map(|(vk, _)| vk)
+                .collect::<Vec<_>>(),
+            vec![signing_key.to_verification_key()],
+        );
+
+        let mut other = Transaction::default();
+        other.with_message(["adversary"]);
+        let mut other: Transaction<ReadyForSigning> =
+            cbor::decode(&cbor::ToCbor::to_cbor(&other)).unwrap();
+        assert!(other.merge_witness_set(&witness_set).is_err());
+        assert!(other.merge(&signed).is_err());
+        assert!(other.inner.transaction_witness_set.vkeywitness.is_none());
+
+        assert!(merged.merge_witness_set(&[0x80]).is_err());
+    }
+
+    #[test]
+    fn decodes_only_bodies_encoding_back_identically() {
+        let canonical = hex::decode("84a300d901028001800201a0f5f6").unwrap();
+        let transaction = Transaction::try_from(canonical.as_slice()).unwrap();
+        assert_eq!(cbor::ToCbor::to_cbor(&transaction), canonical);
+        assert_eq!(transaction.fee(), 1);
+
+        // Same, with the fee over two bytes; then with inputs as a plain array.
+        for non_canonical in ["84a300d90102800180021801a0f5f6", "84a3008001800201a0f5f6"] {
+            let non_canonical = hex::decode(non_canonical).unwrap();
+            assert!(cbor::decode::<Transaction<ReadyForSigning>>(&non_canonical).is_ok());
+            assert!(Transaction::try_from(non_canonical.as_slice()).is_err());
+        }
+
+        assert!(Transaction::from_str(" 84a300d901028001800201a0f5f6\n").is_ok());
+        assert!(Transaction::from_str("84a300d901028001800201a0f5").is_err());
+    }
+
     #[test]
     fn display_transaction_2() {
         let transaction: Transaction<ReadyForSigning> = cbor::decode(
@@ -1501,4 +1980,78 @@ mod tests {
             },
         );
     }
+
+    // -------------------------------------------------------------- Properties
+
+    prop_compose! {
+        fn unsigned()(
+            inputs in prop::collection::btree_set(any::input(), 1..4),
+            outputs in prop::collection::vec((any::address_shelley(), any::<u64>()), 0..3),
+            fee in any::<u64>(),
+            message in option::of("[a-z ]{0,80}"),
+        ) -> Transaction<ReadyForSigning> {
+            let mut transaction = Transaction::default();
+            transaction
+                .with_inputs(inputs.into_iter().map(|input| (input, None)))
+                .with_outputs(outputs.into_iter().map(|(address, lovelace)| {
+                    Output::new(address.into(), Value::new(lovelace))
+                }))
+                .with_fee(fee);
+            if let Some(message) = &message {
+                transaction.with_message([message.as_str()]);
+            }
+            cbor::decode(&cbor::ToCbor::to_cbor(&transaction)).unwrap()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn round_trips_through_cbor(
+            mut transaction in unsigned(),
+            signers in prop::collection::vec(any::<[u8; 32]>(), 0..3),
+        ) {
+            for signer in signers {
+                transaction.sign(&SigningKey::from(signer));
+            }
+
+            let bytes = cbor::ToCbor::to_cbor(&transaction);
+            let decoded = Transaction::try_from(bytes.as_slice()).unwrap();
+            prop_assert_eq!(cbor::ToCbor::to_cbor(&decoded), bytes.clone());
+            prop_assert_eq!(decoded.id(), transaction.id());
+            prop_assert_eq!(
+                decoded.vkey_witnesses().collect::<Vec<_>>(),
+                transaction.vkey_witnesses().collect::<Vec<_>>()
+            );
+
+            let decoded = Transaction::from_str(&hex::encode(&bytes)).unwrap();
+            prop_assert_eq!(cbor::ToCbor::to_cbor(&decoded), bytes);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn merges_witnesses_of_all_parties(
+            transaction in unsigned(),
+            signers in prop::collection::btree_set(any::<[u8; 32]>(), 1..4),
+        ) {
+            let mut expected = transaction.clone();
+            let mut via_witness_set = transaction.clone();
+            let mut via_transaction = transaction.clone();
+            for signer in signers {
+                let signing_key = SigningKey::from(signer);
+                expected.sign(&signing_key);
+
+                let mut signed = Transaction::try_from(
+                    cbor::ToCbor::to_cbor(&transaction).as_slice(),
+                ).unwrap();
+                signed.sign(&signing_key);
+                via_witness_set.merge_witness_set(&signed.witness_set()).unwrap();
+                via_transaction.merge(&signed).unwrap();
+            }
+
+            let witnesses = expected.vkey_witnesses().collect::<Vec<_>>();
+            prop_assert_eq!(via_witness_set.vkey_witnesses().collect::<Vec<_>>(), witnesses.clone());
+            prop_assert_eq!(via_transaction.vkey_witnesses().collect::<Vec<_>>(), witnesses);
+        }
+    }
 }
@@ -3,7 +3,8 @@
 //  file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::{
-    ExecutionUnits, Hash, Input, Output, ProtocolParameters, RedeemerPointer, Transaction,
+    ExecutionUnits, Hash, Input, Metadatum, Output, ProtocolParameters, RedeemerPointer,
+    Transaction,
     cardano::transaction::{IsTransactionBodyState, state},
     cbor::{self, ToCbor},
     pallas,
@@ -255,6 +256,9 @@ impl Transaction<state::InConstruction> {
             // full new output). But users may pre-define such outputs; hence the safeguard here.
             fail_on_insufficiently_funded_outputs(tx.outputs())?;
 
+            // Fails on metadata the ledger would reject, rather than at submission.
+            fail_on_oversized_metadata(tx.metadata())?;
+
             // Serialise the transaction to compute its fee.
             serialized_tx.clear();
             cbor::encode(&tx, &mut serialized_tx).unwrap();
@@ -389,6 +393,24 @@ fn fail_on_missing_collateral<T>(
     Ok(())
 }
 
+fn fail_on_oversized_metadata(metadata: BTreeMap<u64, Metadatum>) -> anyhow::Result<()> {
+    let oversized = metadata
+        .into_iter()
+        .filter(|(_, metadatum)| !metadatum.is_within_limits())
+        .map(|(label, _)| label.to_string())
+        .collect::<Vec<_>>();
+
+    if !oversized.is_empty() {
+        return Err(anyhow!(
+            "text or bytes over {} bytes under metadata label(s) {}",
+            Metadatum::MAX_CHUNK_SIZE,
+            oversized.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
 fn fail_on_insufficiently_funded_outputs(
     outputs: impl Iterator<Item = Output>,
 ) -> anyhow::Result<()> {
@@ -526,10 +548,10 @@ fn dump_tx_context(tx_id: &Hash<32>, serialized_tx: &[u8], inputs: &[uplc::tx::R
 #[cfg(test)]
 mod tests {
     use crate::{
-        Address, ChangeStrategy, Hash, Input, Output, PlutusData, PlutusScript, PlutusVersion,
-        ProtocolParameters, SlotBound, Transaction, address, address::kind::*, address_test,
-        assets, cbor::ToCbor, hash, input, key_credential, output, plutus_data, plutus_script,
-        script_credential, value,
+        Address, Certificate, ChangeStrategy, Hash, Input, NetworkId, Output, PlutusData,
+        PlutusScript, PlutusVersion, ProtocolParameters, RedeemerPointer, SlotBound, Transaction,
+        address, address::kind::*, address_test, assets, cardano::transaction::state, cbor::ToCbor,
+        hash, input, key_credential, output, plutus_data, plutus_script, script_credential, value,
     };
     use indoc::indoc;
     use std::{cell::LazyCell, collections::BTreeMap, sync::LazyLock};
@@ -923,6 +945,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stake_certificates_and_withdrawals() {
+        let always_succeed_script = ALWAYS_SUCCEED_SCRIPT;
+        let always_succeed_credential =
+            script_credential!("bd3ae991b5aafccafe5ca70758bd36a9b2f872f57f6d3a1ffa0eb777");
+        let my_credential =
+            key_credential!("82c1729d5fd44124a6ae72bcdb86b6e827aac6a74301e4003c092e6f");
+        let my_address: Address<Any> = address_test!(
+            "addr_test1qzpvzu5atl2yzf9x4eetekuxkm5z02kx5apsreqq8syjum6274ase8lkeffp39narear74ed0nf804e5drfm9l99v4eq3ecz8t"
+        );
+        let pool = Hash::from([1; 28]);
+
+        let resolved_inputs = BTreeMap::from([(
+            input!(
+                "c984c8bf52a141254c714c905b2d27b432d4b546f815fbc2fea7b9da6e490324",
+                3
+            ),
+            Output::new(my_address.clone(), value!(10_000_000)),
+        )]);
+
+        let change = |tx: &Transaction<state::ReadyForSigning>| {
+            tx.outputs()
+                .map(|output| output.value().lovelace())
+                .sum::<u64>()
+        };
+
+        let register = Transaction::build(&FIXTURE_PROTOCOL_PARAMETERS, &resolved_inputs, |tx| {
+            tx.with_inputs(vec![input!(
+                "c984c8bf52a141254c714c905b2d27b432d4b546f815fbc2fea7b9da6e490324",
+                3,
+                _
+            )])
+            .with_certificates(vec![
+                (
+                    Certificate::register_and_delegate(my_credential.clone(), pool, 2_000_000),
+                    None,
+                ),
+                (
+                    Certificate::register(always_succeed_credential.clone(), 2_000_000),
+                    Some(PlutusData::list::<PlutusData>([])),
+                ),
+            ])
+            .with_collaterals(vec![input!(
+                "c984c8bf52a141254c714c905b2d27b432d4b546f815fbc2fea7b9da6e490324",
+                3
+            )])
+            .with_plutus_scripts(vec![always_succeed_script.clone()])
+            .with_change_strategy(ChangeStrategy::as_last_output(my_address.clone()))
+            .ok()
+        })
+        .unwrap();
+
+        assert_eq!(
+            change(&register) + register.fee(),
+            10_000_000 - 2 * 2_000_000
+        );
+        assert_eq!(
+            register.redeemers().map(|(ptr, _)| ptr).collect::<Vec<_>>(),
+            vec![RedeemerPointer::from_publish(1)],
+        );
+        assert!(
+            register
+                .required_signatories(&resolved_inputs)
+                .unwrap()
+                .contains(&my_credential.as_key().unwrap())
+        );
+
+        let withdraw = Transaction::build(&FIXTURE_PROTOCOL_PARAMETERS, &resolved_inputs, |tx| {
+            tx.with_inputs(vec![input!(
+                "c984c8bf52a141254c714c905b2d27b432d4b546f815fbc2fea7b9da6e490324",
+                3,
+                _
+            )])
+            .with_withdrawals(
+                NetworkId::TESTNET,
+                vec![
+                    (my_credential.clone(), 1_000_000, None),
+                    (
+                        always_succeed_credential.clone(),
+                        500_000,
+                        Some(PlutusData::list::<PlutusData>([])),
+                    ),
+                ],
+            )
+            .with_certificates(vec![(
+                Certificate::deregister(my_credential.clone(), 2_000_000),
+                None,
+            )])
+            .with_collaterals(vec![input!(
+                "c984c8bf52a141254c714c905b2d27b432d4b546f815fbc2fea7b9da6e490324",
+                3
+            )])
+            .with_plutus_scripts(vec![always_succeed_script.clone()])
+            .with_change_strategy(ChangeStrategy::as_last_output(my_address.clone()))
+            .ok()
+        })
+        .unwrap();
+
+        assert_eq!(
+            change(&withdraw) + withdraw.fee(),
+            10_000_000 + 1_000_000 + 500_000 + 2_000_000
+        );
+        // Scripts come first among reward accounts.
+        assert_eq!(
+            withdraw.withdrawals().collect::<Vec<_>>(),
+            vec![
+                (always_succeed_credential, 500_000),
+                (my_credential, 1_000_000)
+            ],
+        );
+        assert_eq!(
+            withdraw.redeemers().map(|(ptr, _)| ptr).collect::<Vec<_>>(),
+            vec![RedeemerPointer::from_withdraw(0)],
+        );
+    }
+
     /// This test confirms that when a script fails and log leve is info, then the `dump_tx_context`
     /// function is triggered.
     ///
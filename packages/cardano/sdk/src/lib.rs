@@ -13,12 +13,16 @@ pub use cardano::{
     // NOTE: The main function of this comment is to force the formatter to put one import per
     // line in the following import list; making diffs and extensions easier.
     address::{Address, IsAddressKind},
+    certificate::Certificate,
     credential::Credential,
+    crypto::bip32::{DerivationPath, ExtendedSigningKey},
+    crypto::bip39::Mnemonic,
     crypto::ed25519::{LeakableSigningKey, Signature, SigningKey, VerificationKey},
     datum::Datum,
     execution_units::ExecutionUnits,
     hash::Hash,
     input::Input,
+    metadatum::Metadatum,
     network::Network,
     network_id::NetworkId,
     output::Output,
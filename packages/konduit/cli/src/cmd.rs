@@ -4,7 +4,10 @@ use clap::Parser;
 mod adaptor;
 mod admin;
 mod consumer;
+mod inspect;
 mod parsers;
+mod sign;
+mod submit;
 
 /// A utility for constructing and driving Konduit's stages
 #[derive(Debug, clap::Parser)]
@@ -13,6 +16,7 @@ pub(crate) enum Cmd {
     Adaptor(WithEnv<env::adaptor::Env, adaptor::Cmd>),
     Admin(WithEnv<env::admin::Env, admin::Cmd>),
     Consumer(WithEnv<env::consumer::Env, consumer::Cmd>),
+    Inspect(WithEnv<env::connector::ConnectorEnv, inspect::Cmd>),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -30,6 +34,7 @@ impl Cmd {
             Self::Adaptor(WithEnv { env, cmd }) => cmd.run(env).await,
             Self::Admin(WithEnv { env, cmd }) => cmd.run(env).await,
             Self::Consumer(WithEnv { env, cmd }) => cmd.run(env).await,
+            Self::Inspect(WithEnv { env, cmd }) => cmd.run(env).await,
         }
     }
 
@@ -1,9 +1,16 @@
-use crate::{cmd::parsers::parse_keytag_receipt, config::adaptor::Config};
+use crate::{
+    cmd::parsers::parse_keytag_receipt,
+    config::adaptor::Config,
+    shared::{SignerArgs, TxFile, nth_path},
+};
 use cardano_connector::CardanoConnector;
-use cardano_sdk::Credential;
+use cardano_sdk::VerificationKey;
 use konduit_data::{Keytag, Receipt};
-use konduit_tx::{self, Bounds, KONDUIT_VALIDATOR, NetworkParameters, adaptor::AdaptorPreferences};
-use std::collections::BTreeMap;
+use konduit_tx::{
+    self, Bounds, KONDUIT_VALIDATORS, NetworkParameters, TxBudget, Utxos,
+    adaptor::AdaptorPreferences,
+};
+use std::{collections::BTreeMap, path::PathBuf};
 
 /// Create and submit Konduit transactions
 #[derive(Debug, Clone, clap::Args)]
@@ -16,17 +23,43 @@ pub struct Cmd {
     /// squash_body,signature;cheque_body,signature,secret;cheque,secret;
     #[arg(long, value_parser=parse_keytag_receipt)]
     pub receipt: Vec<(Keytag, Receipt)>,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    /// Save the unsigned transactions, along with the utxos they resolve, rather than signing and
+    /// submitting them. With several transactions, each goes to `<FILE>.<n>`. See `sign` and
+    /// `submit`.
+    #[arg(long, value_name = "FILE")]
+    pub out_unsigned: Option<PathBuf>,
+
+    /// Build for this adaptor key, rather than that of the signer; which is then not needed. This
+    /// way, the signing key can stay on another machine.
+    #[arg(
+        long,
+        value_name = "VERIFICATION_KEY",
+        requires = "out_unsigned",
+        conflicts_with_all = ["keystore", "remote_signer"]
+    )]
+    pub verification_key: Option<VerificationKey>,
 }
 
 impl Cmd {
     pub async fn run(self, config: &Config) -> anyhow::Result<()> {
         let connector = config.connector.connector().await?;
-        let own_key = config.wallet.to_verification_key();
+        let (own_key, signer) = match self.verification_key {
+            Some(verification_key) => (verification_key, None),
+            None => {
+                let signer = self.signer.signer(&config.wallet).await?;
+                (signer.verification_key(), Some(signer))
+            }
+        };
         let own_address = own_key.to_address(connector.network().into());
         let receipts = self.receipt.into_iter().collect::<BTreeMap<_, _>>();
         let preferences = AdaptorPreferences {
             min_single: 10_000,
             min_total: 1_000_000,
+            budget: TxBudget::default(),
         };
         let bounds = Bounds::twenty_mins();
         let upper = bounds.upper.unwrap();
@@ -37,7 +70,7 @@ impl Cmd {
             network_id,
             protocol_parameters,
         };
-        let utxos = connector
+        let mut utxos = connector
             .utxos_at(&own_address.payment(), None)
             .await?
             .into_iter()
@@ -49,13 +82,11 @@ impl Cmd {
                     )
                     .await?,
             )
-            .chain(
-                connector
-                    .utxos_at(&Credential::from_script(KONDUIT_VALIDATOR.hash), None)
-                    .await?,
-            )
-            .collect();
-        let mut tx = konduit_tx::adaptor::tx(
+            .collect::<Utxos>();
+        for credential in KONDUIT_VALIDATORS.credentials() {
+            utxos.extend(connector.utxos_at(&credential, None).await?);
+        }
+        let txs = konduit_tx::adaptor::txs(
             &network_parameters,
             &preferences,
             &own_key,
@@ -63,8 +94,22 @@ impl Cmd {
             &utxos,
             &upper,
         )?;
-        println!("Tx id :: {}", tx.id());
-        tx.sign(&config.wallet);
-        connector.submit(&tx).await
+        let count = txs.len();
+        for (n, mut tx) in txs.into_iter().enumerate() {
+            println!("Tx id :: {}", tx.id());
+            match (&self.out_unsigned, &signer) {
+                (Some(path), _) => {
+                    let path = nth_path(path, n, count);
+                    TxFile::new(tx, &utxos).save(&path)?;
+                    println!("Saved :: {}", path.display());
+                }
+                (None, Some(signer)) => {
+                    signer.witness(&mut tx).await?;
+                    connector.submit(&tx).await?;
+                }
+                (None, None) => unreachable!("a verification key requires --out-unsigned"),
+            }
+        }
+        Ok(())
     }
 }
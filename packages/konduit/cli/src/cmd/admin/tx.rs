@@ -1,8 +1,8 @@
-use crate::{cardano::ADA, config::admin::Config};
+use crate::{cardano::ADA, config::admin::Config, shared::TxFile};
 use cardano_connector::CardanoConnector;
 use cardano_sdk::{Address, SigningKey, Value, address::kind};
-use konduit_tx::{self, KONDUIT_VALIDATOR};
-use std::str;
+use konduit_tx::{self, KONDUIT_VALIDATOR, Utxos};
+use std::{path::PathBuf, str};
 
 /// Create and submit Konduit transactions
 #[derive(Debug, Clone, clap::Subcommand)]
@@ -27,6 +27,11 @@ pub struct SendArgs {
     /// Where the rest of the value goes. Defaults to own address
     #[arg(long)]
     rest: Option<Address<kind::Shelley>>,
+
+    /// Save the unsigned transaction, along with the utxos it resolves, rather than signing and
+    /// submitting it. See `sign` and `submit`.
+    #[arg(long, value_name = "FILE")]
+    out_unsigned: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -53,6 +58,11 @@ pub struct DeployArgs {
     /// If true, will spend utxos with scripts. Defaults to false
     #[arg(long, default_value_t = false)]
     spend_all: bool,
+
+    /// Save the unsigned transaction, along with the utxos it resolves, rather than signing and
+    /// submitting it. See `sign` and `submit`.
+    #[arg(long, value_name = "FILE")]
+    out_unsigned: Option<PathBuf>,
 }
 
 impl Cmd {
@@ -82,10 +92,13 @@ async fn run_send(
         .await?
         .into_iter()
         .filter(|(_, o)| o.script().is_none() || args.spend_all)
-        .collect();
+        .collect::<Utxos>();
     let protocol_parameters = connector.protocol_parameters().await?;
     let mut tx = konduit_tx::admin::send(&protocol_parameters, &utxos, tos, change_address)?;
     println!("Tx id :: {}", tx.id());
+    if let Some(path) = &args.out_unsigned {
+        return TxFile::new(tx, &utxos).save(path);
+    }
     tx.sign(wallet);
     connector.submit(&tx).await
 }
@@ -105,7 +118,7 @@ async fn run_deploy(
         .await?
         .into_iter()
         .filter(|(_, o)| o.script().is_none() || args.spend_all)
-        .collect();
+        .collect::<Utxos>();
     let protocol_parameters = connector.protocol_parameters().await?;
     let mut tx = konduit_tx::admin::deploy(
         &protocol_parameters,
@@ -115,6 +128,9 @@ async fn run_deploy(
         change_address,
     )?;
     println!("Tx id :: {}", tx.id());
+    if let Some(path) = &args.out_unsigned {
+        return TxFile::new(tx, &utxos).save(path);
+    }
     tx.sign(wallet);
     connector.submit(&tx).await
 }
@@ -122,6 +138,7 @@ async fn run_deploy(
 #[cfg(test)]
 mod tests {
     use super::{ADA, DeployArgs, SendArgs, run_deploy, run_send};
+    use crate::shared::TxFile;
     use cardano_connector::CardanoConnector;
     use cardano_sdk::{
         Address, Credential, Hash, Input, Network, Output, PlutusVersion, ProtocolParameters,
@@ -235,6 +252,7 @@ mod tests {
             spend_all: false,
             to: vec![super::AddressAmount(destination, 2)],
             rest: None,
+            out_unsigned: None,
         };
 
         run_send(&connector, &wallet, args)
@@ -259,7 +277,10 @@ mod tests {
             &connector,
             &wallet,
             &own_address,
-            DeployArgs { spend_all: false },
+            DeployArgs {
+                spend_all: false,
+                out_unsigned: None,
+            },
         )
         .await
         .expect("deploy should skip script utxos by default");
@@ -282,7 +303,10 @@ mod tests {
             &connector,
             &wallet,
             &own_address,
-            DeployArgs { spend_all: true },
+            DeployArgs {
+                spend_all: true,
+                out_unsigned: None,
+            },
         )
         .await
         .expect("deploy should allow script utxos when requested");
@@ -291,4 +315,37 @@ mod tests {
         assert_eq!(state.protocol_parameter_calls, 1);
         assert_eq!(state.submit_calls, 1);
     }
+
+    #[tokio::test]
+    async fn send_can_save_the_unsigned_transaction_instead() {
+        let wallet = SigningKey::from([8; 32]);
+        let own_address = payment_address(&wallet);
+        let connector = FakeConnector::new(own_address.payment(), wallet_utxos(&own_address));
+        let path = std::env::temp_dir().join(format!("konduit-send-{}", std::process::id()));
+        let args = SendArgs {
+            spend_all: false,
+            to: vec![super::AddressAmount(own_address.clone(), 2)],
+            rest: None,
+            out_unsigned: Some(path.clone()),
+        };
+
+        run_send(&connector, &wallet, args)
+            .await
+            .expect("send should save the transaction");
+
+        let file = TxFile::load(&path).expect("saved transaction file");
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(file.transaction.vkey_witnesses().count(), 0);
+        assert!(
+            file.transaction
+                .inputs()
+                .all(|input| file.utxos.contains_key(&input))
+        );
+        let summary = file.inspect(&ProtocolParameters::preview()).to_string();
+        assert!(summary.contains("signatures: none"), "{summary}");
+        assert!(!summary.contains("unresolved"), "{summary}");
+
+        let state = connector.state.lock().expect("state lock");
+        assert_eq!(state.submit_calls, 0);
+    }
 }
@@ -1,4 +1,9 @@
-use crate::{config::consumer::Config, env::consumer::Env, shared::Setup};
+use crate::{
+    cmd::{sign, submit},
+    config::consumer::Config,
+    env::consumer::Env,
+    shared::{Setup, SetupArgs},
+};
 
 mod make;
 mod show;
@@ -11,7 +16,7 @@ pub enum Cmd {
     ///
     /// Defaults can be overridden manually via options or via environment variables.
     /// See also consumer --help.
-    Setup,
+    Setup(SetupArgs),
 
     /// Show info (requires env)
     #[clap(subcommand)]
@@ -23,12 +28,19 @@ pub enum Cmd {
 
     /// Build transactions useful to a consumer.
     Tx(tx::Cmd),
+
+    /// Sign a transaction file, as saved by `--out-unsigned`.
+    Sign(sign::Cmd),
+
+    /// Submit a signed transaction file.
+    Submit(submit::Cmd),
 }
 
 impl Cmd {
     pub(crate) async fn run(self, env: Env) -> anyhow::Result<()> {
-        if let Cmd::Setup = self {
-            return env.setup();
+        if let Cmd::Setup(args) = self {
+            let wallet = args.wallet()?.or(env.wallet);
+            return Env { wallet, ..env }.setup();
         }
 
         let config = Config::try_from(env)?;
@@ -37,7 +49,9 @@ impl Cmd {
             Cmd::Make(cmd) => cmd.run(&config),
             Cmd::Show(cmd) => cmd.run(&config).await,
             Cmd::Tx(cmd) => cmd.run(&config).await,
-            Cmd::Setup => unreachable!(),
+            Cmd::Sign(cmd) => cmd.run(&config.wallet, config.connector.network()).await,
+            Cmd::Submit(cmd) => cmd.run(&config.connector.connector().await?).await,
+            Cmd::Setup(_) => unreachable!(),
         }
     }
 }
@@ -1,9 +1,9 @@
-use crate::{cardano::ADA, config::consumer::Config};
+use crate::{cardano::ADA, config::consumer::Config, shared::TxFile};
 use cardano_sdk::VerificationKey;
 use konduit_client::l1;
 use konduit_data::{Duration, Tag};
 use konduit_tx::consumer::{Intent, OpenIntent};
-use std::{collections::BTreeMap, str};
+use std::{collections::BTreeMap, path::PathBuf, str};
 
 /// Consumer tx. Can open, add, close, expire, elapse, and end.
 /// Only open add and close need to be declared, the other steps are inferred from the context.
@@ -21,6 +21,11 @@ pub struct Cmd {
     /// Close channel
     #[arg(long, value_names = ["TAG"])]
     close: Vec<Tag>,
+
+    /// Save the unsigned transaction, along with the utxos it resolves, rather than signing and
+    /// submitting it. See `sign` and `submit`.
+    #[arg(long, value_name = "FILE")]
+    out_unsigned: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -100,9 +105,22 @@ impl Cmd {
             .collect::<BTreeMap<_, _>>();
 
         let client = l1::Client::new(&connector, &config.wallet);
-        let id = client
-            .execute(&config.wallet, None, opens, intents, &config.host_address)
-            .await?;
+        let id = match self.out_unsigned {
+            Some(path) => {
+                let wallet = config.wallet.to_verification_key();
+                let (tx, utxos) = client
+                    .prepare(&wallet, None, opens, intents, &config.host_address)
+                    .await?;
+                let id = tx.id();
+                TxFile::new(tx, &utxos).save(&path)?;
+                id
+            }
+            None => {
+                client
+                    .execute(&config.wallet, None, opens, intents, &config.host_address)
+                    .await?
+            }
+        };
 
         println!("\"{id}\"");
 
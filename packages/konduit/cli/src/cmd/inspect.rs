@@ -0,0 +1,75 @@
+use crate::{config::connector::Connector, env::connector::ConnectorEnv, shared::TxFile};
+use anyhow::anyhow;
+use cardano_sdk::{Hash, ProtocolParameters, Transaction};
+use konduit_tx::Utxos;
+use std::{path::Path, str::FromStr};
+
+/// Inspect CLI
+#[derive(Debug, clap::Subcommand)]
+pub enum Cmd {
+    /// Explain a transaction in Konduit terms: the channels it spends, with their stage before and
+    /// after and the step taken; the squashes and cheques it carries, and whether their signatures
+    /// hold; the channels it opens; and its validity interval.
+    Tx(TxArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct TxArgs {
+    /// A transaction id to look up, a transaction's CBOR in hex, or a transaction file as saved
+    /// by `--out-unsigned`.
+    tx: String,
+
+    /// Print JSON, for tooling.
+    #[arg(long, default_value_t = false)]
+    json: bool,
+
+    /// Look nothing up. Channels spent are then only recognised from a transaction file.
+    #[arg(long, default_value_t = false)]
+    offline: bool,
+}
+
+impl Cmd {
+    pub(crate) async fn run(self, env: ConnectorEnv) -> anyhow::Result<()> {
+        match self {
+            Cmd::Tx(args) => args.run(env).await,
+        }
+    }
+}
+
+impl TxArgs {
+    async fn run(self, env: ConnectorEnv) -> anyhow::Result<()> {
+        let config = Connector::try_from(env)?;
+
+        let (transaction, utxos) = if Path::new(&self.tx).is_file() {
+            let file = TxFile::load(Path::new(&self.tx))?;
+            (file.transaction, file.utxos)
+        } else if self.offline {
+            (Transaction::from_str(&self.tx)?, Utxos::new())
+        } else {
+            let connector = config.connector().await?;
+            let transaction = match Hash::<32>::from_str(self.tx.trim()) {
+                Ok(id) => connector
+                    .transaction(&id)
+                    .await?
+                    .ok_or(anyhow!("transaction {id} not found"))?,
+                Err(_) => Transaction::from_str(&self.tx)?,
+            };
+            let utxos = connector.resolve(transaction.inputs()).await?;
+            (transaction, utxos)
+        };
+
+        let inspection = konduit_tx::inspect(
+            &transaction,
+            &utxos,
+            &ProtocolParameters::from(config.network()),
+        );
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&inspection)?);
+        } else {
+            print!("{inspection}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::shared::{SignerArgs, TxFile, review};
+use cardano_sdk::{Network, ProtocolParameters, SigningKey};
+use std::path::PathBuf;
+
+/// Add a signature to a transaction file, as saved by `--out-unsigned`. Meant for a machine that
+/// holds the key, but has no access to the chain.
+#[derive(Debug, Clone, clap::Args)]
+pub struct Cmd {
+    /// Transaction file to sign
+    #[arg(long, value_name = "FILE")]
+    tx: PathBuf,
+
+    /// Where to save the signed transaction. Defaults to the transaction file itself.
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+
+    /// Sign without asking for confirmation
+    #[arg(long, default_value_t = false)]
+    yes: bool,
+
+    #[command(flatten)]
+    signer: SignerArgs,
+}
+
+impl Cmd {
+    pub async fn run(self, wallet: &SigningKey, network: Network) -> anyhow::Result<()> {
+        let mut file = TxFile::load(&self.tx)?;
+        let signer = self.signer.signer(wallet).await?;
+
+        review(
+            &file.inspect(&ProtocolParameters::from(network)),
+            &format!("Sign with {}", signer.verification_key()),
+            self.yes,
+        )?;
+
+        signer.witness(&mut file.transaction).await?;
+        file.save(self.out.as_ref().unwrap_or(&self.tx))?;
+
+        println!("\"{}\"", file.transaction.id());
+
+        Ok(())
+    }
+}
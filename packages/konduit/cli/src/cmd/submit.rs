@@ -0,0 +1,34 @@
+use crate::shared::{TxFile, review};
+use cardano_connector::CardanoConnector;
+use cardano_sdk::ProtocolParameters;
+use std::path::PathBuf;
+
+/// Submit a transaction file, as saved by `--out-unsigned` then completed by `sign`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct Cmd {
+    /// Transaction file to submit
+    #[arg(long, value_name = "FILE")]
+    tx: PathBuf,
+
+    /// Submit without asking for confirmation
+    #[arg(long, default_value_t = false)]
+    yes: bool,
+}
+
+impl Cmd {
+    pub async fn run(self, connector: &impl CardanoConnector) -> anyhow::Result<()> {
+        let file = TxFile::load(&self.tx)?;
+
+        review(
+            &file.inspect(&ProtocolParameters::from(connector.network())),
+            "Submit",
+            self.yes,
+        )?;
+
+        connector.submit(&file.transaction).await?;
+
+        println!("\"{}\"", file.transaction.id());
+
+        Ok(())
+    }
+}
@@ -8,13 +8,14 @@ use cardano_connector_utxorpc::{
     Config as UtxoRpcRuntimeConfig, UtxoRpc, ensure_network_matches, live_network,
 };
 use cardano_sdk::{
-    Credential, Input, Network, Output, ProtocolParameters, Transaction,
+    Credential, Hash, Input, Network, Output, ProtocolParameters, Transaction, cbor,
     transaction::state::ReadyForSigning,
 };
+use konduit_tx::Utxos;
 use std::collections::BTreeMap;
 
 pub enum Connector {
-    Blockfrost(Blockfrost),
+    Blockfrost(Box<Blockfrost>),
     UtxoRpc(Box<UtxoRpc>),
 }
 
@@ -29,7 +30,9 @@ impl Connector {
     fn new_blockfrost(config: &BlockfrostConfig) -> anyhow::Result<Self> {
         let project_id = validated_blockfrost_project_id(config)?;
 
-        Ok(Self::Blockfrost(Blockfrost::new(project_id.to_string())))
+        Ok(Self::Blockfrost(Box::new(Blockfrost::new(
+            project_id.to_string(),
+        ))))
     }
 
     async fn new_utxorpc(config: &UtxoRpcConfig) -> anyhow::Result<Self> {
@@ -55,6 +58,54 @@ impl Connector {
     }
 }
 
+impl Connector {
+    /// A transaction, if the chain knows of it.
+    pub async fn transaction(&self, id: &Hash<32>) -> Result<Option<Transaction<ReadyForSigning>>> {
+        self.transaction_cbor(id)
+            .await?
+            // Unlike a plain decode, checks that the body re-encodes as is, so that the id holds.
+            .map(|bytes| Transaction::try_from(bytes.as_slice()))
+            .transpose()
+    }
+
+    /// The outputs behind some inputs, spent or not, as found in the transactions producing them.
+    /// Those that can't be found are left out.
+    pub async fn resolve(&self, inputs: impl IntoIterator<Item = Input>) -> Result<Utxos> {
+        let mut by_transaction = BTreeMap::<Hash<32>, Vec<Input>>::new();
+        for input in inputs {
+            by_transaction
+                .entry(input.transaction_id())
+                .or_default()
+                .push(input);
+        }
+
+        let mut utxos = Utxos::new();
+        for (id, inputs) in by_transaction {
+            let Some(bytes) = self.transaction_cbor(&id).await? else {
+                continue;
+            };
+            // Only outputs are needed here, so there's no need for the body to re-encode as is.
+            let transaction: Transaction<ReadyForSigning> = cbor::decode(&bytes)
+                .map_err(|e| anyhow!(e).context(format!("malformed transaction {id}")))?;
+            let outputs = transaction.outputs().collect::<Vec<_>>();
+            for input in inputs {
+                if let Some(output) = outputs.get(input.output_index() as usize) {
+                    utxos.insert(input, output.clone());
+                }
+            }
+        }
+
+        Ok(utxos)
+    }
+
+    async fn transaction_cbor(&self, id: &Hash<32>) -> Result<Option<Vec<u8>>> {
+        match self {
+            Self::Blockfrost(c) => c.transaction_cbor(id).await,
+            Self::UtxoRpc(c) => c.transaction_cbor(id).await,
+        }
+    }
+}
+
 fn validated_blockfrost_project_id(config: &BlockfrostConfig) -> anyhow::Result<&str> {
     let project_id = config.project_id.as_deref().ok_or_else(|| {
         anyhow!("Cardano backend blockfrost requires KONDUIT_BLOCKFROST_PROJECT_ID")
@@ -106,6 +157,13 @@ impl CardanoConnector for Connector {
             Self::UtxoRpc(c) => c.submit(transaction).await,
         }
     }
+
+    async fn confirmations(&self, id: &Hash<32>) -> Result<Option<u64>> {
+        match self {
+            Self::Blockfrost(c) => c.confirmations(id).await,
+            Self::UtxoRpc(c) => c.confirmations(id).await,
+        }
+    }
 }
 
 #[cfg(test)]
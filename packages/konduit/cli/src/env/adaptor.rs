@@ -17,7 +17,7 @@ pub struct Env {
     #[serde(flatten)]
     pub connector: ConnectorEnv,
 
-    // Wallet signing key (32 byte hex)
+    // Wallet signing key (32 byte hex, or 64 byte hex when derived from a mnemonic)
     #[arg(long, env = "KONDUIT_WALLET")]
     #[serde(rename = "KONDUIT_WALLET")]
     pub wallet: Option<LeakableSigningKey>,
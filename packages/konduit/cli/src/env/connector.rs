@@ -37,15 +37,23 @@ impl TryFrom<ConnectorEnv> for Connector {
 
     fn try_from(env: ConnectorEnv) -> Result<Self, Self::Error> {
         Ok(match env.backend {
-            Backend::Blockfrost => Connector::Blockfrost(Blockfrost {
-                network: env
-                    .network
-                    .or(infer_blockfrost_network(
-                        env.blockfrost_project_id.as_deref(),
-                    )?)
-                    .unwrap_or(Network::Mainnet),
-                project_id: normalize(env.blockfrost_project_id),
-            }),
+            Backend::Blockfrost => {
+                if let Some(network) = env.network.filter(Network::is_custom) {
+                    return Err(anyhow!(
+                        "Cardano backend blockfrost does not serve custom network {network}; use utxorpc"
+                    ));
+                }
+
+                Connector::Blockfrost(Blockfrost {
+                    network: env
+                        .network
+                        .or(infer_blockfrost_network(
+                            env.blockfrost_project_id.as_deref(),
+                        )?)
+                        .unwrap_or(Network::Mainnet),
+                    project_id: normalize(env.blockfrost_project_id),
+                })
+            }
             Backend::Utxorpc => Connector::UtxoRpc(UtxoRpc {
                 network: env
                     .network
@@ -218,6 +226,23 @@ mod tests {
         assert_eq!(filled.network, Some(Network::Preview));
     }
 
+    #[test]
+    fn blockfrost_rejects_custom_network() {
+        let env = ConnectorEnv {
+            backend: Backend::Blockfrost,
+            network: Some(Network::try_from("custom:42:1700000000").unwrap()),
+            blockfrost_project_id: None,
+            utxorpc_uri: None,
+        };
+
+        let error = match Connector::try_from(env) {
+            Ok(_) => panic!("custom network should fail with Blockfrost"),
+            Err(error) => error,
+        };
+
+        assert!(error.to_string().contains("custom network"));
+    }
+
     #[test]
     fn blockfrost_network_id_defaults_to_mainnet_when_unset() {
         let env = ConnectorEnv {
@@ -1,7 +1,13 @@
 mod default_path;
 mod fill;
+mod review;
 mod setup;
+mod signer;
+mod tx_file;
 
 pub use default_path::*;
 pub use fill::*;
+pub use review::*;
 pub use setup::*;
+pub use signer::*;
+pub use tx_file::*;
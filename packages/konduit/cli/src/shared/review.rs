@@ -0,0 +1,22 @@
+use anyhow::anyhow;
+use konduit_tx::Inspection;
+use std::io::{self, IsTerminal};
+
+/// Print the inspection of a transaction to stderr then, unless confirmed already, ask whether to
+/// proceed. For operators to review what they're about to sign or submit.
+pub fn review(inspection: &Inspection, action: &str, yes: bool) -> anyhow::Result<()> {
+    eprint!("{inspection}");
+    if yes {
+        return Ok(());
+    }
+    if !io::stdin().is_terminal() {
+        return Err(anyhow!("{action}: no terminal to confirm on; pass --yes"));
+    }
+    eprint!("{action}? [y/N] ");
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    if !matches!(answer.trim(), "y" | "Y" | "yes") {
+        return Err(anyhow!("aborted"));
+    }
+    Ok(())
+}
@@ -1,5 +1,9 @@
 use crate::shared::{DefaultPath, Fill};
-use std::io::IsTerminal;
+use cardano_sdk::{LeakableSigningKey, Mnemonic, SigningKey};
+use std::{
+    io::{self, IsTerminal},
+    str::FromStr,
+};
 
 pub trait Setup {
     fn setup(self) -> anyhow::Result<()>
@@ -13,3 +17,76 @@ pub trait Setup {
         Ok(())
     }
 }
+
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct SetupArgs {
+    /// Derive the wallet from a BIP39 mnemonic (CIP-1852), so that other Cardano wallets can
+    /// restore it. Reads the phrase from stdin when '-'. Without a phrase, a new 24-word one is
+    /// generated and printed to stderr.
+    #[arg(long, value_name = "PHRASE", num_args = 0..=1, default_missing_value = "")]
+    pub mnemonic: Option<String>,
+
+    /// Passphrase of the mnemonic, if any
+    #[arg(long, default_value = "", requires = "mnemonic")]
+    pub passphrase: String,
+
+    /// Account of the mnemonic wallet, whose first payment key becomes the Konduit wallet
+    #[arg(long, default_value_t = 0, requires = "mnemonic")]
+    pub account: u32,
+}
+
+impl SetupArgs {
+    /// The wallet derived from the mnemonic, when one is asked for; overriding any other.
+    pub fn wallet(&self) -> anyhow::Result<Option<LeakableSigningKey>> {
+        let mnemonic = match self.mnemonic.as_deref() {
+            None => return Ok(None),
+            Some("") => {
+                let mnemonic = Mnemonic::new();
+                eprintln!("New mnemonic; write it down, it won't be shown again:\n{mnemonic}");
+                mnemonic
+            }
+            Some("-") => {
+                let mut phrase = String::new();
+                io::stdin().read_line(&mut phrase)?;
+                Mnemonic::from_str(&phrase)?
+            }
+            Some(phrase) => Mnemonic::from_str(phrase)?,
+        };
+        let wallet = SigningKey::from_mnemonic(&mnemonic, &self.passphrase, self.account)?;
+        Ok(Some(wallet.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_the_wallet_from_a_mnemonic() {
+        assert!(SetupArgs::default().wallet().unwrap().is_none());
+
+        let args = SetupArgs {
+            mnemonic: Some(
+                "eight country switch draw meat scout mystery blade tip drift useless good keep usage title"
+                    .to_string(),
+            ),
+            ..SetupArgs::default()
+        };
+        let wallet = args.wallet().unwrap().unwrap();
+        assert!(wallet.is_extended());
+        assert_eq!(
+            wallet.to_verification_key(),
+            args.wallet().unwrap().unwrap().to_verification_key()
+        );
+
+        let other_account = SetupArgs { account: 1, ..args };
+        assert_ne!(
+            wallet.to_verification_key(),
+            other_account
+                .wallet()
+                .unwrap()
+                .unwrap()
+                .to_verification_key()
+        );
+    }
+}
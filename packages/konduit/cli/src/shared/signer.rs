@@ -0,0 +1,39 @@
+use cardano_sdk::SigningKey;
+use konduit_signer::Signer;
+use std::{path::PathBuf, sync::Arc};
+
+/// Where to sign with, when not with the configured wallet.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct SignerArgs {
+    /// Sign with the key of an encrypted keystore, rather than the configured wallet.
+    #[arg(long, env = "KONDUIT_KEYSTORE", conflicts_with = "remote_signer")]
+    pub keystore: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "KONDUIT_KEYSTORE_PASSWORD",
+        hide_env_values = true,
+        requires = "keystore"
+    )]
+    pub keystore_password: Option<String>,
+
+    /// Sign with a `konduit-signer serve` daemon listening on this socket, rather than the
+    /// configured wallet.
+    #[arg(long, env = "KONDUIT_REMOTE_SIGNER")]
+    pub remote_signer: Option<PathBuf>,
+}
+
+impl SignerArgs {
+    pub async fn signer(self, wallet: &SigningKey) -> anyhow::Result<Arc<dyn Signer>> {
+        match (self.keystore, self.remote_signer) {
+            (Some(path), _) => konduit_signer::Config::Keystore {
+                path,
+                password: self.keystore_password.unwrap_or_default(),
+            },
+            (None, Some(socket)) => konduit_signer::Config::Remote { socket },
+            (None, None) => konduit_signer::Config::SigningKey(wallet.clone()),
+        }
+        .build()
+        .await
+    }
+}
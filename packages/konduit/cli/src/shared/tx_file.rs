@@ -0,0 +1,140 @@
+use anyhow::{Context, anyhow};
+use cardano_sdk::{
+    Input, Output, ProtocolParameters, Transaction, cbor, transaction::state::ReadyForSigning,
+};
+use konduit_tx::{Inspection, Utxos};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// A transaction, along with the utxos it spends, references or uses as collateral; so that it
+/// can be reviewed and signed on a machine without access to the chain. This is what
+/// `--out-unsigned` writes, and what `sign` and `submit` read.
+#[derive(Debug, Clone)]
+pub struct TxFile {
+    pub transaction: Transaction<ReadyForSigning>,
+    pub utxos: Utxos,
+}
+
+/// The JSON representation, with CBOR in hex. Utxos are `[input, output]` pairs, as CIP-30
+/// wallets exchange them.
+#[derive(Debug, Serialize, Deserialize)]
+struct Encoded {
+    transaction: String,
+    utxos: Vec<String>,
+}
+
+impl TxFile {
+    /// Keep, out of the given utxos, only those the transaction resolves.
+    pub fn new(transaction: Transaction<ReadyForSigning>, utxos: &Utxos) -> Self {
+        let utxos = transaction
+            .inputs()
+            .chain(transaction.reference_inputs())
+            .chain(transaction.collaterals())
+            .filter_map(|input| Some((input.clone(), utxos.get(&input)?.clone())))
+            .collect();
+        Self { transaction, utxos }
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("cannot read transaction file {}", path.display()))?;
+        let encoded: Encoded = serde_json::from_str(&text)
+            .with_context(|| format!("malformed transaction file {}", path.display()))?;
+
+        let transaction = Transaction::from_str(&encoded.transaction)?;
+        let utxos = encoded
+            .utxos
+            .iter()
+            .map(|utxo| {
+                let bytes = hex::decode(utxo)?;
+                cbor::decode::<(Input, Output)>(&bytes)
+                    .map_err(|e| anyhow!(e).context("malformed utxo"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self { transaction, utxos })
+    }
+
+    pub fn inspect(&self, protocol_parameters: &ProtocolParameters) -> Inspection {
+        konduit_tx::inspect(&self.transaction, &self.utxos, protocol_parameters)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let encoded = Encoded {
+            transaction: hex::encode(cbor::ToCbor::to_cbor(&self.transaction)),
+            utxos: self
+                .utxos
+                .iter()
+                .map(|(input, output)| hex::encode(cbor::ToCbor::to_cbor(&(input, output))))
+                .collect(),
+        };
+        fs::write(path, serde_json::to_string_pretty(&encoded)? + "\n")
+            .with_context(|| format!("cannot write transaction file {}", path.display()))
+    }
+}
+
+/// Where the n-th of several transactions goes, when asked to save them at a single path: the
+/// path itself when there's only one, and `<path>.<n>` otherwise.
+pub fn nth_path(path: &Path, n: usize, count: usize) -> PathBuf {
+    if count == 1 {
+        return path.to_path_buf();
+    }
+    let mut path = path.as_os_str().to_owned();
+    path.push(format!(".{n}"));
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano_sdk::{Hash, SigningKey, Value};
+
+    #[test]
+    fn round_trips_through_files() {
+        let wallet = SigningKey::from([1; 32]);
+        let address = wallet
+            .to_verification_key()
+            .to_address(cardano_sdk::NetworkId::TESTNET);
+        let input = Input::new(Hash::<32>::from([2; 32]), 0);
+        let utxos = Utxos::from([
+            (
+                input.clone(),
+                Output::new(address.clone().into(), Value::new(5_000_000)),
+            ),
+            (
+                Input::new(Hash::<32>::from([3; 32]), 1),
+                Output::new(address.clone().into(), Value::new(1_000_000)),
+            ),
+        ]);
+
+        let mut transaction = Transaction::default();
+        transaction
+            .with_inputs([(input.clone(), None)])
+            .with_outputs([Output::new(address.into(), Value::new(4_800_000))])
+            .with_fee(200_000);
+        let mut transaction: Transaction<ReadyForSigning> =
+            cbor::decode(&cbor::ToCbor::to_cbor(&transaction)).unwrap();
+        transaction.sign(&wallet);
+
+        let path = std::env::temp_dir().join(format!("konduit-tx-file-{}", std::process::id()));
+        TxFile::new(transaction.clone(), &utxos)
+            .save(&path)
+            .unwrap();
+        let loaded = TxFile::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.transaction.to_string(), transaction.to_string());
+        assert_eq!(loaded.utxos.keys().collect::<Vec<_>>(), vec![&input]);
+    }
+
+    #[test]
+    fn numbers_paths_of_several_transactions() {
+        let path = Path::new("tx.json");
+        assert_eq!(nth_path(path, 0, 1), PathBuf::from("tx.json"));
+        assert_eq!(nth_path(path, 1, 2), PathBuf::from("tx.json.1"));
+    }
+}
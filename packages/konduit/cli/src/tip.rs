@@ -1,8 +1,8 @@
 use crate::config::{self};
 use cardano_connector::CardanoConnector;
-use cardano_sdk::{Address, Credential, Hash, Input, Value, address::kind};
+use cardano_sdk::{Address, Hash, Input, Value, address::kind};
 use konduit_data::{Pending, Used};
-use konduit_tx::{Channel, KONDUIT_VALIDATOR, Utxo, Utxos};
+use konduit_tx::{Channel, KONDUIT_VALIDATOR, KONDUIT_VALIDATORS, Utxo, Utxos};
 use std::{collections::BTreeMap, fmt};
 
 pub struct Consumer {
@@ -37,9 +37,7 @@ impl Consumer {
             .await?;
         let reference_script = get_script(connector, &config.host_address).await?;
         // FIXME :: NO STAKING
-        let konduit_utxos = connector
-            .utxos_at(&Credential::from_script(KONDUIT_VALIDATOR.hash), None)
-            .await?;
+        let konduit_utxos = konduit_utxos(connector).await?;
         let channels = filter_channels(&konduit_utxos, |co| co.constants().add_vkey == add_vkey)
             .into_iter()
             .collect();
@@ -62,6 +60,9 @@ impl fmt::Display for Consumer {
         for (input, channel) in self.channels.iter() {
             writeln!(f, "  Input : {}", input)?;
             writeln!(f, "  Tag : {}", channel.constants().tag)?;
+            if channel.is_outdated() {
+                writeln!(f, "  Validator : {} (outdated)", channel.own_hash())?;
+            }
             writeln!(
                 f,
                 "  Sub : {} || Close Period : {}",
@@ -93,9 +94,7 @@ impl Adaptor {
             .utxos_at(&own_address.payment(), own_address.delegation().as_ref())
             .await?;
         let reference_script = get_script(connector, &config.host_address).await?;
-        let konduit_utxos = connector
-            .utxos_at(&Credential::from_script(KONDUIT_VALIDATOR.hash), None)
-            .await?;
+        let konduit_utxos = konduit_utxos(connector).await?;
         let channels = filter_channels(&konduit_utxos, |co| co.constants().sub_vkey == sub_vkey)
             .into_iter()
             .collect();
@@ -163,6 +162,15 @@ impl fmt::Display for Admin {
     }
 }
 
+/// Utxos at any known validator version.
+async fn konduit_utxos(connector: &impl CardanoConnector) -> anyhow::Result<Utxos> {
+    let mut utxos = Utxos::new();
+    for credential in KONDUIT_VALIDATORS.credentials() {
+        utxos.extend(connector.utxos_at(&credential, None).await?);
+    }
+    Ok(utxos)
+}
+
 async fn get_script(
     connector: &impl CardanoConnector,
     host_address: &Address<kind::Shelley>,
@@ -1,6 +1,6 @@
 use crate::core::{
-    AdaptorInfo, Invoice, Keytag, Locked, PayBody, Quote, QuoteBody, Receipt, Squash, SquashStatus,
-    Tag, TxHelp,
+    AdaptorInfo, Invoice, Keytag, Locked, PayBody, PlutusData, Quote, QuoteBody, Receipt, Squash,
+    SquashStatus, Tag, TxHelp, cbor::ToCbor,
 };
 use anyhow::anyhow;
 use http_client::{HeaderPolicy, Transport, codec, header_policy};
@@ -72,12 +72,12 @@ impl<T: Transport> Adaptor<T> {
     }
 
     pub async fn quote(&self, invoice: &Invoice) -> anyhow::Result<Quote> {
+        self.quote_for(&QuoteBody::Bolt11(invoice.clone())).await
+    }
+
+    pub async fn quote_for(&self, body: &QuoteBody) -> anyhow::Result<Quote> {
         self.http_client
-            .post_with_headers::<QuoteBody, Quote>(
-                "/ch/quote",
-                &QuoteBody::Bolt11(invoice.clone()),
-                self.with_keytag_header(),
-            )
+            .post_with_headers::<QuoteBody, Quote>("/ch/quote", body, self.with_keytag_header())
             .await
             .map_err(|e| anyhow!(e))
     }
@@ -100,15 +100,17 @@ impl<T: Transport> Adaptor<T> {
     // FIXME : This used to be cbor,
     // but everything else is json.
     // The newer http_client does not support switching between encodings.
-    // Rather than hacking this back to where it was,
-    // we need to fix this elsewhere: the server, and then permit the client to
-    // switch between json and cbor.
+    // The server accepts a json string holding the hex-encoded cbor of the squash,
+    // so we send that until the client can switch between json and cbor.
     pub async fn squash(&self, squash: Squash) -> anyhow::Result<SquashStatus> {
-        let mut headers = self.with_keytag_header();
-        headers.push(header_policy::ContentType::from_encoder::<()>(&codec::Cbor).boxed());
+        let body = hex::encode(PlutusData::from(squash).to_cbor());
 
         self.http_client
-            .post_with_headers::<Squash, SquashStatus>("/ch/squash", &squash, headers)
+            .post_with_headers::<String, SquashStatus>(
+                "/ch/squash",
+                &body,
+                self.with_keytag_header(),
+            )
             .await
             .map_err(|e| anyhow!(e))
     }
@@ -0,0 +1,484 @@
+use crate::core::{
+    Channel, Cheque, Cont, Duration, Hash, Input, Lock, PlutusData, Redeemer, Squash, Step, Tag,
+    TransactionSummary, VerificationKey,
+};
+use std::{collections::BTreeMap, fmt};
+
+/// What the consumer remembers of a cheque it has issued.
+#[derive(Debug, Clone)]
+pub struct Issued {
+    pub amount: u64,
+    pub timeout: Duration,
+}
+
+/// A discrepancy found in a transaction spending one of the consumer's channels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub transaction: Hash<32>,
+    pub tag: Tag,
+    pub discrepancy: Discrepancy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// The redeemer could not be decoded, or does not line up with the channels spent.
+    Undecodable(String),
+    /// A squash that does not verify against the consumer's key.
+    InvalidSquash { index: u64 },
+    /// A cheque that does not verify against the consumer's key, or whose secret does not
+    /// match its lock.
+    InvalidCheque { index: u64, lock: Lock },
+    /// A cheque the consumer has no record of.
+    UnknownCheque { index: u64, lock: Lock, amount: u64 },
+    /// A cheque whose amount differs from what the consumer issued.
+    AmountMismatch {
+        lock: Lock,
+        issued: u64,
+        claimed: u64,
+    },
+    /// A cheque used after it has timed out, from the consumer's standpoint.
+    ExpiredCheque {
+        lock: Lock,
+        timeout: Duration,
+        at: Duration,
+    },
+    /// The adaptor claims more than the consumer has ever issued.
+    Overclaimed { issued: u64, claimed: u64 },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undecodable(reason) => write!(f, "undecodable redeemer: {reason}"),
+            Self::InvalidSquash { index } => write!(f, "invalid squash signature (index={index})"),
+            Self::InvalidCheque { index, lock } => {
+                write!(f, "invalid cheque (index={index}, lock={lock})")
+            }
+            Self::UnknownCheque {
+                index,
+                lock,
+                amount,
+            } => write!(
+                f,
+                "unknown cheque (index={index}, lock={lock}, amount={amount})"
+            ),
+            Self::AmountMismatch {
+                lock,
+                issued,
+                claimed,
+            } => write!(
+                f,
+                "amount mismatch (lock={lock}, issued={issued}, claimed={claimed})"
+            ),
+            Self::ExpiredCheque { lock, timeout, at } => {
+                write!(
+                    f,
+                    "expired cheque (lock={lock}, timeout={timeout}, at={at})"
+                )
+            }
+            Self::Overclaimed { issued, claimed } => {
+                write!(f, "overclaimed (issued={issued}, claimed={claimed})")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tx={} tag={}: {}",
+            self.transaction, self.tag, self.discrepancy
+        )
+    }
+}
+
+/// Check the adaptor's steps (sub, respond) on the consumer's channel of the given tag, found in
+/// the given transactions, against what the consumer knows it has issued on it.
+///
+/// `issued` ought to hold every cheque issued on that channel, including those since
+/// squashed: amounts claimed by the adaptor are checked against their total. Cheques whose payment
+/// was not recorded are known by their lock only: neither they nor the total are checked then.
+/// Transactions that do not spend the channel are ignored, as are mutual transactions, which the
+/// consumer has signed anyway.
+pub fn audit(
+    consumer: &VerificationKey,
+    tag: &Tag,
+    issued: &BTreeMap<Lock, Option<Issued>>,
+    transactions: &[TransactionSummary],
+) -> Vec<Finding> {
+    transactions
+        .iter()
+        .flat_map(|tx| audit_transaction(consumer, tag, issued, tx))
+        .collect()
+}
+
+fn audit_transaction(
+    consumer: &VerificationKey,
+    tag: &Tag,
+    issued: &BTreeMap<Lock, Option<Issued>>,
+    tx: &TransactionSummary,
+) -> Vec<Finding> {
+    // Each validator version runs on its own, with its own 'Main' redeemer.
+    let mut validators = BTreeMap::<Hash<28>, Vec<(&Input, Channel)>>::new();
+    for (input, output, _) in tx.inputs.iter() {
+        if let Ok(channel) = Channel::try_from(output) {
+            validators
+                .entry(*channel.own_hash())
+                .or_default()
+                .push((input, channel));
+        }
+    }
+
+    validators
+        .into_values()
+        .flat_map(|channels| audit_validator(consumer, tag, issued, tx, channels))
+        .collect()
+}
+
+fn audit_validator(
+    consumer: &VerificationKey,
+    tag: &Tag,
+    issued: &BTreeMap<Lock, Option<Issued>>,
+    tx: &TransactionSummary,
+    mut channels: Vec<(&Input, Channel)>,
+) -> Vec<Finding> {
+    // Steps in the 'Main' redeemer line up with the validator's inputs, in input order.
+    channels.sort_by_key(|(input, _)| *input);
+
+    let ours =
+        |channel: &Channel| channel.constants().add_vkey == *consumer && channel.tag() == tag;
+    if !channels.iter().any(|(_, channel)| ours(channel)) {
+        return vec![];
+    }
+
+    let finding = |tag: &Tag, discrepancy| Finding {
+        transaction: tx.id,
+        tag: tag.clone(),
+        discrepancy,
+    };
+
+    let redeemers = tx
+        .redeemers
+        .iter()
+        .map(|(input, data)| (input, data))
+        .collect::<BTreeMap<&Input, &PlutusData>>();
+
+    let mut steps = None;
+    for (input, channel) in channels.iter() {
+        let Some(data) = redeemers.get(input) else {
+            continue;
+        };
+        match Redeemer::from_plutus_data(data, channel.version()) {
+            Ok(Redeemer::Main(main)) => {
+                steps = Some(main);
+                break;
+            }
+            Ok(Redeemer::Defer) | Ok(Redeemer::Mutual) => {}
+            Err(err) => {
+                return vec![finding(
+                    channel.tag(),
+                    Discrepancy::Undecodable(err.to_string()),
+                )];
+            }
+        }
+    }
+
+    let Some(steps) = steps else {
+        return vec![];
+    };
+
+    if steps.len() != channels.len() {
+        let (_, channel) = channels
+            .iter()
+            .find(|(_, channel)| ours(channel))
+            .expect("checked above");
+        return vec![finding(
+            channel.tag(),
+            Discrepancy::Undecodable(format!(
+                "{} step(s) for {} channel(s)",
+                steps.len(),
+                channels.len()
+            )),
+        )];
+    }
+
+    let at = Duration::from_secs(tx.timestamp_secs);
+
+    channels
+        .iter()
+        .zip(steps)
+        .filter(|((_, channel), _)| ours(channel))
+        .flat_map(|((_, channel), step)| {
+            let tag = channel.tag();
+            let cheques = match step {
+                Step::Cont(Cont::Sub(squash, unlockeds)) => Some((
+                    squash,
+                    unlockeds.into_iter().map(Cheque::from).collect::<Vec<_>>(),
+                )),
+                Step::Cont(Cont::Respond(squash, cheques)) => Some((squash, cheques)),
+                _ => None,
+            };
+            cheques
+                .map(|(squash, cheques)| {
+                    check_claims(consumer, tag, issued, &at, &squash, &cheques)
+                })
+                .unwrap_or_default()
+                .into_iter()
+                .map(|discrepancy| finding(tag, discrepancy))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn check_claims(
+    consumer: &VerificationKey,
+    tag: &Tag,
+    issued: &BTreeMap<Lock, Option<Issued>>,
+    at: &Duration,
+    squash: &Squash,
+    cheques: &[Cheque],
+) -> Vec<Discrepancy> {
+    let mut discrepancies = vec![];
+
+    if !squash.verify(consumer, tag) {
+        discrepancies.push(Discrepancy::InvalidSquash {
+            index: squash.index(),
+        });
+    }
+
+    for cheque in cheques {
+        let lock = *cheque.lock();
+
+        if !cheque.verify(consumer, tag) {
+            discrepancies.push(Discrepancy::InvalidCheque {
+                index: cheque.index(),
+                lock,
+            });
+        }
+
+        match issued.get(&lock) {
+            None => discrepancies.push(Discrepancy::UnknownCheque {
+                index: cheque.index(),
+                lock,
+                amount: cheque.amount(),
+            }),
+            Some(None) => {}
+            Some(Some(known)) => {
+                if known.amount != cheque.amount() {
+                    discrepancies.push(Discrepancy::AmountMismatch {
+                        lock,
+                        issued: known.amount,
+                        claimed: cheque.amount(),
+                    });
+                }
+                let timeout = std::cmp::min(known.timeout, *cheque.timeout());
+                if timeout < *at {
+                    discrepancies.push(Discrepancy::ExpiredCheque {
+                        lock,
+                        timeout,
+                        at: *at,
+                    });
+                }
+            }
+        }
+    }
+
+    let total_issued = issued
+        .values()
+        .map(|known| known.as_ref().map(|known| known.amount))
+        .sum::<Option<u64>>();
+    let claimed = squash.amount()
+        + cheques
+            .iter()
+            .filter(|cheque| !squash.is_index_squashed(cheque.index()))
+            .map(Cheque::amount)
+            .sum::<u64>();
+    if let Some(total_issued) = total_issued
+        && claimed > total_issued
+    {
+        discrepancies.push(Discrepancy::Overclaimed {
+            issued: total_issued,
+            claimed,
+        });
+    }
+
+    discrepancies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Discrepancy, Issued, audit};
+    use crate::core::{
+        ChequeBody, Constants, Cont, Duration, Hash, Indexes, Input, Lock, Locked, NetworkId, Open,
+        PlutusData, Redeemer, Secret, SigningKey, Squash, SquashBody, Step, Tag,
+        TransactionSummary, Unlocked,
+    };
+    use std::collections::BTreeMap;
+
+    const NOW_SECS: u64 = 1_800_000_000;
+
+    fn consumer() -> SigningKey {
+        SigningKey::from([1; 32])
+    }
+
+    fn tag() -> Tag {
+        Tag::from(vec![1, 2, 3])
+    }
+
+    fn unlocked(signing_key: &SigningKey, index: u64, amount: u64, secret: u8) -> Unlocked {
+        let secret = Secret([secret; 32]);
+        let body = ChequeBody::new(
+            index,
+            amount,
+            Duration::from_secs(NOW_SECS + 3600),
+            Lock::from(secret.clone()),
+        );
+        Unlocked::new(Locked::make(signing_key, &tag(), body), secret).unwrap()
+    }
+
+    fn sub_transaction(squash: Squash, unlockeds: Vec<Unlocked>) -> TransactionSummary {
+        let constants = Constants {
+            tag: tag(),
+            add_vkey: consumer().to_verification_key(),
+            sub_vkey: SigningKey::from([2; 32]).to_verification_key(),
+            close_period: Duration::from_secs(3600),
+        };
+        let input = Input::new(Hash::<32>::from([9; 32]), 0);
+        let output = Open::new(10_000_000, constants, None).output(NetworkId::TESTNET);
+        let redeemer = Redeemer::Main(vec![Step::Cont(Cont::Sub(squash, unlockeds))]);
+        TransactionSummary {
+            id: Hash::<32>::from([7; 32]),
+            index: 0,
+            depth: 1,
+            inputs: vec![(input.clone(), output, None)],
+            outputs: vec![],
+            timestamp_secs: NOW_SECS,
+            redeemers: vec![(input, PlutusData::from(redeemer))],
+        }
+    }
+
+    fn issued(unlockeds: &[&Unlocked]) -> BTreeMap<Lock, Option<Issued>> {
+        unlockeds
+            .iter()
+            .map(|u| {
+                (
+                    *u.lock(),
+                    Some(Issued {
+                        amount: u.amount(),
+                        timeout: *u.timeout(),
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    fn squash(signing_key: &SigningKey, amount: u64) -> Squash {
+        Squash::make(
+            signing_key,
+            &tag(),
+            SquashBody::new_no_verify(amount, 0, Indexes::default()),
+        )
+    }
+
+    #[test]
+    fn honest_sub_has_no_findings() {
+        let cheque = unlocked(&consumer(), 1, 1_000, 42);
+        let tx = sub_transaction(squash(&consumer(), 0), vec![cheque.clone()]);
+        let findings = audit(
+            &consumer().to_verification_key(),
+            &tag(),
+            &issued(&[&cheque]),
+            &[tx],
+        );
+        assert_eq!(findings, vec![]);
+    }
+
+    #[test]
+    fn unknown_and_forged_cheques_are_reported() {
+        let known = unlocked(&consumer(), 1, 1_000, 42);
+        let unknown = unlocked(&consumer(), 2, 500, 43);
+        let forged = unlocked(&SigningKey::from([3; 32]), 3, 500, 44);
+        let tx = sub_transaction(
+            squash(&consumer(), 0),
+            vec![known.clone(), unknown.clone(), forged.clone()],
+        );
+        let discrepancies = audit(
+            &consumer().to_verification_key(),
+            &tag(),
+            &issued(&[&known, &forged]),
+            &[tx],
+        )
+        .into_iter()
+        .map(|finding| finding.discrepancy)
+        .collect::<Vec<_>>();
+        assert!(discrepancies.contains(&Discrepancy::UnknownCheque {
+            index: 2,
+            lock: *unknown.lock(),
+            amount: 500,
+        }));
+        assert!(discrepancies.contains(&Discrepancy::InvalidCheque {
+            index: 3,
+            lock: *forged.lock(),
+        }));
+    }
+
+    #[test]
+    fn overclaims_and_expired_cheques_are_reported() {
+        let cheque = unlocked(&consumer(), 1, 1_000, 42);
+        let mut known = issued(&[&cheque]);
+        known
+            .get_mut(cheque.lock())
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .timeout = Duration::from_secs(NOW_SECS - 1);
+        let tx = sub_transaction(squash(&consumer(), 5_000), vec![cheque.clone()]);
+        let discrepancies = audit(&consumer().to_verification_key(), &tag(), &known, &[tx])
+            .into_iter()
+            .map(|finding| finding.discrepancy)
+            .collect::<Vec<_>>();
+        assert!(discrepancies.contains(&Discrepancy::Overclaimed {
+            issued: 1_000,
+            claimed: 6_000,
+        }));
+        assert!(discrepancies.iter().any(
+            |d| matches!(d, Discrepancy::ExpiredCheque { lock, .. } if lock == cheque.lock())
+        ));
+    }
+
+    #[test]
+    fn cheques_of_unrecorded_payment_are_not_held_against_the_adaptor() {
+        let cheque = unlocked(&consumer(), 1, 1_000, 42);
+        let mut known = issued(&[&cheque]);
+        // Squashed long ago, with nothing but its lock recorded.
+        known.insert(Lock([9; 32]), None);
+        let tx = sub_transaction(squash(&consumer(), 5_000), vec![cheque]);
+        let findings = audit(&consumer().to_verification_key(), &tag(), &known, &[tx]);
+        assert_eq!(findings, vec![]);
+    }
+
+    #[test]
+    fn foreign_channels_are_ignored() {
+        let cheque = unlocked(&consumer(), 1, 1_000, 42);
+        let tx = sub_transaction(squash(&consumer(), 0), vec![cheque]);
+        let findings = audit(
+            &SigningKey::from([5; 32]).to_verification_key(),
+            &tag(),
+            &BTreeMap::new(),
+            &[tx],
+        );
+        assert_eq!(findings, vec![]);
+
+        // Nor are the consumer's other channels, on whose cheques 'issued' has nothing to say.
+        let cheque = unlocked(&consumer(), 1, 1_000, 42);
+        let tx = sub_transaction(squash(&consumer(), 5_000), vec![cheque]);
+        let findings = audit(
+            &consumer().to_verification_key(),
+            &Tag::from(vec![9]),
+            &BTreeMap::new(),
+            &[tx],
+        );
+        assert_eq!(findings, vec![]);
+    }
+}
@@ -1,6 +1,9 @@
-use crate::core::{SigningKey, SquashStatus, Tag};
+use crate::core::{Address, SigningKey, SquashStatus, Tag, address::kind};
 use clap::Parser;
-use std::{io, io::Write};
+use std::{io, io::Write, path::PathBuf, time::Duration};
+
+mod state;
+pub use state::{ChannelState, Payment, State};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about = "Konduit Consumer CLI")]
@@ -24,6 +27,22 @@ pub struct Cli {
     #[arg(long, env = "KONDUIT_TAG")]
     pub tag: Tag,
 
+    /// Optional URL of a Cardano connector. Required for on-chain (L1) commands.
+    #[arg(long, env = "KONDUIT_CONNECTOR_URL")]
+    pub connector_url: Option<String>,
+
+    /// Address holding the Konduit validator reference script. Required to submit L1 steps.
+    #[arg(long, env = "KONDUIT_HOST_ADDRESS")]
+    pub host_address: Option<Address<kind::Shelley>>,
+
+    /// Local state file, tracking cheques issued by this consumer.
+    #[arg(
+        long,
+        env = "KONDUIT_STATE_FILE",
+        default_value = ".konduit-client.json"
+    )]
+    pub state_file: PathBuf,
+
     /// Optional LND REST URL
     #[arg(long, env = "LND_BASE_URL")]
     pub lnd_url: Option<String>,
@@ -49,6 +68,27 @@ pub enum Commands {
     Pay { invoice: String },
     /// Manually squash using the latest state
     Squash,
+    /// Show the latest receipt held by the adaptor
+    Receipt,
+    /// List channels on-chain belonging to the consumer
+    Channels,
+    /// Close the channel, held at an older validator version, and open one with the same adaptor
+    /// and amount at the latest version
+    Migrate {
+        /// Tag of the new channel; must differ from the current one.
+        to: Tag,
+    },
+    /// Check the adaptor's on-chain steps on the consumer's channels against issued cheques
+    Audit,
+    /// Watch the consumer's channels, and submit elapse, expire and end steps as they fall due
+    Watch {
+        /// Time between two checks.
+        #[arg(long, value_parser = humantime::parse_duration, default_value = "1m")]
+        every: Duration,
+        /// Check once, then exit
+        #[arg(long, default_value_t = false)]
+        once: bool,
+    },
 }
 
 pub fn confirm(prompt: &str) -> anyhow::Result<bool> {
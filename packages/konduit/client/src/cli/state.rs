@@ -0,0 +1,204 @@
+use crate::core::{Keytag, Lock, Locked};
+use anyhow::Context;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Consumer-side bookkeeping persisted between invocations of the CLI.
+///
+/// The adaptor is the one holding receipts. What the consumer needs to remember is which cheques
+/// it has actually issued, so that it can refuse to squash anything else.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    #[serde(default)]
+    channels: BTreeMap<String, ChannelState>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ChannelState {
+    /// Locked cheques issued, and not yet known to be squashed nor expired.
+    #[serde(default)]
+    pub lockeds: BTreeMap<Lock, Payment>,
+    /// Cheques that have been squashed. Kept around to audit what the adaptor claims on-chain.
+    /// Those squashed before payments were recorded along with them have none.
+    #[serde(default, deserialize_with = "squashed")]
+    pub squashed: BTreeMap<Lock, Option<Payment>>,
+    /// Cheques that timed out before being squashed. No longer squashable, but still issued: the
+    /// adaptor may have legitimately used them on-chain before they timed out.
+    #[serde(default)]
+    pub expired: BTreeMap<Lock, Payment>,
+}
+
+/// Squashed cheques, as recorded now, or as a mere list of locks by older versions.
+fn squashed<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<Lock, Option<Payment>>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Squashed {
+        Payments(BTreeMap<Lock, Option<Payment>>),
+        Locks(Vec<Lock>),
+    }
+    Ok(match Squashed::deserialize(deserializer)? {
+        Squashed::Payments(payments) => payments,
+        Squashed::Locks(locks) => locks.into_iter().map(|lock| (lock, None)).collect(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payment {
+    pub invoice: String,
+    pub amount: u64,
+    /// Cheque timeout, as posix time in milliseconds.
+    pub timeout_ms: u64,
+}
+
+impl Payment {
+    /// The payment a signed cheque makes, so that what is recorded is what was signed.
+    pub fn new(invoice: String, locked: &Locked) -> Self {
+        Self {
+            invoice,
+            amount: locked.amount(),
+            timeout_ms: locked.timeout().as_millis() as u64,
+        }
+    }
+}
+
+impl State {
+    /// Load the state from disk. A missing file is an empty state.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !fs::exists(path)? {
+            return Ok(Self::default());
+        }
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse state file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn channel(&self, keytag: &Keytag) -> ChannelState {
+        self.channels
+            .get(&keytag.to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn channel_mut(&mut self, keytag: &Keytag) -> &mut ChannelState {
+        self.channels.entry(keytag.to_string()).or_default()
+    }
+}
+
+impl ChannelState {
+    pub fn is_known(&self, lock: &Lock) -> bool {
+        self.lockeds.contains_key(lock)
+    }
+
+    pub fn issue(&mut self, lock: Lock, payment: Payment) {
+        self.lockeds.insert(lock, payment);
+    }
+
+    pub fn settle(&mut self, locks: impl IntoIterator<Item = Lock>) {
+        for lock in locks {
+            if let Some(payment) = self.lockeds.remove(&lock) {
+                self.squashed.insert(lock, Some(payment));
+            }
+        }
+    }
+
+    /// Every cheque issued on the channel, squashed, expired or neither, with its payment if
+    /// recorded.
+    pub fn issued(&self) -> impl Iterator<Item = (&Lock, Option<&Payment>)> {
+        self.squashed
+            .iter()
+            .map(|(lock, payment)| (lock, payment.as_ref()))
+            .chain(
+                self.expired
+                    .iter()
+                    .map(|(lock, payment)| (lock, Some(payment))),
+            )
+            .chain(
+                self.lockeds
+                    .iter()
+                    .map(|(lock, payment)| (lock, Some(payment))),
+            )
+    }
+
+    /// Set aside cheques whose timeout is before `now_ms`: they can no longer be unlocked.
+    pub fn expire(&mut self, now_ms: u64) -> Vec<Lock> {
+        let expired = self
+            .lockeds
+            .iter()
+            .filter(|(_, payment)| payment.timeout_ms < now_ms)
+            .map(|(lock, _)| *lock)
+            .collect::<Vec<_>>();
+        for lock in expired.iter() {
+            if let Some(payment) = self.lockeds.remove(lock) {
+                self.expired.insert(*lock, payment);
+            }
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ChequeBody, Duration, SigningKey, Tag};
+
+    #[test]
+    fn payments_are_recorded_as_signed() {
+        let body = ChequeBody::new(1, 500, Duration::from_millis(1_234_567), Lock([1; 32]));
+        let locked = Locked::make(
+            &SigningKey::from([1; 32]),
+            &Tag::from(b"tag".to_vec()),
+            body,
+        );
+        let payment = Payment::new("lnbc1".to_string(), &locked);
+        assert_eq!(payment.amount, 500);
+        assert_eq!(payment.timeout_ms, 1_234_567);
+    }
+
+    #[test]
+    fn expired_cheques_are_still_issued() {
+        let payment = |timeout_ms| Payment {
+            invoice: "lnbc1".to_string(),
+            amount: 100,
+            timeout_ms,
+        };
+        let mut channel = ChannelState::default();
+        channel.issue(Lock([1; 32]), payment(1_000));
+        channel.issue(Lock([2; 32]), payment(3_000));
+        channel.issue(Lock([3; 32]), payment(3_000));
+        channel.settle([Lock([3; 32])]);
+
+        assert_eq!(channel.expire(2_000), vec![Lock([1; 32])]);
+        assert!(!channel.is_known(&Lock([1; 32])));
+        assert_eq!(
+            channel.issued().map(|(lock, _)| *lock).collect::<Vec<_>>(),
+            vec![Lock([3; 32]), Lock([1; 32]), Lock([2; 32])]
+        );
+    }
+
+    #[test]
+    fn loads_squashed_locks_of_older_state_files() {
+        let lock = Lock([4; 32]);
+        let json = serde_json::json!({
+            "channels": {
+                "keytag": { "lockeds": {}, "squashed": [lock] }
+            }
+        });
+        let state = serde_json::from_value::<State>(json).unwrap();
+        let channel = &state.channels["keytag"];
+        assert_eq!(channel.issued().collect::<Vec<_>>(), vec![(&lock, None)]);
+
+        // Saved anew, the state loads as is.
+        let state = serde_json::from_slice::<State>(&serde_json::to_vec(&state).unwrap()).unwrap();
+        assert_eq!(
+            state.channels["keytag"].squashed,
+            BTreeMap::from([(lock, None)])
+        );
+    }
+}
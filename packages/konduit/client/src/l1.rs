@@ -1,8 +1,12 @@
-use crate::core::{
-    Address, Bounds, Channel, Credential, Hash, Input, KONDUIT_VALIDATOR, NetworkId,
-    NetworkParameters, Output, SigningKey, Tag,
-    address::kind,
-    consumer::{self, Intent, OpenIntent},
+use crate::{
+    core::{
+        Address, Bounds, Channel, Credential, Hash, Input, KONDUIT_VALIDATORS, NetworkId,
+        NetworkParameters, Output, SigningKey, Tag, Transaction, Utxos, VerificationKey,
+        address::kind,
+        consumer::{self, Funding, Intent, OpenIntent},
+        transaction::state::ReadyForSigning,
+    },
+    watchdog::{self, Tick},
 };
 use anyhow::anyhow;
 use cardano_connector::CardanoConnector;
@@ -26,19 +30,14 @@ where
     }
 
     /// Check the chain for opened channels that match the client's credentials, regardless of the
-    /// adaptor they're configured with.
+    /// adaptor they're configured with, nor the validator version they're held at.
     pub async fn channels(
         &self,
         stake_credential: Option<&Credential>,
     ) -> anyhow::Result<impl Iterator<Item = Channel>> {
         let consumer = self.consumer.to_verification_key();
 
-        let utxos_konduit = all_utxos_at(
-            self.connector,
-            &KONDUIT_VALIDATOR.to_credential(),
-            stake_credential,
-        )
-        .await?;
+        let utxos_konduit = all_konduit_utxos(self.connector, stake_credential).await?;
 
         let consumer_channels = utxos_konduit
             .into_iter()
@@ -49,6 +48,7 @@ where
     }
 
     /// Execute the given intents on any compatible channel owned by the client's credentials.
+    /// Newly opened channels are delegated to the stake credential, if any.
     pub async fn execute(
         &self,
         wallet_sk: &SigningKey,
@@ -57,68 +57,221 @@ where
         intents: BTreeMap<Tag, Intent>,
         script_deployment_address: &Address<kind::Shelley>,
     ) -> anyhow::Result<Hash<32>> {
-        let network_parameters = NetworkParameters {
-            network_id: NetworkId::from(self.connector.network()),
-            protocol_parameters: self.connector.protocol_parameters().await?,
-        };
-
         let consumer_sk = self.consumer;
         let consumer_vk = self.consumer.to_verification_key();
 
         let wallet_vk = wallet_sk.to_verification_key();
 
-        let utxos_script_ref = self
-            .connector
-            .utxos_at(
-                &script_deployment_address.payment(),
-                script_deployment_address.delegation().as_ref(),
+        let (mut tx, _) = self
+            .prepare(
+                &wallet_vk,
+                stake_credential,
+                opens,
+                intents,
+                script_deployment_address,
             )
             .await?;
 
-        let utxos_konduit = all_utxos_at(
+        tx.sign_with(|msg| (consumer_vk, consumer_sk.sign(msg)));
+
+        if wallet_vk != consumer_vk {
+            tx.sign_with(|msg| (wallet_vk, wallet_sk.sign(msg)));
+        }
+
+        self.submit(&tx).await
+    }
+
+    /// Like 'execute', but without signing nor submitting: the transaction comes back unsigned,
+    /// along with the utxos it was built from. It needs the witnesses of both the consumer and
+    /// the wallet.
+    pub async fn prepare(
+        &self,
+        wallet_vk: &VerificationKey,
+        stake_credential: Option<&Credential>,
+        opens: Vec<OpenIntent>,
+        intents: BTreeMap<Tag, Intent>,
+        script_deployment_address: &Address<kind::Shelley>,
+    ) -> anyhow::Result<(Transaction<ReadyForSigning>, Utxos)> {
+        let (network_parameters, utxos) = self
+            .utxos(stake_credential, &opens, script_deployment_address)
+            .await?;
+
+        let utxos_wallet = all_utxos_at(
             self.connector,
-            &KONDUIT_VALIDATOR.to_credential(),
+            &Credential::from(wallet_vk),
             stake_credential,
         )
-        .await?
-        .collect::<Vec<_>>();
+        .await?;
 
-        let utxos_wallet = Box::new(
-            all_utxos_at(
-                self.connector,
-                &Credential::from(&wallet_vk),
-                stake_credential,
-            )
-            .await?,
-        );
+        let utxos = utxos.into_iter().chain(utxos_wallet).collect();
 
-        if opens.is_empty() && utxos_konduit.is_empty() {
-            return Err(anyhow!("nothing to do"));
-        }
+        let tx = consumer::tx(
+            &network_parameters,
+            wallet_vk,
+            stake_credential,
+            opens,
+            intents,
+            &utxos,
+            Bounds::twenty_mins(),
+        )?;
+
+        Ok((tx, utxos))
+    }
+
+    /// Like 'execute', but funded by another wallet, such as a CIP-30 browser wallet. The
+    /// transaction comes back signed by the consumer only: the funding wallet must witness it in
+    /// turn, before it goes through 'submit'.
+    pub async fn prepare_funded(
+        &self,
+        funding: &Funding,
+        stake_credential: Option<&Credential>,
+        opens: Vec<OpenIntent>,
+        intents: BTreeMap<Tag, Intent>,
+        script_deployment_address: &Address<kind::Shelley>,
+    ) -> anyhow::Result<Transaction<ReadyForSigning>> {
+        let consumer_sk = self.consumer;
+        let consumer_vk = self.consumer.to_verification_key();
 
-        let mut tx = consumer::tx(
+        let (network_parameters, utxos) = self
+            .utxos(stake_credential, &opens, script_deployment_address)
+            .await?;
+
+        let mut tx = consumer::funded_tx(
             &network_parameters,
-            &wallet_vk,
+            &consumer_vk,
+            stake_credential,
             opens,
             intents,
-            &std::iter::empty()
-                .chain(utxos_script_ref)
-                .chain(utxos_konduit)
-                .chain(utxos_wallet)
-                .collect(),
+            &utxos,
+            funding,
             Bounds::twenty_mins(),
         )?;
 
         tx.sign_with(|msg| (consumer_vk, consumer_sk.sign(msg)));
 
-        if wallet_vk != consumer_vk {
-            tx.sign_with(|msg| (wallet_vk, wallet_sk.sign(msg)));
+        Ok(tx)
+    }
+
+    /// Submit a fully witnessed transaction, such as one from 'prepare_funded'.
+    pub async fn submit(&self, tx: &Transaction<ReadyForSigning>) -> anyhow::Result<Hash<32>> {
+        self.connector.submit(tx).await?;
+        Ok(tx.id())
+    }
+
+    /// The network parameters, and the utxos any consumer transaction needs besides its fuel:
+    /// script references and channels.
+    async fn utxos(
+        &self,
+        stake_credential: Option<&Credential>,
+        opens: &[OpenIntent],
+        script_deployment_address: &Address<kind::Shelley>,
+    ) -> anyhow::Result<(NetworkParameters, Utxos)> {
+        let network_parameters = NetworkParameters {
+            network_id: NetworkId::from(self.connector.network()),
+            protocol_parameters: self.connector.protocol_parameters().await?,
+        };
+
+        let utxos_script_ref = self
+            .connector
+            .utxos_at(
+                &script_deployment_address.payment(),
+                script_deployment_address.delegation().as_ref(),
+            )
+            .await?;
+
+        let utxos_konduit = all_konduit_utxos(self.connector, stake_credential)
+            .await?
+            .collect::<Vec<_>>();
+
+        if opens.is_empty() && utxos_konduit.is_empty() {
+            return Err(anyhow!("nothing to do"));
         }
 
-        self.connector.submit(&tx).await?;
+        let utxos = std::iter::empty()
+            .chain(utxos_script_ref)
+            .chain(utxos_konduit)
+            .collect();
 
-        Ok(tx.id())
+        Ok((network_parameters, utxos))
+    }
+
+    /// Move a channel held at an older validator version to the latest one, under a new tag. See
+    /// 'consumer::migrate'.
+    pub async fn migrate(
+        &self,
+        wallet_sk: &SigningKey,
+        stake_credential: Option<&Credential>,
+        from: &Tag,
+        to: Tag,
+        script_deployment_address: &Address<kind::Shelley>,
+    ) -> anyhow::Result<Hash<32>> {
+        let channel = self
+            .channels(stake_credential)
+            .await?
+            .find(|channel| channel.tag() == from)
+            .ok_or_else(|| anyhow!("no channel found with tag {from}"))?;
+
+        let (open, intent) = consumer::migrate(&channel, to)?;
+
+        self.execute(
+            wallet_sk,
+            stake_credential,
+            vec![open],
+            BTreeMap::from([(from.clone(), intent)]),
+            script_deployment_address,
+        )
+        .await
+    }
+
+    /// Watchdog entry point: look for steps the consumer can take on its own (elapse, expire, end)
+    /// and submit a transaction performing all those that are due, if any.
+    ///
+    /// Meant to be called periodically; see `Tick::next` for when something is next due.
+    pub async fn tick(
+        &self,
+        wallet_sk: &SigningKey,
+        stake_credential: Option<&Credential>,
+        script_deployment_address: &Address<kind::Shelley>,
+    ) -> anyhow::Result<Tick> {
+        let scheduled = watchdog::schedule(self.channels(stake_credential).await?);
+
+        let lower = Bounds::twenty_mins()
+            .lower
+            .expect("twenty_mins has a lower bound");
+
+        let mut tick = Tick {
+            lower,
+            scheduled,
+            submitted: None,
+        };
+
+        if tick.due().next().is_some() {
+            let tx_id = self
+                .execute(
+                    wallet_sk,
+                    stake_credential,
+                    vec![],
+                    BTreeMap::new(),
+                    script_deployment_address,
+                )
+                .await?;
+            tick.submitted = Some(tx_id);
+        }
+
+        Ok(tick)
+    }
+}
+
+/// Utxos at any known konduit validator version; see 'all_utxos_at'.
+async fn all_konduit_utxos<Connector: CardanoConnector>(
+    connector: &Connector,
+    stake_credential_opt: Option<&Credential>,
+) -> anyhow::Result<impl Iterator<Item = (Input, Output)> + use<Connector>> {
+    let mut utxos = BTreeMap::new();
+    for credential in KONDUIT_VALIDATORS.credentials() {
+        utxos.extend(all_utxos_at(connector, &credential, stake_credential_opt).await?);
     }
+    Ok(utxos.into_iter())
 }
 
 /// A version of 'utxos_at' that fetches utxos at the payment credential, without delegation
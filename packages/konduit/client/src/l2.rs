@@ -1,8 +1,8 @@
 use crate::{
     Adaptor,
     core::{
-        AdaptorInfo, ChequeBody, Duration, Invoice, Lock, Locked, Quote, Receipt, SigningKey,
-        Squash, SquashBody, SquashStatus,
+        AdaptorInfo, Channel, ChequeBody, Duration, Invoice, Lock, Locked, Quote, Receipt,
+        SigningKey, Squash, SquashBody, SquashStatus, Stage, Tag,
     },
 };
 use anyhow::anyhow;
@@ -38,25 +38,32 @@ where
         self.adaptor.receipt().await
     }
 
-    pub async fn pay(&self, invoice: &Invoice, quote: &Quote) -> anyhow::Result<SquashStatus> {
+    /// Sign a locked cheque paying an invoice as quoted, timing out relative to now.
+    pub fn cheque(&self, invoice: &Invoice, quote: &Quote) -> anyhow::Result<Locked> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
-            .expect("failed calculate duration since UNIX epoch ?!")
-            .as_millis() as u64;
-
-        let timeout = Duration::from_millis(now + quote.relative_timeout);
-
-        let body = ChequeBody::new(
-            quote.index,
-            quote.amount,
-            timeout,
-            Lock(invoice.payment_hash),
-        );
-
+            .expect("failed calculate duration since UNIX epoch ?!");
         let tag = self.adaptor.tag().ok_or(anyhow!("no tag set on adaptor"))?;
+        Ok(cheque(
+            self.signing_key,
+            tag,
+            Lock(invoice.payment_hash),
+            quote,
+            now,
+        ))
+    }
 
-        let locked = Locked::make(self.signing_key, tag, body);
+    pub async fn pay(&self, invoice: &Invoice, quote: &Quote) -> anyhow::Result<SquashStatus> {
+        let locked = self.cheque(invoice, quote)?;
+        self.pay_cheque(invoice, locked).await
+    }
 
+    /// Hand over a cheque signed beforehand, see `cheque`.
+    pub async fn pay_cheque(
+        &self,
+        invoice: &Invoice,
+        locked: Locked,
+    ) -> anyhow::Result<SquashStatus> {
         self.adaptor.pay(invoice, locked).await
     }
 
@@ -184,3 +191,59 @@ where
         }
     }
 }
+
+/// A locked cheque for a quote, timing out `relative_timeout` after `now` (since the epoch).
+fn cheque(
+    signing_key: &SigningKey,
+    tag: &Tag,
+    lock: Lock,
+    quote: &Quote,
+    now: std::time::Duration,
+) -> Locked {
+    let timeout = Duration::from_millis(now.as_millis() as u64 + quote.relative_timeout);
+    let body = ChequeBody::new(quote.index, quote.amount, timeout, lock);
+    Locked::make(signing_key, tag, body)
+}
+
+/// Funds still available for payments on a channel: whatever it holds minus what the adaptor could
+/// still sub from it given the receipt, mirroring the adaptor's own view. Only opened channels
+/// accept new payments.
+pub fn capacity(channel: &Channel, receipt: Option<&Receipt>) -> u64 {
+    let Stage::Opened(subbed, useds) = channel.stage() else {
+        return 0;
+    };
+    let Some(receipt) = receipt else {
+        return channel.amount();
+    };
+    if receipt.capacity() == 0 {
+        return 0;
+    }
+    let subable = receipt.potentially_subable(useds).saturating_sub(*subbed);
+    channel.amount().saturating_sub(subable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cheques_time_out_relative_to_now() {
+        let signing_key = SigningKey::from([1; 32]);
+        let tag = Tag::from(b"deadbeef".to_vec());
+        let quote = Quote {
+            index: 3,
+            amount: 1_000,
+            relative_timeout: 60_000,
+            routing_fee: 10,
+        };
+        let now = std::time::Duration::from_millis(1_700_000_000_000);
+
+        let locked = cheque(&signing_key, &tag, Lock([7; 32]), &quote, now);
+
+        assert_eq!(locked.index(), 3);
+        assert_eq!(locked.amount(), 1_000);
+        assert_eq!(locked.timeout(), Duration::from_millis(1_700_000_060_000));
+        assert_eq!(locked.lock(), &Lock([7; 32]));
+        assert!(locked.verify(&signing_key.to_verification_key(), &tag));
+    }
+}
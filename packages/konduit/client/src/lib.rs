@@ -1,11 +1,17 @@
 mod adaptor;
 pub use adaptor::Adaptor;
 
+pub mod audit;
+
 #[cfg(feature = "cli")]
 pub mod cli;
 
 pub mod l1;
 pub mod l2;
 
+pub mod router;
+
+pub mod watchdog;
+
 mod prelude;
 pub(crate) use prelude::*;
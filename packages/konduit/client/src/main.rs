@@ -1,9 +1,44 @@
+use anyhow::anyhow;
+use bln_sdk::types::Invoice;
+use cardano_connector_client::Connector;
 use clap::Parser;
-use konduit_client::cli::Cli;
+use http_client::{codec, header_policy, transport};
+use konduit_client::{
+    Adaptor,
+    audit::{self, Issued},
+    cli::{Cli, Commands, Payment, State, confirm, prompt_if_incomplete},
+    l1, l2,
+    watchdog::{Scheduled, Tick},
+};
+use konduit_data::{Keytag, Lock, PossibleStep, SquashBody, SquashStatus};
+use konduit_tx::KONDUIT_VALIDATORS;
+use serde::Serialize;
+use std::{str::FromStr, time::Duration};
+use web_time::{SystemTime, UNIX_EPOCH};
+
+type HttpClient = http_client::Client<transport::Reqwest, codec::Json>;
+
+/// Tolerance on a locked cheque's timeout before we forget about it, to accommodate for clock
+/// skew between the consumer and the adaptor.
+const LOCKED_CHEQUE_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize)]
+struct SyncReport {
+    /// Locks of the cheques squashed during this sync.
+    squashed: Vec<Lock>,
+    /// Locks of the cheques still pending.
+    pending: Vec<Lock>,
+    /// Locks of the cheques set aside as expired during this sync.
+    expired: Vec<Lock>,
+    /// Amount provably owed to the adaptor according to the latest receipt.
+    owed: u64,
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    dotenvy::dotenv()?;
+    if std::fs::exists(".env")? {
+        dotenvy::dotenv()?;
+    }
 
     if std::fs::exists(".env.consumer")? {
         dotenvy::from_filename(".env.consumer").map_err(|err| {
@@ -11,66 +46,282 @@ async fn main() -> anyhow::Result<()> {
         })?;
     }
 
+    env_logger::init();
+
     let cli = Cli::parse();
-    println!("{:?}", cli);
 
-    // let client = Adaptor::new(&cli.server_url, cli.signing_key, cli.tag).await?;
+    let verification_key = cli.signing_key.to_verification_key();
+    let keytag = Keytag::new(verification_key, cli.tag.clone());
+
+    match cli.command {
+        Commands::Info => {
+            let adaptor = Adaptor::new(new_http_client(&cli.server_url), Some(&keytag)).await?;
+            print_json(adaptor.info())?;
+        }
+
+        Commands::AddInvoice { amount_msat, memo } => {
+            let (lnd_url, lnd_macaroon) = cli
+                .lnd_url
+                .as_deref()
+                .and_then(|url| Some((url, cli.lnd_macaroon.as_deref()?)))
+                .ok_or_else(|| anyhow!("LND credentials not provided"))?;
+
+            let json: serde_json::Value = new_http_client(lnd_url)
+                .post_with_headers(
+                    "/v1/invoices",
+                    &serde_json::json!({ "value_msat": amount_msat.to_string(), "memo": memo }),
+                    vec![
+                        header_policy::Custom::new("Grpc-Metadata-macaroon", lnd_macaroon).boxed(),
+                    ],
+                )
+                .await
+                .map_err(|e| anyhow!(e))?;
+
+            let payment_request = json["payment_request"]
+                .as_str()
+                .ok_or_else(|| anyhow!("LND failed to return invoice: {}", json))?;
+
+            print_json(&serde_json::json!({ "payment_request": payment_request }))?;
+        }
+
+        Commands::Quote { invoice } => {
+            let invoice = Invoice::from_str(&invoice)?;
+            let adaptor = Adaptor::new(new_http_client(&cli.server_url), Some(&keytag)).await?;
+            let quote = l2::Client::new(&adaptor, &cli.signing_key)
+                .quote(&invoice)
+                .await?;
+            print_json(&quote)?;
+        }
+
+        Commands::Pay { invoice: raw } => {
+            let invoice = Invoice::from_str(&raw)?;
+            let adaptor = Adaptor::new(new_http_client(&cli.server_url), Some(&keytag)).await?;
+            let client = l2::Client::new(&adaptor, &cli.signing_key);
+
+            let quote = client.quote(&invoice).await?;
+            eprintln!("{}", serde_json::to_string_pretty(&quote)?);
+
+            if !cli.yes && !confirm("Proceed with payment?")? {
+                return Ok(());
+            }
+
+            // Record the cheque *before* handing it over to the adaptor. Should anything go
+            // wrong afterwards, we still know we've emitted it.
+            let locked = client.cheque(&invoice, &quote)?;
+            let mut state = State::load(&cli.state_file)?;
+            state
+                .channel_mut(&keytag)
+                .issue(*locked.lock(), Payment::new(raw, &locked));
+            state.save(&cli.state_file)?;
+
+            let status = client.pay_cheque(&invoice, locked).await?;
+            let report = sync(&client, &keytag, &mut state, status, cli.yes).await?;
+            state.save(&cli.state_file)?;
+
+            print_json(&serde_json::json!({ "quote": quote, "sync": report }))?;
+        }
+
+        Commands::Squash => {
+            let adaptor = Adaptor::new(new_http_client(&cli.server_url), Some(&keytag)).await?;
+            let client = l2::Client::new(&adaptor, &cli.signing_key);
+
+            let mut state = State::load(&cli.state_file)?;
+            let status = client.squash(SquashBody::default()).await?;
+            let report = sync(&client, &keytag, &mut state, status, cli.yes).await?;
+            state.save(&cli.state_file)?;
 
-    // match cli.command {
-    //     Commands::Info => {
-    //         println!("{}", serde_json::to_string_pretty(client.info())?);
-    //     }
+            print_json(&report)?;
+        }
 
-    //     Commands::AddInvoice { amount_msat, memo } => {
-    //         let (lnd_url, lnd_macaroon) = cli
-    //             .lnd_url
-    //             .as_deref()
-    //             .and_then(|url| Some((url, cli.lnd_macaroon.as_deref()?)))
-    //             .ok_or_else(|| anyhow!("LND credentials not provided"))?;
+        Commands::Receipt => {
+            let adaptor = Adaptor::new(new_http_client(&cli.server_url), Some(&keytag)).await?;
+            let receipt = l2::Client::new(&adaptor, &cli.signing_key)
+                .receipt()
+                .await?;
+            print_json(&receipt)?;
+        }
 
-    //         let http_client = HttpClient::new(lnd_url);
+        Commands::Channels => {
+            let connector = new_connector(cli.connector_url.as_deref()).await?;
+            let channels = l1::Client::new(&connector, &cli.signing_key)
+                .channels(None)
+                .await?
+                .map(|channel| {
+                    serde_json::json!({
+                        "tag": channel.constants().tag.to_string(),
+                        "adaptor": channel.constants().sub_vkey.to_string(),
+                        "amount": channel.amount(),
+                        "stage": channel.stage().label(),
+                        "outdated": channel.is_outdated(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            print_json(&channels)?;
+        }
 
-    //         let json: serde_json::Value = http_client
-    //             .post_with_headers(
-    //                 "/v1/invoices",
-    //                 &[("Grpc-Metadata-macaroon", lnd_macaroon)],
-    //                 serde_json::to_vec(&json!({ "value_msat": amount_msat, "memo": memo }))?,
-    //             )
-    //             .await?;
+        Commands::Migrate { to } => {
+            let connector = new_connector(cli.connector_url.as_deref()).await?;
+            let host_address = cli
+                .host_address
+                .as_ref()
+                .ok_or_else(|| anyhow!("host address not provided"))?;
 
-    //         json["payment_request"]
-    //             .as_str()
-    //             .map(|s| println!("{s}"))
-    //             .ok_or_else(|| anyhow!("LND failed to return invoice: {}", json))?;
-    //     }
+            if !cli.yes && !confirm(&format!("Close channel {} and open {to}?", cli.tag))? {
+                return Ok(());
+            }
 
-    //     Commands::Quote { invoice } => {
-    //         let quote = client.quote(&invoice).await?;
-    //         println!("{}", serde_json::to_string_pretty(&quote)?);
-    //     }
+            let tx_id = l1::Client::new(&connector, &cli.signing_key)
+                .migrate(&cli.signing_key, None, &cli.tag, to.clone(), host_address)
+                .await?;
 
-    //     Commands::Pay { invoice } => {
-    //         let quote = client.quote(&invoice).await?;
+            print_json(&serde_json::json!({
+                "transaction": tx_id.to_string(),
+                "tag": to.to_string(),
+            }))?;
+        }
 
-    //         log::info!("quote = {}", serde_json::to_string(&quote)?);
+        Commands::Audit => {
+            let connector = new_connector(cli.connector_url.as_deref()).await?;
+            let state = State::load(&cli.state_file)?;
+            let issued = state
+                .channel(&keytag)
+                .issued()
+                .map(|(lock, payment)| {
+                    let issued = payment.map(|payment| Issued {
+                        amount: payment.amount,
+                        timeout: konduit_data::Duration::from_millis(payment.timeout_ms),
+                    });
+                    (*lock, issued)
+                })
+                .collect();
 
-    //         if !cli.yes && !confirm("Proceed with payment?")? {
-    //             return Ok(());
-    //         }
+            let mut transactions = vec![];
+            for credential in KONDUIT_VALIDATORS.credentials() {
+                transactions.extend(connector.transactions(&credential).await?);
+            }
 
-    //         let res = client.pay(&invoice, &quote).await?;
+            let findings = audit::audit(&verification_key, &cli.tag, &issued, &transactions)
+                .into_iter()
+                .map(|finding| {
+                    serde_json::json!({
+                        "transaction": finding.transaction.to_string(),
+                        "tag": finding.tag.to_string(),
+                        "discrepancy": finding.discrepancy.to_string(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            print_json(&findings)?;
+        }
 
-    //         let and_confirm = prompt_if_incomplete(&res, cli.yes)?;
+        Commands::Watch { every, once } => {
+            let connector = new_connector(cli.connector_url.as_deref()).await?;
+            let host_address = cli
+                .host_address
+                .as_ref()
+                .ok_or_else(|| anyhow!("host address not provided"))?;
+            let client = l1::Client::new(&connector, &cli.signing_key);
 
-    //         client.sync(res, and_confirm).await?;
-    //     }
+            loop {
+                match client.tick(&cli.signing_key, None, host_address).await {
+                    Ok(tick) => print_json(&tick_report(&tick))?,
+                    Err(err) if once => return Err(err),
+                    Err(err) => log::error!("watch: tick failed: {err:#}"),
+                }
 
-    //     Commands::Squash => {
-    //         let res = client.squash(SquashBody::default()).await?;
-    //         let and_confirm = prompt_if_incomplete(&res, cli.yes)?;
-    //         client.sync(res, and_confirm).await?;
-    //     }
-    // }
+                if once {
+                    break;
+                }
 
+                tokio::time::sleep(every).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Verify and execute any squash proposed by the adaptor, then reconcile the local state with the
+/// latest receipt.
+async fn sync(
+    client: &l2::Client<'_, transport::Reqwest>,
+    keytag: &Keytag,
+    state: &mut State,
+    status: SquashStatus,
+    auto_confirm: bool,
+) -> anyhow::Result<SyncReport> {
+    let and_confirm = prompt_if_incomplete(&status, auto_confirm)?;
+
+    let known = state.channel(keytag);
+    let squashed = client
+        .sync(status, and_confirm, |lock| known.is_known(&lock))
+        .await?;
+
+    let channel = state.channel_mut(keytag);
+    channel.settle(squashed.iter().copied());
+    let expired =
+        channel.expire(now().saturating_sub(LOCKED_CHEQUE_GRACE_PERIOD).as_millis() as u64);
+
+    let (key, tag) = keytag.split();
+    let owed = client
+        .receipt()
+        .await?
+        .map(|receipt| receipt.provably_owed(&key, &tag))
+        .unwrap_or_default();
+
+    Ok(SyncReport {
+        squashed,
+        pending: channel.lockeds.keys().copied().collect(),
+        expired,
+        owed,
+    })
+}
+
+async fn new_connector(
+    connector_url: Option<&str>,
+) -> anyhow::Result<Connector<transport::Reqwest>> {
+    let connector_url = connector_url.ok_or_else(|| anyhow!("connector URL not provided"))?;
+    Connector::new(new_http_client(connector_url)).await
+}
+
+fn tick_report(tick: &Tick) -> serde_json::Value {
+    let describe = |scheduled: &Scheduled| {
+        let step = match scheduled.step {
+            PossibleStep::Elapse { .. } => "elapse",
+            PossibleStep::Expire { .. } => "expire",
+            PossibleStep::End => "end",
+            PossibleStep::Add | PossibleStep::Close => unreachable!("never scheduled"),
+        };
+        serde_json::json!({
+            "tag": scheduled.tag.to_string(),
+            "step": step,
+            "after": scheduled.after().map(u64::from),
+        })
+    };
+
+    serde_json::json!({
+        "lower": u64::from(tick.lower),
+        "due": tick.due().map(describe).collect::<Vec<_>>(),
+        "waiting": tick.waiting().map(describe).collect::<Vec<_>>(),
+        "next": tick.next().map(u64::from),
+        "submitted": tick.submitted.map(|id| id.to_string()),
+    })
+}
+
+fn new_http_client(base_url: &str) -> HttpClient {
+    HttpClient::new(
+        transport::Reqwest::new(None),
+        codec::Json,
+        base_url.to_string(),
+    )
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
     Ok(())
 }
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|e| unreachable!("couldn't compute duration since UNIX epoch: {e} !?"))
+}
@@ -2,6 +2,7 @@
 
 pub mod core {
     pub use bln_sdk::types::*;
+    pub use cardano_connector_client::types::*;
     pub use cardano_sdk::*;
     pub use konduit_data::*;
     pub use konduit_tx::*;
@@ -0,0 +1,355 @@
+use crate::{
+    Adaptor,
+    core::{Channel, Invoice, Quote, QuoteBody, SigningKey, SquashStatus, Tag},
+    l2,
+};
+use anyhow::anyhow;
+use futures::future::join_all;
+use http_client::Transport;
+use std::fmt;
+
+/// A channel payments may be routed through: the channel as found on-chain, and the adaptor it is
+/// opened with, authenticated for that channel.
+pub struct Route<'a, T: Transport> {
+    pub adaptor: &'a Adaptor<T>,
+    pub channel: Channel,
+}
+
+/// A quote from an adaptor whose channel can cover it.
+#[derive(Debug, Clone)]
+pub struct Offer {
+    pub tag: Tag,
+    pub quote: Quote,
+    pub capacity: u64,
+}
+
+/// Why a route did not make it into the offers.
+#[derive(Debug)]
+pub enum Rejection {
+    /// The adaptor failed to quote or to provide its receipt.
+    Unavailable(anyhow::Error),
+    /// The channel cannot cover the quoted amount.
+    InsufficientCapacity { quote: Quote, capacity: u64 },
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable(err) => write!(f, "unavailable: {err:#}"),
+            Self::InsufficientCapacity { quote, capacity } => write!(
+                f,
+                "insufficient capacity (quoted={}, capacity={capacity})",
+                quote.amount
+            ),
+        }
+    }
+}
+
+/// Outcome of a quote request fanned out to all routes.
+#[derive(Debug, Default)]
+pub struct Ranking {
+    /// Acceptable offers, best first.
+    pub offers: Vec<Offer>,
+    pub rejected: Vec<(Tag, Rejection)>,
+}
+
+impl Ranking {
+    pub fn best(&self) -> Option<&Offer> {
+        self.offers.first()
+    }
+}
+
+/// Pick the best adaptor to pay through, amongst channels held with several of them.
+pub struct Router<'a, T: Transport> {
+    signing_key: &'a SigningKey,
+    routes: Vec<Route<'a, T>>,
+}
+
+impl<'a, T> Router<'a, T>
+where
+    T: Transport,
+    T::Error: Into<anyhow::Error>,
+{
+    pub fn new(signing_key: &'a SigningKey, routes: Vec<Route<'a, T>>) -> Self {
+        Self {
+            signing_key,
+            routes,
+        }
+    }
+
+    /// Ask every route for a quote, concurrently. Offers are ranked by total cost (in lovelace),
+    /// then by timeout; a route failing does not prevent others from being considered.
+    pub async fn rank(&self, body: &QuoteBody) -> Ranking {
+        let outcomes = join_all(self.routes.iter().map(|route| async move {
+            let quote = route.adaptor.quote_for(body).await?;
+            let receipt = route.adaptor.receipt().await?;
+            Ok::<_, anyhow::Error>((quote, l2::capacity(&route.channel, receipt.as_ref())))
+        }))
+        .await;
+
+        let mut ranking = Ranking::default();
+
+        for (route, outcome) in self.routes.iter().zip(outcomes) {
+            let tag = route.channel.tag().clone();
+            match outcome {
+                Ok((quote, capacity)) if quote.amount <= capacity => ranking.offers.push(Offer {
+                    tag,
+                    quote,
+                    capacity,
+                }),
+                Ok((quote, capacity)) => ranking
+                    .rejected
+                    .push((tag, Rejection::InsufficientCapacity { quote, capacity })),
+                Err(err) => ranking.rejected.push((tag, Rejection::Unavailable(err))),
+            }
+        }
+
+        ranking
+            .offers
+            .sort_by_key(|offer| (offer.quote.amount, offer.quote.relative_timeout));
+
+        ranking
+    }
+
+    /// Pay an invoice through the best route, if any.
+    pub async fn pay(&self, invoice: &Invoice) -> anyhow::Result<(Offer, SquashStatus)> {
+        let ranking = self.rank(&QuoteBody::Bolt11(invoice.clone())).await;
+
+        for (tag, rejection) in ranking.rejected.iter() {
+            log::info!("route tag={tag} rejected: {rejection}");
+        }
+
+        let offer = ranking
+            .best()
+            .ok_or_else(|| anyhow!("no route can cover this payment"))?;
+
+        let route = self
+            .routes
+            .iter()
+            .find(|route| route.channel.tag() == &offer.tag)
+            .expect("offer originates from a route");
+
+        let status = l2::Client::new(route.adaptor, self.signing_key)
+            .pay(invoice, &offer.quote)
+            .await?;
+
+        Ok((offer.clone(), status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rejection, Route, Router};
+    use crate::{
+        Adaptor,
+        core::{
+            AdaptorInfo, ChannelParameters, Constants, Duration, Hash, Indexes, Keytag, Open,
+            Quote, QuoteBody, Receipt, SigningKey, SimpleQuote, Squash, SquashBody, Tag, TosInfo,
+            TxHelp,
+        },
+    };
+    use http_client::{Transport, codec};
+    use std::str::FromStr;
+
+    /// An in-process adaptor, answering with canned responses. A missing quote is answered with
+    /// an error status.
+    struct MockAdaptor {
+        quote: Option<Quote>,
+        receipt: Option<Receipt>,
+    }
+
+    impl Transport for MockAdaptor {
+        type Error = std::io::Error;
+
+        async fn transport(
+            &self,
+            req: http::Request<Vec<u8>>,
+        ) -> Result<http::Response<Vec<u8>>, Self::Error> {
+            let body = match req.uri().path() {
+                "/info" => Some(serde_json::to_vec(&info())?),
+                "/ch/quote" => self.quote.as_ref().map(serde_json::to_vec).transpose()?,
+                "/ch/receipt" => Some(serde_json::to_vec(&self.receipt)?),
+                _ => None,
+            };
+            let response = match body {
+                Some(body) => http::Response::builder().status(200).body(body),
+                None => http::Response::builder().status(500).body(vec![]),
+            };
+            Ok(response.expect("valid response"))
+        }
+    }
+
+    fn consumer() -> SigningKey {
+        SigningKey::from([1; 32])
+    }
+
+    fn adaptor_key() -> SigningKey {
+        SigningKey::from([2; 32])
+    }
+
+    fn info() -> AdaptorInfo<TxHelp> {
+        AdaptorInfo {
+            tos: TosInfo { flat_fee: 0 },
+            channel_parameters: ChannelParameters {
+                adaptor_key: adaptor_key().to_verification_key(),
+                close_period: Duration::from_secs(3600),
+                tag_length: 32,
+            },
+            tx_help: TxHelp {
+                host_address: FromStr::from_str(
+                    "addr_test1vrpynvza5vswczszkjhe5cvqz2awmzukf84xa5wway8durqpmfm2m",
+                )
+                .unwrap(),
+                validator: Hash::from([0; 28]),
+            },
+        }
+    }
+
+    fn quote(amount: u64, relative_timeout: u64) -> Option<Quote> {
+        Some(Quote {
+            index: 1,
+            amount,
+            relative_timeout,
+            routing_fee: 0,
+        })
+    }
+
+    fn tag(n: u8) -> Tag {
+        Tag::from(vec![n])
+    }
+
+    async fn adaptor(n: u8, mock: MockAdaptor) -> Adaptor<MockAdaptor> {
+        let keytag = Keytag::new(consumer().to_verification_key(), tag(n));
+        let http_client = http_client::Client::new(mock, codec::Json, "http://mock".to_string());
+        Adaptor::new(http_client, Some(&keytag)).await.unwrap()
+    }
+
+    fn route(adaptor: &Adaptor<MockAdaptor>, amount: u64) -> Route<'_, MockAdaptor> {
+        let constants = Constants {
+            tag: adaptor.tag().unwrap().clone(),
+            add_vkey: consumer().to_verification_key(),
+            sub_vkey: adaptor_key().to_verification_key(),
+            close_period: Duration::from_secs(3600),
+        };
+        Route {
+            adaptor,
+            channel: Open::new(amount, constants, None).data().clone(),
+        }
+    }
+
+    fn body() -> QuoteBody {
+        QuoteBody::Simple(SimpleQuote {
+            amount_msat: 1_000_000,
+            payee: [2; 33],
+            route_hints: vec![],
+        })
+    }
+
+    #[tokio::test]
+    async fn offers_are_ranked_by_cost_then_timeout() {
+        let a = adaptor(
+            1,
+            MockAdaptor {
+                quote: quote(2_000, 10),
+                receipt: None,
+            },
+        )
+        .await;
+        let b = adaptor(
+            2,
+            MockAdaptor {
+                quote: quote(1_500, 20),
+                receipt: None,
+            },
+        )
+        .await;
+        let c = adaptor(
+            3,
+            MockAdaptor {
+                quote: quote(1_500, 10),
+                receipt: None,
+            },
+        )
+        .await;
+
+        let routes = vec![route(&a, 10_000), route(&b, 10_000), route(&c, 10_000)];
+        let ranking = Router::new(&consumer(), routes).rank(&body()).await;
+
+        let tags = ranking
+            .offers
+            .iter()
+            .map(|o| o.tag.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec![tag(3), tag(2), tag(1)]);
+        assert!(ranking.rejected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failing_or_short_routes_do_not_block_others() {
+        let signing_key = consumer();
+        let squash = Squash::make(
+            &signing_key,
+            &tag(3),
+            SquashBody::new_no_verify(4_000, 0, Indexes::default()),
+        );
+
+        let a = adaptor(
+            1,
+            MockAdaptor {
+                quote: None,
+                receipt: None,
+            },
+        )
+        .await;
+        let b = adaptor(
+            2,
+            MockAdaptor {
+                quote: quote(1_000, 10),
+                receipt: None,
+            },
+        )
+        .await;
+        let c = adaptor(
+            3,
+            MockAdaptor {
+                quote: quote(1_500, 10),
+                receipt: Some(Receipt::new(squash)),
+            },
+        )
+        .await;
+        let d = adaptor(
+            4,
+            MockAdaptor {
+                quote: quote(1_800, 10),
+                receipt: None,
+            },
+        )
+        .await;
+
+        let routes = vec![
+            route(&a, 10_000),
+            route(&b, 500),
+            route(&c, 5_000),
+            route(&d, 10_000),
+        ];
+        let ranking = Router::new(&signing_key, routes).rank(&body()).await;
+
+        assert_eq!(ranking.best().map(|o| o.tag.clone()), Some(tag(4)));
+        assert_eq!(ranking.offers.len(), 1);
+
+        let rejected = ranking
+            .rejected
+            .iter()
+            .map(|(tag, rejection)| (tag.clone(), rejection))
+            .collect::<Vec<_>>();
+        assert!(matches!(rejected[0], (ref t, Rejection::Unavailable(_)) if *t == tag(1)));
+        assert!(matches!(
+            rejected[1],
+            (ref t, Rejection::InsufficientCapacity { capacity: 500, .. }) if *t == tag(2)
+        ));
+        assert!(matches!(
+            rejected[2],
+            (ref t, Rejection::InsufficientCapacity { capacity: 1_000, .. }) if *t == tag(3)
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::core::{Channel, Duration, Hash, PossibleStep, Tag};
+
+/// A step the consumer may take on its own on one of its channels, once time allows it. These are
+/// the steps that `konduit_tx::consumer::tx` infers from context, without any explicit intent.
+#[derive(Debug, Clone)]
+pub struct Scheduled {
+    pub tag: Tag,
+    pub step: PossibleStep,
+}
+
+impl Scheduled {
+    /// The time after which the step becomes valid, if the step is time-bound.
+    pub fn after(&self) -> Option<Duration> {
+        match self.step {
+            PossibleStep::Elapse { after } | PossibleStep::Expire { after, .. } => Some(after),
+            PossibleStep::Add | PossibleStep::Close | PossibleStep::End => None,
+        }
+    }
+
+    /// Whether the step is valid in a transaction with the given validity lower bound.
+    pub fn is_due(&self, lower: &Duration) -> bool {
+        self.after().is_none_or(|after| after < *lower)
+    }
+}
+
+/// Collect the steps the consumer can take unilaterally across its channels.
+pub fn schedule(channels: impl IntoIterator<Item = Channel>) -> Vec<Scheduled> {
+    channels
+        .into_iter()
+        .flat_map(|channel| {
+            let tag = channel.constants().tag.clone();
+            channel
+                .stage()
+                .possible_steps()
+                .into_iter()
+                .filter(|step| {
+                    matches!(
+                        step,
+                        PossibleStep::Elapse { .. }
+                            | PossibleStep::Expire { .. }
+                            | PossibleStep::End
+                    )
+                })
+                .map(move |step| Scheduled {
+                    tag: tag.clone(),
+                    step,
+                })
+        })
+        .collect()
+}
+
+/// Outcome of a single watchdog tick.
+#[derive(Debug, Clone)]
+pub struct Tick {
+    /// Lower bound against which steps were deemed due.
+    pub lower: Duration,
+    pub scheduled: Vec<Scheduled>,
+    /// Id of the transaction submitted to perform the due steps, if any.
+    pub submitted: Option<Hash<32>>,
+}
+
+impl Tick {
+    pub fn due(&self) -> impl Iterator<Item = &Scheduled> {
+        self.scheduled.iter().filter(|s| s.is_due(&self.lower))
+    }
+
+    pub fn waiting(&self) -> impl Iterator<Item = &Scheduled> {
+        self.scheduled.iter().filter(|s| !s.is_due(&self.lower))
+    }
+
+    /// Earliest time at which a step, not yet due, becomes due.
+    pub fn next(&self) -> Option<Duration> {
+        self.waiting().filter_map(Scheduled::after).min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Constants, Lock, Pending, SigningKey, Stage, Variables};
+
+    fn channel(tag: u8, stage: Stage) -> Channel {
+        let constants = Constants {
+            tag: Tag::from(vec![tag]),
+            add_vkey: SigningKey::from([1; 32]).to_verification_key(),
+            sub_vkey: SigningKey::from([2; 32]).to_verification_key(),
+            close_period: Duration::from_secs(3600),
+        };
+        Channel::new(constants, Variables::new(10_000, stage))
+    }
+
+    fn pending(timeout: u64) -> Pending {
+        Pending {
+            amount: 100,
+            timeout: Duration::from_millis(timeout),
+            lock: Lock([0; 32]),
+        }
+    }
+
+    fn tick(lower: u64) -> Tick {
+        Tick {
+            lower: Duration::from_millis(lower),
+            scheduled: schedule([
+                channel(1, Stage::Opened(0, vec![])),
+                channel(2, Stage::Closed(0, vec![], Duration::from_millis(2_000))),
+                channel(3, Stage::Responded(0, vec![pending(3_000), pending(1_000)])),
+                channel(4, Stage::Responded(0, vec![])),
+            ]),
+            submitted: None,
+        }
+    }
+
+    fn tags<'a>(scheduled: impl Iterator<Item = &'a Scheduled>) -> Vec<u8> {
+        scheduled.map(|s| s.tag.as_ref()[0]).collect()
+    }
+
+    #[test]
+    fn schedules_only_unilateral_steps() {
+        let scheduled = tick(0).scheduled;
+        assert_eq!(tags(scheduled.iter()), vec![2, 3, 3, 4]);
+        assert!(matches!(scheduled[0].step, PossibleStep::Elapse { .. }));
+        assert!(matches!(scheduled[3].step, PossibleStep::End));
+    }
+
+    #[test]
+    fn steps_are_due_strictly_after_their_bound() {
+        let tick = tick(2_000);
+        assert_eq!(tags(tick.due()), vec![3, 4]);
+        assert_eq!(tags(tick.waiting()), vec![2, 3]);
+    }
+
+    #[test]
+    fn next_is_the_earliest_step_not_yet_due() {
+        assert_eq!(tick(0).next(), Some(Duration::from_millis(1_000)));
+        assert_eq!(tick(1_500).next(), Some(Duration::from_millis(2_000)));
+        assert_eq!(tick(5_000).next(), None);
+    }
+}
@@ -17,12 +17,12 @@ impl PosixSeconds {
     }
 
     pub fn to_slot(&self, slot_config: &uplc::tx::SlotConfig) -> Slot {
+        let millis = self.0 * 1000;
         assert!(
-            self.0 >= slot_config.zero_time,
+            millis >= slot_config.zero_time,
             "Time is before the zero slot time"
         );
-        let slot_length_secs = slot_config.slot_length / 1000;
-        (self.0 - slot_config.zero_time) / (slot_length_secs as u64)
+        slot_config.zero_slot + (millis - slot_config.zero_time) / (slot_config.slot_length as u64)
     }
 }
 
@@ -1,5 +1,5 @@
 use anyhow::anyhow;
-use cardano_sdk::{PlutusData, cbor::ToCbor};
+use cardano_sdk::PlutusData;
 use rand_core::RngCore;
 use std::{fmt, ops::Deref, str::FromStr};
 
@@ -13,19 +13,6 @@ impl Tag {
         rand_core::OsRng.fill_bytes(&mut bytes);
         Self::from(bytes)
     }
-
-    /// Tag data!
-    pub fn data<T>(&self, data: &T) -> Vec<u8>
-    where
-        for<'a> PlutusData<'a>: From<T>,
-        T: Clone,
-    {
-        PlutusData::list([
-            PlutusData::bytes(self.as_ref()),
-            PlutusData::from(data.clone()),
-        ])
-        .to_cbor()
-    }
 }
 
 impl FromStr for Tag {
@@ -1,4 +1,4 @@
-use crate::{ChequeBody, Duration, Lock, Tag, locked::Locked, unlocked::Unlocked};
+use crate::{ChequeBody, Duration, Lock, ProtocolVersion, Tag, locked::Locked, unlocked::Unlocked};
 use anyhow::anyhow;
 use cardano_sdk::{PlutusData, VerificationKey};
 use serde::{Deserialize, Serialize};
@@ -93,37 +93,42 @@ impl From<Locked> for Cheque {
     }
 }
 
-impl<'a> TryFrom<PlutusData<'a>> for Cheque {
-    type Error = anyhow::Error;
-
-    fn try_from(data: PlutusData<'a>) -> anyhow::Result<Self> {
-        let (variant, fields): (u64, Vec<PlutusData<'_>>) = (&data).try_into()?;
-
-        return match variant {
-            _ if variant == 0 => {
-                try_unlocked(fields).map_err(|e| e.context("invalid 'Unlocked' variant"))
-            }
-            _ if variant == 1 => {
-                try_cheque(fields).map_err(|e| e.context("invalid 'Cheque' variant"))
-            }
+impl Cheque {
+    pub fn from_plutus_data(
+        data: &PlutusData<'_>,
+        version: ProtocolVersion,
+    ) -> anyhow::Result<Self> {
+        let (variant, fields): (u64, Vec<PlutusData<'_>>) = data.try_into()?;
+        match variant {
+            0 => Ok(Cheque::from(
+                Unlocked::from_fields(fields, version)
+                    .map_err(|e| e.context("invalid 'Unlocked' variant"))?,
+            )),
+            1 => Ok(Cheque::from(
+                Locked::try_from(fields).map_err(|e| e.context("invalid 'Cheque' variant"))?,
+            )),
             _ => Err(anyhow!("unknown variant: {variant}")),
-        };
-
-        fn try_unlocked(fields: Vec<PlutusData<'_>>) -> anyhow::Result<Cheque> {
-            Ok(Cheque::from(Unlocked::try_from(fields)?))
         }
+    }
 
-        fn try_cheque(fields: Vec<PlutusData<'_>>) -> anyhow::Result<Cheque> {
-            Ok(Cheque::from(Locked::try_from(fields)?))
+    pub fn to_plutus_data<'a>(self, version: ProtocolVersion) -> PlutusData<'a> {
+        match self {
+            Cheque::Unlocked(unlocked) => PlutusData::constr(0, unlocked.into_fields(version)),
+            Cheque::Locked(locked) => PlutusData::constr(1, Vec::from(locked)),
         }
     }
 }
 
+impl<'a> TryFrom<PlutusData<'a>> for Cheque {
+    type Error = anyhow::Error;
+
+    fn try_from(data: PlutusData<'a>) -> anyhow::Result<Self> {
+        Self::from_plutus_data(&data, ProtocolVersion::default())
+    }
+}
+
 impl<'a> From<Cheque> for PlutusData<'a> {
     fn from(value: Cheque) -> Self {
-        match value {
-            Cheque::Unlocked(unlocked) => PlutusData::constr(0, Vec::from(unlocked)),
-            Cheque::Locked(locked) => PlutusData::constr(1, Vec::from(locked)),
-        }
+        value.to_plutus_data(ProtocolVersion::default())
     }
 }
@@ -14,6 +14,7 @@ mod locked;
 mod pay_body;
 mod pending;
 mod possible_step;
+mod protocol_version;
 mod quote;
 mod quote_body;
 mod receipt;
@@ -23,6 +24,7 @@ mod squash_body;
 mod squash_proposal;
 mod squash_status;
 mod stage;
+pub mod tbs;
 mod unlocked;
 mod used;
 mod utils;
@@ -40,6 +42,7 @@ pub use locked::*;
 pub use pay_body::*;
 pub use pending::*;
 pub use possible_step::*;
+pub use protocol_version::*;
 pub use quote::*;
 pub use quote_body::*;
 pub use receipt::*;
@@ -1,6 +1,7 @@
 use crate::{
     Duration, Lock, Tag,
     cheque_body::ChequeBody,
+    tbs::Tbs,
     utils::{signature_from_plutus_data, signature_to_plutus_data},
 };
 use anyhow::{Error, Result, anyhow};
@@ -38,12 +39,16 @@ impl Locked {
     }
 
     pub fn make(signing_key: &SigningKey, tag: &Tag, body: ChequeBody) -> Self {
-        let signature = signing_key.sign(tag.data(&body));
+        let signature = Tbs::Cheque { tag, body: &body }.sign(signing_key);
         Self::new(body, signature)
     }
 
     pub fn verify(&self, verification_key: &VerificationKey, tag: &Tag) -> bool {
-        verification_key.verify(tag.data(&self.body), &self.signature)
+        Tbs::Cheque {
+            tag,
+            body: &self.body,
+        }
+        .verify(verification_key, &self.signature)
     }
 }
 
@@ -0,0 +1,13 @@
+/// Version of the on-chain protocol, which dictates how some of the redeemers' data are encoded.
+///
+/// Plain `From`/`TryFrom` conversions to and from `PlutusData` follow the default version;
+/// versioned conversions are available on the types whose encoding differs across versions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    /// Unlocked cheques carry their body, with both lock and secret.
+    #[default]
+    V0,
+    /// Unlocked cheques are "de-locked" (ADR 09): the lock is omitted and recomputed from the
+    /// secret, making sub and respond redeemers smaller.
+    V1,
+}
@@ -1,4 +1,4 @@
-use crate::{Cheque, Squash, Unlocked, Unpend};
+use crate::{Cheque, ProtocolVersion, Squash, Unlocked, Unpend};
 use anyhow::anyhow;
 use cardano_sdk::{PlutusData, constr};
 
@@ -9,10 +9,11 @@ pub enum Redeemer {
     Mutual,
 }
 
-impl<'a> TryFrom<&PlutusData<'a>> for Redeemer {
-    type Error = anyhow::Error;
-
-    fn try_from(data: &PlutusData<'a>) -> anyhow::Result<Self> {
+impl Redeemer {
+    pub fn from_plutus_data(
+        data: &PlutusData<'_>,
+        version: ProtocolVersion,
+    ) -> anyhow::Result<Self> {
         let (tag, fields) = data.as_constr().ok_or(anyhow!("Not a constructor"))?;
         match tag {
             0 => Ok(Redeemer::Defer),
@@ -20,8 +21,8 @@ impl<'a> TryFrom<&PlutusData<'a>> for Redeemer {
                 let [steps] = <[PlutusData; 1]>::try_from(fields.collect::<Vec<_>>())
                     .map_err(|_| anyhow!("invalid 'Cheque'"))?;
                 let steps = <Vec<PlutusData>>::try_from(&steps)?
-                    .into_iter()
-                    .map(Step::try_from)
+                    .iter()
+                    .map(|step| Step::from_plutus_data(step, version))
                     .collect::<anyhow::Result<Vec<Step>>>()?;
                 Ok(Redeemer::Main(steps))
             }
@@ -29,26 +30,16 @@ impl<'a> TryFrom<&PlutusData<'a>> for Redeemer {
             _ => Err(anyhow!("Unknown Redeemer tag: {}", tag)),
         }
     }
-}
-
-impl<'a> TryFrom<PlutusData<'a>> for Redeemer {
-    type Error = anyhow::Error;
-
-    fn try_from(data: PlutusData<'a>) -> anyhow::Result<Self> {
-        Redeemer::try_from(&data)
-    }
-}
 
-impl<'a> From<Redeemer> for PlutusData<'a> {
-    fn from(value: Redeemer) -> Self {
-        match value {
+    pub fn to_plutus_data<'a>(self, version: ProtocolVersion) -> PlutusData<'a> {
+        match self {
             Redeemer::Defer => constr!(0),
             Redeemer::Main(steps) => constr!(
                 1,
                 PlutusData::list(
                     steps
                         .into_iter()
-                        .map(PlutusData::from)
+                        .map(|step| step.to_plutus_data(version))
                         .collect::<Vec<PlutusData>>(),
                 ),
             ),
@@ -57,6 +48,28 @@ impl<'a> From<Redeemer> for PlutusData<'a> {
     }
 }
 
+impl<'a> TryFrom<&PlutusData<'a>> for Redeemer {
+    type Error = anyhow::Error;
+
+    fn try_from(data: &PlutusData<'a>) -> anyhow::Result<Self> {
+        Redeemer::from_plutus_data(data, ProtocolVersion::default())
+    }
+}
+
+impl<'a> TryFrom<PlutusData<'a>> for Redeemer {
+    type Error = anyhow::Error;
+
+    fn try_from(data: PlutusData<'a>) -> anyhow::Result<Self> {
+        Redeemer::try_from(&data)
+    }
+}
+
+impl<'a> From<Redeemer> for PlutusData<'a> {
+    fn from(value: Redeemer) -> Self {
+        value.to_plutus_data(ProtocolVersion::default())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Step {
     Cont(Cont),
@@ -78,16 +91,17 @@ impl Step {
     }
 }
 
-impl<'a> TryFrom<&PlutusData<'a>> for Step {
-    type Error = anyhow::Error;
-
-    fn try_from(data: &PlutusData<'a>) -> anyhow::Result<Self> {
+impl Step {
+    pub fn from_plutus_data(
+        data: &PlutusData<'_>,
+        version: ProtocolVersion,
+    ) -> anyhow::Result<Self> {
         let (tag, fields) = data.as_constr().ok_or(anyhow!("Not a constructor"))?;
         let [field] = <[PlutusData; 1]>::try_from(fields.collect::<Vec<_>>())
             .map_err(|_| anyhow!("invalid 'Step'"))?;
         match tag {
             0 => {
-                let cont = Cont::try_from(field)?;
+                let cont = Cont::from_plutus_data(&field, version)?;
                 Ok(Step::Cont(cont))
             }
             1 => {
@@ -97,6 +111,21 @@ impl<'a> TryFrom<&PlutusData<'a>> for Step {
             _ => Err(anyhow!("Unknown Step tag: {}", tag)),
         }
     }
+
+    pub fn to_plutus_data<'a>(self, version: ProtocolVersion) -> PlutusData<'a> {
+        match self {
+            Step::Cont(cont) => constr!(0, cont.to_plutus_data(version)),
+            Step::Eol(eol) => constr!(1, PlutusData::from(eol)),
+        }
+    }
+}
+
+impl<'a> TryFrom<&PlutusData<'a>> for Step {
+    type Error = anyhow::Error;
+
+    fn try_from(data: &PlutusData<'a>) -> anyhow::Result<Self> {
+        Step::from_plutus_data(data, ProtocolVersion::default())
+    }
 }
 
 impl<'a> TryFrom<PlutusData<'a>> for Step {
@@ -109,10 +138,7 @@ impl<'a> TryFrom<PlutusData<'a>> for Step {
 
 impl<'a> From<Step> for PlutusData<'a> {
     fn from(value: Step) -> Self {
-        match value {
-            Step::Cont(cont) => constr!(0, PlutusData::from(cont)),
-            Step::Eol(eol) => constr!(1, PlutusData::from(eol)),
-        }
+        value.to_plutus_data(ProtocolVersion::default())
     }
 }
 
@@ -126,10 +152,11 @@ pub enum Cont {
     Expire(Vec<Unpend>),
 }
 
-impl<'a> TryFrom<&PlutusData<'a>> for Cont {
-    type Error = anyhow::Error;
-
-    fn try_from(data: &PlutusData<'a>) -> anyhow::Result<Self> {
+impl Cont {
+    pub fn from_plutus_data(
+        data: &PlutusData<'_>,
+        version: ProtocolVersion,
+    ) -> anyhow::Result<Self> {
         let (tag, fields) = data.as_constr().ok_or(anyhow!("Not a constructor"))?;
         match tag {
             0 => Ok(Cont::Add),
@@ -138,8 +165,8 @@ impl<'a> TryFrom<&PlutusData<'a>> for Cont {
                     .map_err(|_| anyhow!("invalid 'Cont::Sub'"))?;
                 let squash = Squash::try_from(&a)?;
                 let unlocked = <Vec<PlutusData>>::try_from(&b)?
-                    .into_iter()
-                    .map(|x| Unlocked::try_from(&x))
+                    .iter()
+                    .map(|x| Unlocked::from_fields(Vec::try_from(x)?, version))
                     .collect::<anyhow::Result<Vec<Unlocked>>>()?;
                 Ok(Cont::Sub(squash, unlocked))
             }
@@ -149,8 +176,8 @@ impl<'a> TryFrom<&PlutusData<'a>> for Cont {
                     .map_err(|_| anyhow!("invalid 'Cont::Sub'"))?;
                 let squash = Squash::try_from(&a)?;
                 let cheques = <Vec<PlutusData>>::try_from(&b)?
-                    .into_iter()
-                    .map(Cheque::try_from)
+                    .iter()
+                    .map(|x| Cheque::from_plutus_data(x, version))
                     .collect::<anyhow::Result<Vec<Cheque>>>()?;
                 Ok(Cont::Respond(squash, cheques))
             }
@@ -181,19 +208,9 @@ impl<'a> TryFrom<&PlutusData<'a>> for Cont {
             _ => Err(anyhow!("Unknown Cont tag: {}", tag)),
         }
     }
-}
-
-impl<'a> TryFrom<PlutusData<'a>> for Cont {
-    type Error = anyhow::Error;
-
-    fn try_from(data: PlutusData<'a>) -> anyhow::Result<Self> {
-        Cont::try_from(&data)
-    }
-}
 
-impl<'a> From<Cont> for PlutusData<'a> {
-    fn from(value: Cont) -> Self {
-        match value {
+    pub fn to_plutus_data<'a>(self, version: ProtocolVersion) -> PlutusData<'a> {
+        match self {
             Cont::Add => constr!(0),
             Cont::Sub(squash, unlocked) => constr!(
                 1,
@@ -201,7 +218,7 @@ impl<'a> From<Cont> for PlutusData<'a> {
                 PlutusData::list(
                     unlocked
                         .into_iter()
-                        .map(PlutusData::from)
+                        .map(|x| PlutusData::list(x.into_fields(version)))
                         .collect::<Vec<PlutusData>>(),
                 )
             ),
@@ -212,7 +229,7 @@ impl<'a> From<Cont> for PlutusData<'a> {
                 PlutusData::list(
                     cheques
                         .into_iter()
-                        .map(PlutusData::from)
+                        .map(|x| x.to_plutus_data(version))
                         .collect::<Vec<PlutusData>>(),
                 ),
             ),
@@ -238,6 +255,28 @@ impl<'a> From<Cont> for PlutusData<'a> {
     }
 }
 
+impl<'a> TryFrom<&PlutusData<'a>> for Cont {
+    type Error = anyhow::Error;
+
+    fn try_from(data: &PlutusData<'a>) -> anyhow::Result<Self> {
+        Cont::from_plutus_data(data, ProtocolVersion::default())
+    }
+}
+
+impl<'a> TryFrom<PlutusData<'a>> for Cont {
+    type Error = anyhow::Error;
+
+    fn try_from(data: PlutusData<'a>) -> anyhow::Result<Self> {
+        Cont::try_from(&data)
+    }
+}
+
+impl<'a> From<Cont> for PlutusData<'a> {
+    fn from(value: Cont) -> Self {
+        value.to_plutus_data(ProtocolVersion::default())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Eol {
     End,
@@ -273,3 +312,148 @@ impl<'a> From<Eol> for PlutusData<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChequeBody, Duration, Indexes, Lock, Locked, Secret, SquashBody, Tag};
+    use cardano_sdk::SigningKey;
+    use serde_json::Value;
+
+    /// The blueprint of the validator currently deployed, which speaks 'ProtocolVersion::V0'.
+    const BLUEPRINT: &str = include_str!("../../tx/plutus.json");
+
+    /// Check data against a blueprint schema. Only covers what the Konduit blueprint uses.
+    fn conforms(data: &PlutusData, schema: &Value, definitions: &Value) -> bool {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference
+                .trim_start_matches("#/definitions/")
+                .replace("~1", "/");
+            return conforms(data, &definitions[name], definitions);
+        }
+
+        if let Some(any_of) = schema.get("anyOf").and_then(Value::as_array) {
+            return any_of.iter().any(|s| conforms(data, s, definitions));
+        }
+
+        let all = |fields: Vec<PlutusData>, schemas: &Vec<Value>| {
+            fields.len() == schemas.len()
+                && fields
+                    .iter()
+                    .zip(schemas)
+                    .all(|(field, schema)| conforms(field, schema, definitions))
+        };
+
+        match schema["dataType"].as_str() {
+            Some("integer") => data.as_integer::<i128>().is_some(),
+            Some("bytes") => data.as_bytes().is_some(),
+            Some("list") => match (data.as_list(), &schema["items"]) {
+                (Some(items), Value::Array(schemas)) => all(items.collect(), schemas),
+                (Some(mut items), item) => items.all(|x| conforms(&x, item, definitions)),
+                (None, _) => false,
+            },
+            Some("constructor") => match data.as_constr() {
+                Some((index, fields)) => {
+                    Some(index) == schema["index"].as_u64()
+                        && all(fields.collect(), schema["fields"].as_array().unwrap())
+                }
+                None => false,
+            },
+            _ => panic!("unsupported schema: {schema}"),
+        }
+    }
+
+    fn conforms_to_blueprint(data: &PlutusData, definition: &str) -> bool {
+        let blueprint: Value = serde_json::from_str(BLUEPRINT).unwrap();
+        let definitions = &blueprint["definitions"];
+        conforms(data, &definitions[definition], definitions)
+    }
+
+    fn tag() -> Tag {
+        Tag::from(vec![1, 2, 3])
+    }
+
+    fn unlocked(signing_key: &SigningKey, index: u64) -> Unlocked {
+        let secret = Secret([index as u8; 32]);
+        let body = ChequeBody::new(index, 1_000, Duration::from_secs(42), Lock::from(&secret));
+        Unlocked::new(Locked::make(signing_key, &tag(), body), secret).unwrap()
+    }
+
+    fn redeemer() -> (SigningKey, Redeemer) {
+        let signing_key = SigningKey::from([7; 32]);
+        let squash = Squash::make(
+            &signing_key,
+            &tag(),
+            SquashBody::new(500, 1, Indexes::default()).unwrap(),
+        );
+        let sub = Cont::Sub(
+            squash.clone(),
+            vec![unlocked(&signing_key, 2), unlocked(&signing_key, 3)],
+        );
+        let respond = Cont::Respond(
+            squash,
+            vec![
+                Cheque::from(unlocked(&signing_key, 4)),
+                Cheque::from(unlocked(&signing_key, 5).locked()),
+            ],
+        );
+        let redeemer = Redeemer::Main(vec![Step::Cont(sub), Step::Cont(respond)]);
+        (signing_key, redeemer)
+    }
+
+    fn unlockeds(redeemer: Redeemer) -> Vec<Unlocked> {
+        let Redeemer::Main(steps) = redeemer else {
+            panic!("not a main redeemer");
+        };
+        steps
+            .into_iter()
+            .flat_map(|step| match step {
+                Step::Cont(Cont::Sub(_, unlockeds)) => unlockeds,
+                Step::Cont(Cont::Respond(_, cheques)) => {
+                    cheques.iter().filter_map(Cheque::as_unlocked).collect()
+                }
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn v0_round_trips_and_conforms_to_blueprint() {
+        let (_, redeemer) = redeemer();
+        let data = redeemer.clone().to_plutus_data(ProtocolVersion::V0);
+        assert!(conforms_to_blueprint(&data, "konduit/types/Redeemer"));
+
+        let decoded = Redeemer::from_plutus_data(&data, ProtocolVersion::V0).unwrap();
+        assert_eq!(unlockeds(decoded), unlockeds(redeemer));
+    }
+
+    #[test]
+    fn v1_round_trips_and_verifies() {
+        let (signing_key, redeemer) = redeemer();
+        let v0 = redeemer.clone().to_plutus_data(ProtocolVersion::V0);
+        let v1 = redeemer.clone().to_plutus_data(ProtocolVersion::V1);
+        assert!(!conforms_to_blueprint(&v1, "konduit/types/Redeemer"));
+        assert!(
+            cardano_sdk::cbor::ToCbor::to_cbor(&v1).len()
+                < cardano_sdk::cbor::ToCbor::to_cbor(&v0).len()
+        );
+
+        let decoded = Redeemer::from_plutus_data(&v1, ProtocolVersion::V1).unwrap();
+        let decoded = unlockeds(decoded);
+        assert_eq!(decoded, unlockeds(redeemer));
+        assert!(
+            decoded
+                .iter()
+                .all(|u| u.verify_no_time(&signing_key.to_verification_key(), &tag()))
+        );
+    }
+
+    #[test]
+    fn v1_unlocked_with_wrong_secret_does_not_verify() {
+        let signing_key = SigningKey::from([7; 32]);
+        let mut fields = unlocked(&signing_key, 2).into_fields(ProtocolVersion::V1);
+        fields[3] = PlutusData::from(Secret([0xff; 32]));
+        let forged = Unlocked::from_fields(fields, ProtocolVersion::V1).unwrap();
+        assert!(!forged.verify_no_time(&signing_key.to_verification_key(), &tag()));
+    }
+}
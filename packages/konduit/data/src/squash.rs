@@ -1,5 +1,6 @@
 use crate::{
     SquashBody, Tag,
+    tbs::Tbs,
     utils::{signature_from_plutus_data, signature_to_plutus_data},
 };
 use anyhow::anyhow;
@@ -33,12 +34,16 @@ impl Squash {
     }
 
     pub fn make(signing_key: &SigningKey, tag: &Tag, body: SquashBody) -> Self {
-        let signature = signing_key.sign(tag.data(&body));
+        let signature = Tbs::Squash { tag, body: &body }.sign(signing_key);
         Self::new(body, signature)
     }
 
     pub fn verify(&self, verification_key: &VerificationKey, tag: &Tag) -> bool {
-        verification_key.verify(tag.data(&self.body), &self.signature)
+        Tbs::Squash {
+            tag,
+            body: &self.body,
+        }
+        .verify(verification_key, &self.signature)
     }
 }
 
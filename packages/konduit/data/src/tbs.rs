@@ -0,0 +1,180 @@
+//! To-Be-Signed (TBS) forms, as per ADR 08.
+//!
+//! Every message signed with a consumer key is one of the forms registered here, so that no two
+//! forms can ever produce the same bytes. Signing anything new requires extending the registry.
+//!
+//! ```cddl
+//! channel_tag = bytes
+//! index = uint
+//! amount = uint
+//! lock = bytes .size 32
+//! timeout = uint ; posix time in milliseconds
+//! excludes = [* index]
+//!
+//! cheque_body = [channel_tag, [index, amount, timeout, lock]]
+//! squash_body = [channel_tag, [amount, index, excludes]]
+//!
+//! token = bytes .size 32
+//! adaptor_key = bytes .size 32
+//! reg_body = ["REGISTER_TOKEN", channel_tag, [token, adaptor_key]]
+//! ```
+//!
+//! Cheques and squashes are verified on-chain and thus carry no domain label; forms only ever
+//! used off-chain start with one. Arrays follow the Plutus encoding: indefinite when non-empty.
+
+use crate::{ChequeBody, SquashBody, Tag};
+use cardano_sdk::{
+    PlutusData, Signature, SigningKey, VerificationKey,
+    cbor::{self, ToCbor},
+};
+
+/// Domain label of the token registration form.
+pub const REGISTER_TOKEN: &str = "REGISTER_TOKEN";
+
+/// The registry of forms signed with a consumer key.
+#[derive(Debug, Clone)]
+pub enum Tbs<'a> {
+    Cheque {
+        tag: &'a Tag,
+        body: &'a ChequeBody,
+    },
+    Squash {
+        tag: &'a Tag,
+        body: &'a SquashBody,
+    },
+    Register {
+        tag: &'a Tag,
+        token: &'a [u8; 32],
+        adaptor_key: &'a VerificationKey,
+    },
+}
+
+impl Tbs<'_> {
+    /// The bytes to sign, as per the CDDL of the form.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Cheque { tag, body } => PlutusData::list([
+                PlutusData::bytes(tag.as_ref()),
+                PlutusData::from((*body).clone()),
+            ])
+            .to_cbor(),
+            Self::Squash { tag, body } => {
+                PlutusData::list([PlutusData::bytes(tag.as_ref()), PlutusData::from(*body)])
+                    .to_cbor()
+            }
+            Self::Register {
+                tag,
+                token,
+                adaptor_key,
+            } => labelled(
+                REGISTER_TOKEN,
+                [
+                    PlutusData::bytes(tag.as_ref()),
+                    PlutusData::list([
+                        PlutusData::bytes(token.as_slice()),
+                        PlutusData::from(*adaptor_key),
+                    ]),
+                ],
+            ),
+        }
+    }
+
+    pub fn sign(&self, signing_key: &SigningKey) -> Signature {
+        signing_key.sign(self.to_bytes())
+    }
+
+    pub fn verify(&self, verification_key: &VerificationKey, signature: &Signature) -> bool {
+        verification_key.verify(self.to_bytes(), signature)
+    }
+}
+
+/// An (indefinite) array whose first element is a domain label.
+fn labelled<'a>(label: &str, fields: impl IntoIterator<Item = PlutusData<'a>>) -> Vec<u8> {
+    // Writing to a vector cannot fail, and neither can encoding PlutusData.
+    const INFALLIBLE: &str = "failed to encode into a vector";
+    let mut bytes = Vec::new();
+    let mut encoder = cbor::Encoder::new(&mut bytes);
+    encoder
+        .begin_array()
+        .and_then(|e| e.str(label))
+        .expect(INFALLIBLE);
+    for field in fields {
+        encoder.encode(&field).expect(INFALLIBLE);
+    }
+    encoder.end().expect(INFALLIBLE);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Duration, Indexes, Lock};
+
+    fn tag() -> Tag {
+        Tag::from(vec![0x01, 0x02])
+    }
+
+    #[test]
+    fn cheque_body_vector() {
+        let body = ChequeBody::new(1, 2, Duration::from_millis(3), Lock([0x11; 32]));
+        let bytes = Tbs::Cheque {
+            tag: &tag(),
+            body: &body,
+        }
+        .to_bytes();
+        // [channel_tag, [index, amount, timeout, lock]]
+        let expected = format!("9f420102 9f010203 5820{} ff ff", "11".repeat(32));
+        assert_eq!(hex::encode(bytes), expected.replace(' ', ""));
+    }
+
+    #[test]
+    fn squash_body_vector() {
+        let body = SquashBody::new(5, 7, Indexes::default()).unwrap();
+        let bytes = Tbs::Squash {
+            tag: &tag(),
+            body: &body,
+        }
+        .to_bytes();
+        // [channel_tag, [amount, index, excludes]]
+        assert_eq!(hex::encode(bytes), "9f4201029f050780ffff");
+    }
+
+    #[test]
+    fn reg_body_vector() {
+        let adaptor_key = VerificationKey::from([0x22; 32]);
+        let bytes = Tbs::Register {
+            tag: &tag(),
+            token: &[0x33; 32],
+            adaptor_key: &adaptor_key,
+        }
+        .to_bytes();
+        // ["REGISTER_TOKEN", channel_tag, [token, adaptor_key]]
+        let expected = format!(
+            "9f 6e{} 420102 9f 5820{} 5820{} ff ff",
+            hex::encode(REGISTER_TOKEN),
+            "33".repeat(32),
+            "22".repeat(32),
+        );
+        assert_eq!(hex::encode(bytes), expected.replace(' ', ""));
+    }
+
+    #[test]
+    fn signatures_are_bound_to_the_channel_tag() {
+        let signing_key = SigningKey::from([0x44; 32]);
+        let verification_key = signing_key.to_verification_key();
+        let body = SquashBody::new(5, 7, Indexes::default()).unwrap();
+        let squash = Tbs::Squash {
+            tag: &tag(),
+            body: &body,
+        };
+        let signature = squash.sign(&signing_key);
+        assert!(squash.verify(&verification_key, &signature));
+
+        let other_tag = Tag::from(vec![0x01]);
+        let other = Tbs::Squash {
+            tag: &other_tag,
+            body: &body,
+        };
+        assert!(!other.verify(&verification_key, &signature));
+    }
+}
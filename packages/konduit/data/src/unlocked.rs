@@ -1,7 +1,8 @@
 use crate::{
-    Duration, Lock, Secret, Tag,
+    Duration, Lock, ProtocolVersion, Secret, Tag,
     cheque_body::ChequeBody,
     locked::Locked,
+    tbs::Tbs,
     utils::{signature_from_plutus_data, signature_to_plutus_data},
 };
 use anyhow::{Error, Result, anyhow};
@@ -64,9 +65,63 @@ impl Unlocked {
         &self.body.timeout < timeout && self.verify_no_time(verification_key, tag)
     }
 
+    /// The body the consumer has signed, with the lock recomputed from the secret. This is what
+    /// de-locked cheques (ADR 09) are verified against, as they do not carry their lock.
+    pub fn effective_body(&self) -> ChequeBody {
+        ChequeBody {
+            lock: Lock::from(&self.secret),
+            ..self.body.clone()
+        }
+    }
+
     pub fn verify_no_time(&self, verification_key: &VerificationKey, tag: &Tag) -> bool {
-        let locked = self.locked();
-        locked.verify(verification_key, tag) && locked.body.is_secret(&self.secret)
+        let body = self.effective_body();
+        body.lock == self.body.lock
+            && Tbs::Cheque { tag, body: &body }.verify(verification_key, &self.signature)
+    }
+
+    pub fn from_fields(fields: Vec<PlutusData<'_>>, version: ProtocolVersion) -> Result<Self> {
+        match version {
+            ProtocolVersion::V0 => {
+                let [a, b, c] = <[PlutusData; 3]>::try_from(fields)
+                    .map_err(|_| anyhow!("invalid 'Unlocked'"))?;
+                let locked = Locked::new(ChequeBody::try_from(a)?, signature_from_plutus_data(&b)?);
+                Self::new(locked, Secret::try_from(&c)?)
+            }
+            ProtocolVersion::V1 => {
+                let [a, b, c, d, e] = <[PlutusData; 5]>::try_from(fields)
+                    .map_err(|_| anyhow!("invalid (de-locked) 'Unlocked'"))?;
+                let secret = Secret::try_from(&d)?;
+                let body = ChequeBody::new(
+                    <u64>::try_from(&a)?,
+                    <u64>::try_from(&b)?,
+                    Duration::try_from(&c)?,
+                    Lock::from(&secret),
+                );
+                Ok(Self::new_no_verify(
+                    body,
+                    signature_from_plutus_data(&e)?,
+                    secret,
+                ))
+            }
+        }
+    }
+
+    pub fn into_fields<'a>(self, version: ProtocolVersion) -> Vec<PlutusData<'a>> {
+        match version {
+            ProtocolVersion::V0 => vec![
+                PlutusData::from(self.body),
+                signature_to_plutus_data(self.signature),
+                PlutusData::from(self.secret),
+            ],
+            ProtocolVersion::V1 => vec![
+                PlutusData::from(self.body.index),
+                PlutusData::from(self.body.amount),
+                PlutusData::from(self.body.timeout),
+                PlutusData::from(self.secret),
+                signature_to_plutus_data(self.signature),
+            ],
+        }
     }
 }
 
@@ -83,10 +138,7 @@ impl<'a> TryFrom<Vec<PlutusData<'a>>> for Unlocked {
     type Error = Error;
 
     fn try_from(list: Vec<PlutusData<'a>>) -> Result<Self> {
-        let [a, b, c] =
-            <[PlutusData; 3]>::try_from(list).map_err(|_| anyhow!("invalid 'Unlocked'"))?;
-        let locked = Locked::new(ChequeBody::try_from(a)?, signature_from_plutus_data(&b)?);
-        Self::new(locked, Secret::try_from(&c)?)
+        Self::from_fields(list, ProtocolVersion::default())
     }
 }
 
@@ -98,10 +150,6 @@ impl<'a> From<Unlocked> for PlutusData<'a> {
 
 impl<'a> From<Unlocked> for Vec<PlutusData<'a>> {
     fn from(unlocked: Unlocked) -> Self {
-        vec![
-            PlutusData::from(unlocked.body),
-            signature_to_plutus_data(unlocked.signature),
-            PlutusData::from(unlocked.secret),
-        ]
+        unlocked.into_fields(ProtocolVersion::default())
     }
 }
@@ -1,15 +1,24 @@
 use async_trait::async_trait;
 
+mod submission;
+pub use submission::{Metrics, Rejection};
+
 mod args;
 pub use args::AdminArgs as Args;
 
 mod config;
 pub use config::Config;
 
+mod onchain;
+pub use onchain::Onchain;
+
 mod service;
 pub use service::Service;
 
 #[async_trait(?Send)]
 pub trait SyncApi: Send + Sync {
     async fn sync(&self) -> Result<(), anyhow::Error>;
+
+    /// Counts of submission outcomes since startup.
+    fn metrics(&self) -> Metrics;
 }
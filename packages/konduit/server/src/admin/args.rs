@@ -6,6 +6,20 @@ pub struct AdminArgs {
     // Prevent name clash with fx_every
     #[arg( long, env = "ADMIN_EVERY", value_parser = humantime::parse_duration, default_value = "10m",)]
     pub admin_every: Duration,
+    /// How often protocol parameters and reference scripts are refreshed. They are also refreshed
+    /// whenever a submission fails on them.
+    #[arg(long, env = "ADMIN_REFRESH_EVERY", value_parser = humantime::parse_duration, default_value = "1h")]
+    pub admin_refresh_every: Duration,
+    /// How deep, in blocks, admin txs are followed on-chain until deemed confirmed.
+    #[arg(long, env = "ADMIN_CONFIRMATION_DEPTH", default_value_t = 15)]
+    pub admin_confirmation_depth: u64,
+    /// Dedicated collaterals the admin wallet keeps, splitting them off its funds when short.
+    #[arg(long, env = "ADMIN_COLLATERALS", default_value_t = 1)]
+    pub admin_collaterals: usize,
+    /// Pure-ADA UTxOs the admin wallet holds, collaterals aside, beyond which they are
+    /// consolidated.
+    #[arg(long, env = "ADMIN_MAX_FUEL_UTXOS", default_value_t = 20)]
+    pub admin_max_fuel_utxos: usize,
     #[arg(long, env = crate::env::MIN_SINGLE, default_value_t = 1000)]
     pub min_single: u64,
     #[arg(long, env = crate::env::MIN_TOTAL, default_value_t = 1_000_000)]
@@ -1,39 +1,40 @@
 use crate::common;
-use cardano_sdk::{Address, SigningKey, VerificationKey, address::kind};
+use cardano_sdk::{Address, address::kind};
 use konduit_data::ChannelParameters;
-use konduit_tx::adaptor::AdaptorPreferences;
+use konduit_signer::Signer;
+use konduit_tx::{TxBudget, adaptor::AdaptorPreferences, admin::WalletPolicy};
+use std::{sync::Arc, time::Duration};
 
 pub struct Config {
-    pub wallet: SigningKey,
+    pub signer: Arc<dyn Signer>,
     pub channel_parameters: ChannelParameters,
     pub tx_preferences: AdaptorPreferences,
+    pub wallet_policy: WalletPolicy,
     pub host_address: Address<kind::Shelley>,
+    pub refresh_every: Duration,
+    pub confirmation_depth: u64,
 }
 
 impl Config {
-    pub fn from_args(common: common::Args, admin: super::Args) -> Self {
-        let common::Args {
-            signing_key: wallet,
-            close_period,
-            tag_length,
-            host_address,
-            ..
-        } = common;
-        let adaptor_key = VerificationKey::from(&wallet);
-        let channel_parameters = ChannelParameters {
-            adaptor_key,
-            close_period,
-            tag_length,
-        };
+    pub fn from_args(common: &common::Args, admin: super::Args, signer: Arc<dyn Signer>) -> Self {
+        let channel_parameters = common.channel_parameters(signer.verification_key());
         let tx_preferences = AdaptorPreferences {
             min_single: admin.min_single,
             min_total: admin.min_total,
+            budget: TxBudget::default(),
+        };
+        let wallet_policy = WalletPolicy {
+            collaterals: admin.admin_collaterals,
+            max_fuel_utxos: admin.admin_max_fuel_utxos,
         };
         Self {
-            wallet,
+            signer,
             channel_parameters,
             tx_preferences,
-            host_address,
+            wallet_policy,
+            host_address: common.host_address.clone(),
+            refresh_every: admin.admin_refresh_every,
+            confirmation_depth: admin.admin_confirmation_depth,
         }
     }
 }
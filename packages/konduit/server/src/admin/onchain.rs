@@ -0,0 +1,112 @@
+use cardano_connector::CardanoConnector;
+use cardano_sdk::{Address, Hash, Input, Output, address::kind};
+use konduit_tx::{KONDUIT_VALIDATOR, NetworkParameters, find_reference_scripts};
+use std::{collections::BTreeMap, time::Instant};
+
+/// The "almost constant" chain state that admin txs are built against: protocol parameters, and
+/// the reference scripts deployed at the host address. Both change rarely, so they are fetched
+/// once, and refreshed from time to time, or when a submission suggests they are outdated.
+#[derive(Debug, Clone)]
+pub struct Onchain {
+    pub network_parameters: NetworkParameters,
+    /// Reference scripts of every known validator version deployed at the host address.
+    pub script_utxos: BTreeMap<Input, Output>,
+    pub fetched_at: Instant,
+}
+
+impl Onchain {
+    pub async fn fetch<Connector: CardanoConnector>(
+        cardano: &Connector,
+        host_address: &Address<kind::Shelley>,
+    ) -> anyhow::Result<Self> {
+        let protocol_parameters = cardano.protocol_parameters().await?;
+        let network_id = cardano.network().into();
+        let network_parameters = NetworkParameters {
+            network_id,
+            protocol_parameters,
+        };
+        let host_utxos = cardano
+            .utxos_at(&host_address.payment(), host_address.delegation().as_ref())
+            .await?;
+        let script_candidates = host_utxos
+            .iter()
+            .filter_map(|(input, output)| {
+                output
+                    .script()
+                    .map(|script| (input.clone(), Hash::<28>::from(script), script.version()))
+            })
+            .collect::<Vec<_>>();
+        let script_utxos = find_reference_scripts(&host_utxos);
+        if KONDUIT_VALIDATOR
+            .find_reference_script(&script_utxos)
+            .is_none()
+        {
+            let script_summary = if script_candidates.is_empty() {
+                "none".to_string()
+            } else {
+                script_candidates
+                    .iter()
+                    .map(|(input, hash, version)| {
+                        format!("{input} hash={hash} version={version:#}")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            return Err(anyhow::anyhow!(
+                "No reference script found at host address {}. Retrieved {} host UTxO(s); script candidates: {}. Expected script hash {}",
+                host_address,
+                script_candidates.len(),
+                script_summary,
+                KONDUIT_VALIDATOR.hash,
+            ));
+        }
+        Ok(Self {
+            network_parameters,
+            script_utxos,
+            fetched_at: Instant::now(),
+        })
+    }
+}
+
+/// Ledger rejections that betray outdated protocol parameters or reference scripts, as named by
+/// the ledger's predicate failures.
+const OUTDATED: [&str; 5] = [
+    // Fees computed with former fee parameters.
+    "FeeTooSmallUTxO",
+    // Script integrity hash computed over a former cost model.
+    "PPViewHashesDontMatch",
+    "ScriptIntegrityHashMismatch",
+    // Reference script moved, no longer witnessing the spend. An input spent instead betrays a
+    // stale UTxO view, which a refresh of the chain state does not fix.
+    "MissingScriptWitnessesUTXOW",
+    // Execution budget priced or capped with former parameters.
+    "ExUnitsTooBigUTxO",
+];
+
+/// Whether a submission failed in a way that refreshing the chain state may fix.
+pub fn is_outdated(error: &anyhow::Error) -> bool {
+    let message = format!("{error:#}");
+    OUTDATED.iter().any(|failure| message.contains(failure))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spots_outdated_chain_state() {
+        assert!(is_outdated(&anyhow::anyhow!(
+            "submit failed: ShelleyTxValidationError (ApplyTxError [FeeTooSmallUTxO (Coin 180000) (Coin 170000)])"
+        )));
+        assert!(is_outdated(
+            &anyhow::anyhow!("PPViewHashesDontMatch").context("failed to submit")
+        ));
+        assert!(!is_outdated(&anyhow::anyhow!(
+            "OutsideValidityIntervalUTxO"
+        )));
+        assert!(!is_outdated(&anyhow::anyhow!(
+            "ApplyTxError [BadInputsUTxO (fromList [...])]"
+        )));
+    }
+}
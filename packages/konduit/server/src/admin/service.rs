@@ -1,27 +1,50 @@
 use crate::{
-    admin::{SyncApi, config::Config},
+    admin::{
+        SyncApi,
+        config::Config,
+        onchain::{self, Onchain},
+        submission::{Metrics, Rejection, Submissions},
+    },
     channel::Retainer,
     db,
 };
 use async_trait::async_trait;
 use cardano_connector::CardanoConnector;
-use cardano_sdk::{Credential, Hash, Input, Output, SigningKey, VerificationKey};
-use konduit_data::{ChannelParameters, Keytag, Secret};
+use cardano_sdk::{
+    Address, Credential, Hash, Input, Output, Transaction, address::kind, transaction::state,
+};
+use konduit_data::{ChannelParameters, Keytag, Receipt, Secret};
+use konduit_signer::Signer;
 use konduit_tx::{
-    Bounds, ChannelUtxo, KONDUIT_VALIDATOR, NetworkParameters, adaptor::AdaptorPreferences,
+    Bounds, ChannelUtxo, KONDUIT_VALIDATORS, adaptor::AdaptorPreferences, admin::WalletPolicy,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard, RwLock},
+    time::Duration,
 };
-use std::{collections::BTreeMap, iter, sync::Arc};
+
+/// How many times a submission failing on the way to the ledger is attempted again.
+const SUBMIT_RETRIES: u32 = 2;
+/// Wait before the n-th retry: n times this.
+const RETRY_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Clone)]
 pub struct Service<Connector: CardanoConnector + Send + Sync + 'static> {
     bln: Arc<dyn bln_client::Api + Send + Sync + 'static>,
     cardano: Arc<Connector>,
     db: Arc<dyn db::Api + Send + Sync + 'static>,
-    network_parameters: NetworkParameters,
     channel_parameters: ChannelParameters,
     tx_preferences: AdaptorPreferences,
-    script_utxo: (Input, Output),
-    wallet: SigningKey,
+    wallet_policy: WalletPolicy,
+    host_address: Address<kind::Shelley>,
+    /// Swapped as a whole on refresh, so that a sync builds against a consistent state.
+    onchain: Arc<RwLock<Arc<Onchain>>>,
+    refresh_every: Duration,
+    signer: Arc<dyn Signer>,
+    /// Our txs from submission to confirmation depth. Unconfirmed, the next ones are built upon.
+    submissions: Arc<Mutex<Submissions>>,
+    confirmation_depth: u64,
 }
 
 impl<Connector: CardanoConnector + Send + Sync + 'static> Service<Connector> {
@@ -32,74 +55,159 @@ impl<Connector: CardanoConnector + Send + Sync + 'static> Service<Connector> {
         db: Arc<dyn db::Api + Send + Sync + 'static>,
     ) -> anyhow::Result<Self> {
         let Config {
-            wallet,
+            signer,
             channel_parameters,
             tx_preferences,
+            wallet_policy,
             host_address,
+            refresh_every,
+            confirmation_depth,
         } = config;
-        // Treat network parameters as constants.
-        // This will mean the service requires restarting
-        // when a there is a protocol params change.
-        let protocol_parameters = cardano.clone().protocol_parameters().await?;
-        let network_id = cardano.network().into();
-        let network_parameters = NetworkParameters {
-            network_id,
-            protocol_parameters,
-        };
-        // Treat reference script utxo as constant.
-        // If this moves, the service needs to be restarted.
-        let host_utxos = cardano
-            .utxos_at(&host_address.payment(), host_address.delegation().as_ref())
-            .await?;
-        let script_candidates = host_utxos
-            .iter()
-            .filter_map(|(input, output)| {
-                output
-                    .script()
-                    .map(|script| (input.clone(), Hash::<28>::from(script), script.version()))
-            })
-            .collect::<Vec<_>>();
-        let Some(script_utxo) = host_utxos.into_iter().find(|(_, o)| {
-            o.script()
-                .is_some_and(|s| Hash::<28>::from(s) == KONDUIT_VALIDATOR.hash)
-        }) else {
-            let script_summary = if script_candidates.is_empty() {
-                "none".to_string()
-            } else {
-                script_candidates
-                    .iter()
-                    .map(|(input, hash, version)| {
-                        format!("{input} hash={hash} version={version:#}")
-                    })
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            };
-
-            return Err(anyhow::anyhow!(
-                "No reference script found at host address {}. Retrieved {} host UTxO(s); script candidates: {}. Expected script hash {}",
-                host_address,
-                script_candidates.len(),
-                script_summary,
-                KONDUIT_VALIDATOR.hash,
-            ));
-        };
+        // Network parameters and reference scripts are almost constant: they are fetched once,
+        // then refreshed periodically or upon a submission failing on them. See `Onchain`.
+        let onchain = Onchain::fetch(cardano.as_ref(), &host_address).await?;
 
         Ok(Self {
             bln,
             cardano,
             db,
-            network_parameters,
             channel_parameters,
             tx_preferences,
-            script_utxo,
-            wallet,
+            wallet_policy,
+            host_address,
+            onchain: Arc::new(RwLock::new(Arc::new(onchain))),
+            refresh_every,
+            signer,
+            submissions: Arc::default(),
+            confirmation_depth,
         })
     }
 
+    /// Counts of submission outcomes since startup.
+    pub fn metrics(&self) -> Metrics {
+        self.submissions().metrics()
+    }
+
+    fn submissions(&self) -> MutexGuard<'_, Submissions> {
+        self.submissions.lock().expect("submissions lock")
+    }
+
+    /// The chain state admin txs are currently built against.
+    pub fn onchain(&self) -> Arc<Onchain> {
+        self.onchain.read().expect("onchain lock").clone()
+    }
+
+    /// Fetch the chain state anew, and swap it in. On failure, the current state is kept.
+    pub async fn refresh(&self) -> anyhow::Result<()> {
+        let onchain = Onchain::fetch(self.cardano.as_ref(), &self.host_address).await?;
+        *self.onchain.write().expect("onchain lock") = Arc::new(onchain);
+        Ok(())
+    }
+
+    async fn refresh_if_stale(&self) {
+        if self.onchain().fetched_at.elapsed() < self.refresh_every {
+            return;
+        }
+        if let Err(error) = self.refresh().await {
+            log::warn!("failed to refresh chain state, keeping the current one: {error:#}");
+        }
+    }
+
+    /// Build, sign and submit txs, in order, against the current chain state. Should the first
+    /// submission fail on an outdated state, the state is refreshed and the txs rebuilt and
+    /// submitted once more. Later failures only refresh the state: the txs before went through,
+    /// and the next sync picks up from there. Failures on a stale chain view are left to `sync`,
+    /// which rebuilds against a fresh one.
+    async fn submit_with(
+        &self,
+        build: impl Fn(&Onchain) -> anyhow::Result<Vec<Transaction<state::ReadyForSigning>>>,
+    ) -> anyhow::Result<()> {
+        let build = &build;
+        let submit = |onchain: Arc<Onchain>| async move {
+            let txs = build(&onchain).map_err(|error| (0, error))?;
+            self.submit_all(txs).await
+        };
+        match submit(self.onchain()).await {
+            Ok(()) => Ok(()),
+            Err((0, error))
+                if onchain::is_outdated(&error)
+                    && !Rejection::classify(&error).is_rebuildable() =>
+            {
+                log::warn!("submission failed on outdated chain state, refreshing: {error:#}");
+                self.refresh().await?;
+                submit(self.onchain()).await.map_err(|(_, error)| error)
+            }
+            Err((submitted, error)) => {
+                if onchain::is_outdated(&error)
+                    && let Err(refresh_error) = self.refresh().await
+                {
+                    log::warn!("failed to refresh chain state: {refresh_error:#}");
+                }
+                if submitted == 0 {
+                    return Err(error);
+                }
+                Err(error.context(format!("after submitting {submitted} tx(s)")))
+            }
+        }
+    }
+
+    /// Sign and submit txs in order, stopping at the first failure, along with the number of txs
+    /// submitted before it.
+    async fn submit_all(
+        &self,
+        txs: Vec<Transaction<state::ReadyForSigning>>,
+    ) -> Result<(), (usize, anyhow::Error)> {
+        for (submitted, mut tx) in txs.into_iter().enumerate() {
+            self.signer
+                .witness(&mut tx)
+                .await
+                .map_err(|error| (submitted, error))?;
+            self.submit(&tx).await.map_err(|error| (submitted, error))?;
+            self.submissions().submitted(&tx);
+        }
+        Ok(())
+    }
+
+    /// Submit a tx, attempting again should it fail on the way to the ledger.
+    async fn submit(&self, tx: &Transaction<state::ReadyForSigning>) -> anyhow::Result<()> {
+        let mut retries = 0;
+        loop {
+            let Err(error) = self.cardano.submit(tx).await else {
+                return Ok(());
+            };
+            let rejection = Rejection::classify(&error);
+            self.submissions().rejected(rejection);
+            if !rejection.is_retriable() || retries == SUBMIT_RETRIES {
+                log::warn!("tx {} rejected ({rejection:?}): {error:#}", tx.id());
+                if rejection == Rejection::InputsSpent {
+                    self.submissions().drop_parents(tx);
+                }
+                return Err(error);
+            }
+            retries += 1;
+            log::warn!(
+                "submission of tx {} failed ({rejection:?}), retrying: {error:#}",
+                tx.id()
+            );
+            self.submissions().retried();
+            tokio::time::sleep(RETRY_DELAY * retries).await;
+        }
+    }
+
+    /// Follow our txs on-chain until as deep as required.
+    async fn follow_confirmations(&self) {
+        let confirming = self.submissions().confirming();
+        for id in confirming {
+            let depth = self.cardano.confirmations(&id).await;
+            self.submissions()
+                .settle(&id, depth, self.confirmation_depth);
+        }
+    }
+
     fn retainers(&self, utxos: &BTreeMap<Input, Output>) -> BTreeMap<Keytag, Vec<Retainer>> {
         let close_period = self.channel_parameters.close_period;
         let tag_length = self.channel_parameters.tag_length;
-        let own_vkey = VerificationKey::from(&self.wallet);
+        let own_vkey = self.signer.verification_key();
         let candidates = utxos
             .iter()
             .filter_map(|u| ChannelUtxo::try_from(u).ok())
@@ -128,19 +236,50 @@ impl<Connector: CardanoConnector + Send + Sync + 'static> Service<Connector> {
 
     /// These should be considered confirmed utxos,
     /// acceptable to be treated as retainers.
+    /// Channels are found regardless of the stake credential consumers delegate them to.
     async fn snapshot(&self) -> anyhow::Result<BTreeMap<Input, Output>> {
-        let credential = Credential::from_script(KONDUIT_VALIDATOR.hash);
-        let utxos = self.cardano.utxos_at(&credential, None).await?;
+        let mut utxos = BTreeMap::new();
+        for credential in KONDUIT_VALIDATORS.credentials() {
+            utxos.extend(self.cardano.utxos_at(&credential, None).await?);
+        }
         Ok(utxos)
     }
 
-    /// These should be considered confirmed utxos,
-    /// acceptable to be treated as retainers.
+    /// These should be considered confirmed utxos.
     async fn wallet_utxos(&self) -> anyhow::Result<BTreeMap<Input, Output>> {
-        let vkh = Hash::<28>::new(VerificationKey::from(&self.wallet));
+        let vkh = Hash::<28>::new(self.signer.verification_key());
         let credential = Credential::from_key(vkh);
-        let utxos = self.cardano.utxos_at(&credential, None).await?;
-        Ok(utxos)
+        self.cardano.utxos_at(&credential, None).await
+    }
+
+    fn wallet_address(&self) -> Address<kind::Any> {
+        self.signer
+            .verification_key()
+            .to_address(self.cardano.network().into())
+            .into()
+    }
+
+    /// Keep the wallet's UTxO set in line with its policy: see `konduit_tx::admin::upkeep`.
+    async fn upkeep(&self, wallet_utxos: &BTreeMap<Input, Output>) -> anyhow::Result<()> {
+        let address = self.wallet_address();
+        // Txs just submitted spend some of these, and pay change back.
+        let wallet_utxos = self
+            .submissions()
+            .pending(wallet_utxos)
+            .into_iter()
+            .filter(|(_, output)| output.address() == &address)
+            .collect();
+        self.submit_with(|onchain| {
+            Ok(konduit_tx::admin::upkeep(
+                &onchain.network_parameters.protocol_parameters,
+                &wallet_utxos,
+                address.clone(),
+                &self.wallet_policy,
+            )?
+            .into_iter()
+            .collect())
+        })
+        .await
     }
 
     pub async fn unlocks(&self) -> Result<(), anyhow::Error> {
@@ -181,26 +320,64 @@ impl<Connector: CardanoConnector + Send + Sync + 'static> Service<Connector> {
                     .map(|r| (kt.clone(), r))
             })
             .collect::<BTreeMap<_, _>>();
-        // FIXME :: This is the fudge. We treat tip as snapshot.
-        // We are more likely to either:
-        // - treat as confirmed something that will rollback
-        // - use as an input a utxo that has already been spent.
-        let tip = iter::once(self.script_utxo.clone())
-            .chain(snapshot)
-            .chain(self.wallet_utxos().await?)
+        self.refresh_if_stale().await;
+        self.follow_confirmations().await;
+        let mut wallet_utxos = self.wallet_utxos().await?;
+        let stepped = match self.step(&snapshot, &wallet_utxos, &receipts).await {
+            // Built against a stale chain view or bounds: once more, against fresh ones. Pending
+            // txs whose outputs the rejected tx spent are dropped already, see `submit`.
+            Err(error) if Rejection::classify(&error).is_rebuildable() => {
+                log::warn!("rebuilding against a fresh chain view: {error:#}");
+                self.submissions().rebuilt();
+                let snapshot = self.snapshot().await?;
+                wallet_utxos = self.wallet_utxos().await?;
+                self.step(&snapshot, &wallet_utxos, &receipts).await
+            }
+            stepped => stepped,
+        };
+        if let Err(error) = self.upkeep(&wallet_utxos).await {
+            log::warn!("wallet upkeep failed: {error:#}");
+        }
+        stepped
+    }
+
+    /// Build and submit the txs stepping our channels, given chain views of channels and wallet.
+    async fn step(
+        &self,
+        snapshot: &BTreeMap<Input, Output>,
+        wallet_utxos: &BTreeMap<Input, Output>,
+        receipts: &BTreeMap<Keytag, Receipt>,
+    ) -> anyhow::Result<()> {
+        let view = snapshot
+            .iter()
+            .chain(wallet_utxos)
+            .map(|(input, output)| (input.clone(), output.clone()))
             .collect::<BTreeMap<_, _>>();
-        let upper_bound = Bounds::twenty_mins().upper.expect("This returns `Some`!!");
-        let mut tx = konduit_tx::adaptor::tx(
-            &self.network_parameters,
-            &self.tx_preferences,
-            &VerificationKey::from(&self.wallet),
-            &receipts,
-            &tip,
-            &upper_bound,
-        )?;
-        tx.sign(&self.wallet);
-        self.cardano.submit(&tx).await?;
-        Ok(())
+        // Our unconfirmed txs are built upon rather than waited for: the channels and change they
+        // produce are spent right away, and those they spend are left out.
+        let pending = {
+            let mut submissions = self.submissions();
+            submissions.reconcile(&view);
+            submissions.pending(&view)
+        };
+        self.submit_with(|onchain| {
+            let tip = onchain
+                .script_utxos
+                .iter()
+                .chain(&pending)
+                .map(|(input, output)| (input.clone(), output.clone()))
+                .collect::<BTreeMap<_, _>>();
+            let upper_bound = Bounds::twenty_mins().upper.expect("This returns `Some`!!");
+            konduit_tx::adaptor::txs(
+                &onchain.network_parameters,
+                &self.tx_preferences,
+                &self.signer.verification_key(),
+                receipts,
+                &tip,
+                &upper_bound,
+            )
+        })
+        .await
     }
 }
 
@@ -209,26 +386,51 @@ impl<Connector: CardanoConnector + Send + Sync + 'static> SyncApi for Service<Co
     async fn sync(&self) -> Result<(), anyhow::Error> {
         Service::sync(self).await
     }
+
+    fn metrics(&self) -> Metrics {
+        Service::metrics(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Service;
-    use crate::{Channel, ChannelError, admin::config::Config, channel::Retainer, db};
+    use crate::{
+        Channel, ChannelError,
+        admin::{Rejection, config::Config},
+        channel::Retainer,
+        db,
+    };
     use async_trait::async_trait;
     use cardano_connector::CardanoConnector;
+    use cardano_sdk::cbor::{FromCbor, ToCbor};
     use cardano_sdk::{
-        Address, Credential, Hash, Input, Network, Output, PlutusScript, PlutusVersion,
+        Address, Credential, Hash, Input, Network, NetworkId, Output, PlutusScript, PlutusVersion,
         ProtocolParameters, SigningKey, Transaction, Value, address::kind, transaction::state,
     };
-    use konduit_data::{ChannelParameters, Duration, Keytag, Locked, Secret, Squash};
-    use konduit_tx::{KONDUIT_VALIDATOR, adaptor::AdaptorPreferences};
-    use std::{collections::BTreeMap, sync::Arc};
+    use konduit_data::{
+        ChannelParameters, Constants, Duration, Indexes, Keytag, Locked, Secret, Squash,
+        SquashBody, Tag,
+    };
+    use konduit_tx::{
+        KONDUIT_VALIDATOR, Open, TxBudget, adaptor::AdaptorPreferences, admin::WalletPolicy,
+    };
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
 
     struct FakeConnector {
         network: Network,
-        protocol_parameters: Result<ProtocolParameters, String>,
-        host_utxos: Result<BTreeMap<Input, Output>, String>,
+        protocol_parameters: Mutex<Result<ProtocolParameters, String>>,
+        host_utxos: Mutex<Result<BTreeMap<Input, Output>, String>>,
+        /// UTxOs elsewhere than at the host address, if the chain is followed at all: submissions
+        /// are then rejected on inputs neither found there nor produced by txs accepted since.
+        chain: Mutex<Option<BTreeMap<Input, Output>>>,
+        /// Txs accepted, as if in the mempool.
+        submitted: Mutex<Vec<Transaction<state::ReadyForSigning>>>,
+        /// Errors the next submissions fail with, in order.
+        failures: Mutex<Vec<String>>,
     }
 
     impl FakeConnector {
@@ -238,10 +440,21 @@ mod tests {
         ) -> Self {
             Self {
                 network: Network::Preview,
-                protocol_parameters: protocol_parameters.map_err(Into::into),
-                host_utxos: host_utxos.map_err(Into::into),
+                protocol_parameters: Mutex::new(protocol_parameters.map_err(Into::into)),
+                host_utxos: Mutex::new(host_utxos.map_err(Into::into)),
+                chain: Mutex::default(),
+                submitted: Mutex::default(),
+                failures: Mutex::default(),
             }
         }
+
+        fn set_protocol_parameters(&self, protocol_parameters: ProtocolParameters) {
+            *self.protocol_parameters.lock().unwrap() = Ok(protocol_parameters);
+        }
+
+        fn set_host_utxos(&self, host_utxos: BTreeMap<Input, Output>) {
+            *self.host_utxos.lock().unwrap() = Ok(host_utxos);
+        }
     }
 
     impl CardanoConnector for FakeConnector {
@@ -254,7 +467,11 @@ mod tests {
         }
 
         async fn protocol_parameters(&self) -> anyhow::Result<ProtocolParameters> {
-            self.protocol_parameters.clone().map_err(anyhow::Error::msg)
+            self.protocol_parameters
+                .lock()
+                .unwrap()
+                .clone()
+                .map_err(anyhow::Error::msg)
         }
 
         async fn utxos_at(
@@ -262,21 +479,69 @@ mod tests {
             payment: &Credential,
             delegation: Option<&Credential>,
         ) -> anyhow::Result<BTreeMap<Input, Output>> {
-            let expected = test_host_address().payment();
-            assert_eq!(payment, &expected);
+            if payment != &test_host_address().payment() {
+                let chain = self.chain.lock().unwrap();
+                return Ok(chain
+                    .iter()
+                    .flatten()
+                    .filter(|(_, output)| {
+                        output
+                            .address()
+                            .as_shelley()
+                            .is_some_and(|address| &address.payment() == payment)
+                    })
+                    .map(|(input, output)| (input.clone(), output.clone()))
+                    .collect());
+            }
             assert_eq!(delegation, test_host_address().delegation().as_ref());
-            self.host_utxos.clone().map_err(anyhow::Error::msg)
+            self.host_utxos
+                .lock()
+                .unwrap()
+                .clone()
+                .map_err(anyhow::Error::msg)
         }
 
+        /// Rejects txs paying less than the fee constant of the current protocol parameters.
         async fn submit(
             &self,
-            _transaction: &Transaction<state::ReadyForSigning>,
+            transaction: &Transaction<state::ReadyForSigning>,
         ) -> anyhow::Result<()> {
+            let failure = {
+                let mut failures = self.failures.lock().unwrap();
+                (!failures.is_empty()).then(|| failures.remove(0))
+            };
+            if let Some(failure) = failure {
+                return Err(anyhow::anyhow!(failure));
+            }
+            let minimum = self.protocol_parameters().await?.base_fee(0);
+            if transaction.fee() < minimum {
+                return Err(anyhow::anyhow!(
+                    "ApplyTxError [FeeTooSmallUTxO (Coin {minimum}) (Coin {})]",
+                    transaction.fee()
+                ));
+            }
+            if let Some(chain) = self.chain.lock().unwrap().as_ref() {
+                let submitted = self.submitted.lock().unwrap();
+                let known = |input: &Input| {
+                    chain.contains_key(input)
+                        || submitted.iter().any(|tx| tx.id() == input.transaction_id())
+                };
+                if !transaction.inputs().all(|input| known(&input)) {
+                    return Err(anyhow::anyhow!(
+                        "ApplyTxError [BadInputsUTxO (fromList [...])]"
+                    ));
+                }
+            }
+            self.submitted.lock().unwrap().push(transaction.clone());
             Ok(())
         }
     }
 
-    struct FakeDb;
+    #[derive(Default)]
+    struct FakeDb {
+        /// Channels, as known off-chain, whatever the retainers.
+        channels: Mutex<BTreeMap<Keytag, Channel>>,
+    }
 
     #[async_trait]
     impl db::Api for FakeDb {
@@ -284,7 +549,13 @@ mod tests {
             &self,
             _retainers: BTreeMap<Keytag, Vec<Retainer>>,
         ) -> db::Result<BTreeMap<Keytag, Result<Channel, ChannelError>>> {
-            Ok(BTreeMap::new())
+            Ok(self
+                .channels
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(keytag, channel)| (keytag.clone(), Ok(channel.clone())))
+                .collect())
         }
 
         async fn get_channel(&self, _keytag: &Keytag) -> db::Result<Option<Channel>> {
@@ -311,7 +582,7 @@ mod tests {
     fn test_config() -> Config {
         let wallet = SigningKey::from([7; 32]);
         Config {
-            wallet: wallet.clone(),
+            signer: Arc::new(wallet.clone()),
             channel_parameters: ChannelParameters {
                 adaptor_key: wallet.to_verification_key(),
                 close_period: Duration::from_secs(60),
@@ -320,8 +591,12 @@ mod tests {
             tx_preferences: AdaptorPreferences {
                 min_single: 1,
                 min_total: 1,
+                budget: TxBudget::default(),
             },
+            wallet_policy: WalletPolicy::default(),
             host_address: test_host_address(),
+            refresh_every: std::time::Duration::from_secs(3600),
+            confirmation_depth: 3,
         }
     }
 
@@ -355,7 +630,7 @@ mod tests {
             test_config(),
             Arc::new(bln_client::mock::Client::new()),
             connector,
-            Arc::new(FakeDb),
+            Arc::new(FakeDb::default()),
         )
         .await
         .err()
@@ -379,7 +654,7 @@ mod tests {
             test_config(),
             Arc::new(bln_client::mock::Client::new()),
             connector,
-            Arc::new(FakeDb),
+            Arc::new(FakeDb::default()),
         )
         .await
         .err()
@@ -401,10 +676,246 @@ mod tests {
             test_config(),
             Arc::new(bln_client::mock::Client::new()),
             connector,
-            Arc::new(FakeDb),
+            Arc::new(FakeDb::default()),
         )
         .await;
 
         assert!(service.is_ok(), "startup smoke path should succeed");
     }
+
+    async fn running_service(connector: Arc<FakeConnector>) -> Service<FakeConnector> {
+        Service::new(
+            test_config(),
+            Arc::new(bln_client::mock::Client::new()),
+            connector,
+            Arc::new(FakeDb::default()),
+        )
+        .await
+        .expect("service starts")
+    }
+
+    #[tokio::test]
+    async fn refresh_swaps_in_changed_protocol_parameters() {
+        let connector = Arc::new(FakeConnector::new(
+            Ok::<_, &str>(ProtocolParameters::default()),
+            Ok::<_, &str>(host_utxos_with_reference_script()),
+        ));
+        let service = running_service(connector.clone()).await;
+        let before = service.onchain();
+
+        let updated = ProtocolParameters::default().with_fee_constant(200_000);
+        connector.set_protocol_parameters(updated.clone());
+        service.refresh().await.unwrap();
+
+        assert_eq!(
+            service.onchain().network_parameters.protocol_parameters,
+            updated
+        );
+        // Holders of the former state are unaffected by the swap.
+        assert_eq!(
+            before.network_parameters.protocol_parameters,
+            ProtocolParameters::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_keeps_current_state_when_reference_script_is_gone() {
+        let connector = Arc::new(FakeConnector::new(
+            Ok::<_, &str>(ProtocolParameters::default()),
+            Ok::<_, &str>(host_utxos_with_reference_script()),
+        ));
+        let service = running_service(connector.clone()).await;
+
+        connector.set_host_utxos(BTreeMap::new());
+        assert!(service.refresh().await.is_err());
+        assert_eq!(
+            service.onchain().script_utxos,
+            host_utxos_with_reference_script()
+        );
+    }
+
+    #[tokio::test]
+    async fn submission_failing_on_outdated_parameters_refreshes_and_retries() {
+        let connector = Arc::new(FakeConnector::new(
+            Ok::<_, &str>(ProtocolParameters::default().with_fee_constant(150_000)),
+            Ok::<_, &str>(host_utxos_with_reference_script()),
+        ));
+        let service = running_service(connector.clone()).await;
+        connector.set_protocol_parameters(ProtocolParameters::default().with_fee_constant(200_000));
+
+        service
+            .submit_with(|onchain| {
+                let mut tx = Transaction::default();
+                tx.with_inputs([(Input::new(Hash::<32>::from([1; 32]), 0), None)])
+                    .with_fee(onchain.network_parameters.protocol_parameters.base_fee(0));
+                Ok(vec![Transaction::from_cbor(&tx.to_cbor())?])
+            })
+            .await
+            .unwrap();
+
+        let fees = connector
+            .submitted
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|tx| tx.fee())
+            .collect::<Vec<_>>();
+        assert_eq!(fees, vec![200_000]);
+    }
+
+    /// A tx spending the given inputs, paying the fee constant, with a single output.
+    fn spending(
+        inputs: impl IntoIterator<Item = Input>,
+        fee: u64,
+    ) -> Transaction<state::ReadyForSigning> {
+        let mut tx = Transaction::default();
+        tx.with_inputs(inputs.into_iter().map(|input| (input, None)))
+            .with_outputs([Output::new(
+                test_host_address().into(),
+                Value::new(2_000_000),
+            )])
+            .with_fee(fee);
+        Transaction::from_cbor(&tx.to_cbor()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn rejection_on_pending_outputs_drops_the_txs_producing_them() {
+        let connector = Arc::new(FakeConnector::new(
+            Ok::<_, &str>(ProtocolParameters::default()),
+            Ok::<_, &str>(host_utxos_with_reference_script()),
+        ));
+        let service = running_service(connector.clone()).await;
+        let fee = ProtocolParameters::default().base_fee(0);
+        let parent = spending([Input::new(Hash::<32>::from([1; 32]), 0)], fee);
+        let child = spending([Input::new(parent.id(), 0)], fee);
+        let sibling = spending([Input::new(Hash::<32>::from([2; 32]), 0)], fee);
+        service
+            .submit_with(|_| Ok(vec![parent.clone(), child.clone(), sibling.clone()]))
+            .await
+            .unwrap();
+
+        // The parent never made it: the ledger knows none of its outputs, nor the child's.
+        *connector.failures.lock().unwrap() =
+            vec!["ApplyTxError [BadInputsUTxO (fromList [...])]".to_string()];
+        let respending = spending([Input::new(parent.id(), 0)], fee);
+        assert!(
+            service
+                .submit_with(|_| Ok(vec![respending.clone()]))
+                .await
+                .is_err()
+        );
+        let metrics = service.metrics();
+        assert_eq!((metrics.dropped, metrics.unconfirmed), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn submission_failing_on_the_way_to_the_ledger_is_retried() {
+        let connector = Arc::new(FakeConnector::new(
+            Ok::<_, &str>(ProtocolParameters::default()),
+            Ok::<_, &str>(host_utxos_with_reference_script()),
+        ));
+        let service = running_service(connector.clone()).await;
+        *connector.failures.lock().unwrap() = vec![
+            "status: Unavailable, message: \"transport error\"".to_string(),
+            "ApplyTxError [OutsideValidityIntervalUTxO (ValidityInterval ...) (SlotNo 42)]"
+                .to_string(),
+        ];
+        let build = |onchain: &crate::admin::Onchain| {
+            let mut tx = Transaction::default();
+            tx.with_inputs([(Input::new(Hash::<32>::from([1; 32]), 0), None)])
+                .with_fee(onchain.network_parameters.protocol_parameters.base_fee(0));
+            Ok(vec![Transaction::from_cbor(&tx.to_cbor())?])
+        };
+
+        // Retried once, then rejected by the ledger: not retried.
+        assert!(service.submit_with(build).await.is_err());
+        let metrics = service.metrics();
+        assert_eq!(metrics.retried, 1);
+        assert_eq!(
+            metrics.rejected.into_iter().collect::<Vec<_>>(),
+            vec![(Rejection::OutsideValidity, 1), (Rejection::Transient, 1)]
+        );
+
+        service.submit_with(build).await.unwrap();
+        let metrics = service.metrics();
+        assert_eq!((metrics.submitted, metrics.unconfirmed), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn sync_rebuilds_without_the_pending_txs_the_ledger_lost() {
+        let connector = Arc::new(FakeConnector::new(
+            Ok::<_, &str>(ProtocolParameters::preview()),
+            Ok::<_, &str>(host_utxos_with_reference_script()),
+        ));
+        let wallet = SigningKey::from([7; 32]);
+        let consumer = SigningKey::from([8; 32]);
+        let constants = Constants {
+            tag: Tag::from(vec![1]),
+            add_vkey: consumer.to_verification_key(),
+            sub_vkey: wallet.to_verification_key(),
+            close_period: Duration::from_secs(3600),
+        };
+        let keytag = Keytag::new(constants.add_vkey, constants.tag.clone());
+        let channel = Input::new(Hash::<32>::from([0x11; 32]), 0);
+        let wallet_address = wallet.to_verification_key().to_address(NetworkId::TESTNET);
+        *connector.chain.lock().unwrap() = Some(BTreeMap::from([
+            (
+                channel.clone(),
+                Open::new(10_000_000, constants.clone(), None).output(NetworkId::TESTNET),
+            ),
+            (
+                Input::new(Hash::<32>::from([0xff; 32]), 0),
+                Output::new(wallet_address.clone().into(), Value::new(5_000_000)),
+            ),
+            (
+                Input::new(Hash::<32>::from([0xff; 32]), 1),
+                Output::new(wallet_address.into(), Value::new(100_000_000)),
+            ),
+        ]));
+        let db = Arc::new(FakeDb::default());
+        let squash = |amount| {
+            let mut off_chain = Channel::new(keytag.clone());
+            off_chain
+                .update_squash(Squash::make(
+                    &consumer,
+                    &constants.tag,
+                    SquashBody::new_no_verify(amount, 0, Indexes::default()),
+                ))
+                .unwrap();
+            db.channels
+                .lock()
+                .unwrap()
+                .insert(keytag.clone(), off_chain);
+        };
+        let service = Service::new(
+            test_config(),
+            Arc::new(bln_client::mock::Client::new()),
+            connector.clone(),
+            db.clone(),
+        )
+        .await
+        .unwrap();
+
+        squash(3_000_000);
+        service.sync().await.unwrap();
+        assert_eq!(service.metrics().unconfirmed, 1);
+
+        // The sub falls out of the mempool, unbeknownst to us; a larger squash comes in. The next
+        // sub, built on the lost one, is rejected: the lost one is dropped, and the sub rebuilt
+        // against the chain.
+        connector.submitted.lock().unwrap().clear();
+        squash(5_000_000);
+        service.sync().await.unwrap();
+
+        let metrics = service.metrics();
+        assert_eq!(metrics.rebuilt, 1);
+        assert_eq!(metrics.dropped, 1);
+        assert_eq!(
+            metrics.rejected.into_iter().collect::<Vec<_>>(),
+            vec![(Rejection::InputsSpent, 1)]
+        );
+        let submitted = connector.submitted.lock().unwrap();
+        assert_eq!(submitted.len(), 1);
+        assert!(submitted[0].inputs().any(|input| input == channel));
+    }
 }
@@ -0,0 +1,294 @@
+use cardano_sdk::{Hash, Transaction, transaction::state};
+use konduit_tx::{Unconfirmed, Utxos, unconfirmed::Reconciled};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, Instant},
+};
+
+/// How long a tx may stay unconfirmed. Admin txs are valid for twenty minutes at most: past that,
+/// one that has not made it on-chain never will.
+pub const UNCONFIRMED_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// How long a tx is followed once on-chain, should its depth never be known.
+pub const CONFIRMING_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Why a submission failed, as told by the ledger's predicate failures or the way to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rejection {
+    /// An input is spent already, or was never produced: the chain view the tx was built against
+    /// is stale.
+    InputsSpent,
+    /// The tip is outside the tx's validity interval.
+    OutsideValidity,
+    /// The fee falls short, most likely of changed protocol parameters.
+    FeeTooLow,
+    /// A script failed, or its budget, integrity hash or witnesses are off.
+    ScriptFailure,
+    /// The backend could not be reached or failed on its own: the ledger said nothing.
+    Transient,
+    /// Anything else.
+    Other,
+}
+
+/// Ledger failures, and failures on the way to it, for each rejection; first match wins, as a
+/// ledger error may carry several failures, some mere consequences of others.
+const FAILURES: [(Rejection, &[&str]); 5] = [
+    (
+        Rejection::InputsSpent,
+        &["BadInputsUTxO", "UnknownInputs", "already been spent"],
+    ),
+    (
+        Rejection::OutsideValidity,
+        &["OutsideValidityIntervalUTxO", "OutsideValidityInterval"],
+    ),
+    (Rejection::FeeTooLow, &["FeeTooSmallUTxO"]),
+    (
+        Rejection::ScriptFailure,
+        &[
+            "ValidationTagMismatch",
+            "PlutusFailure",
+            "CollectErrors",
+            "MissingScriptWitnessesUTXOW",
+            "MissingRedeemers",
+            "ExtraRedeemers",
+            "PPViewHashesDontMatch",
+            "ScriptIntegrityHashMismatch",
+            "ExUnitsTooBigUTxO",
+        ],
+    ),
+    (
+        Rejection::Transient,
+        &[
+            "timed out",
+            "timeout",
+            "connection",
+            "transport error",
+            "unavailable",
+            "too many requests",
+            "502",
+            "503",
+            "504",
+        ],
+    ),
+];
+
+impl Rejection {
+    pub fn classify(error: &anyhow::Error) -> Self {
+        let message = format!("{error:#}");
+        let lowercase = message.to_lowercase();
+        FAILURES
+            .iter()
+            .find(|(rejection, failures)| {
+                failures.iter().any(|failure| match rejection {
+                    Rejection::Transient => lowercase.contains(failure),
+                    _ => message.contains(failure),
+                })
+            })
+            .map(|(rejection, _)| *rejection)
+            .unwrap_or(Rejection::Other)
+    }
+
+    /// Whether the same tx may go through on a second attempt.
+    pub fn is_retriable(&self) -> bool {
+        matches!(self, Rejection::Transient)
+    }
+
+    /// Whether a tx built anew, against a fresh chain view and bounds, may go through.
+    pub fn is_rebuildable(&self) -> bool {
+        matches!(self, Rejection::InputsSpent | Rejection::OutsideValidity)
+    }
+}
+
+/// Counts of submission outcomes since startup.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Metrics {
+    /// Txs accepted by the backend.
+    pub submitted: u64,
+    /// Submissions attempted again, the same tx.
+    pub retried: u64,
+    /// Txs built anew, after a rejection.
+    pub rebuilt: u64,
+    /// Submissions failed, by rejection.
+    pub rejected: BTreeMap<Rejection, u64>,
+    /// Txs seen on-chain.
+    pub included: u64,
+    /// Txs at the confirmation depth.
+    pub confirmed: u64,
+    /// Txs seen on-chain, then gone.
+    pub rolled_back: u64,
+    /// Txs that never made it on-chain, or were built on one that did not.
+    pub dropped: u64,
+    /// Txs awaiting inclusion, currently.
+    pub unconfirmed: usize,
+    /// Txs on-chain awaiting the confirmation depth, currently.
+    pub confirming: usize,
+}
+
+/// Our submitted txs, from submission to confirmation depth: unconfirmed, they are built upon
+/// (see `Unconfirmed`); then on-chain, they are followed until deep enough.
+#[derive(Debug, Default)]
+pub struct Submissions {
+    unconfirmed: Unconfirmed,
+    /// On-chain, but not as deep as required, along with when first seen.
+    confirming: BTreeMap<Hash<32>, Instant>,
+    metrics: Metrics,
+}
+
+impl Submissions {
+    pub fn submitted(&mut self, transaction: &Transaction<state::ReadyForSigning>) {
+        log::info!("submitted tx {}", transaction.id());
+        self.unconfirmed.record(transaction);
+        self.metrics.submitted += 1;
+    }
+
+    pub fn rejected(&mut self, rejection: Rejection) {
+        *self.metrics.rejected.entry(rejection).or_default() += 1;
+    }
+
+    pub fn retried(&mut self) {
+        self.metrics.retried += 1;
+    }
+
+    pub fn rebuilt(&mut self) {
+        self.metrics.rebuilt += 1;
+    }
+
+    /// Catch up with a chain view, covering both channels and wallet.
+    pub fn reconcile(&mut self, view: &Utxos) {
+        let Reconciled { confirmed, dropped } = self.unconfirmed.reconcile(view, UNCONFIRMED_TTL);
+        for id in confirmed {
+            log::info!("tx {id} on-chain");
+            self.confirming.insert(id, Instant::now());
+            self.metrics.included += 1;
+        }
+        for id in dropped {
+            log::warn!(
+                "dropping tx {id}: rejected, rolled back, expired, or built on one that was"
+            );
+            self.metrics.dropped += 1;
+        }
+    }
+
+    /// Drop the unconfirmed txs whose outputs a tx rejected on its inputs spends, along with those
+    /// built on them: had they made it to the ledger, or its mempool, those inputs would be known.
+    pub fn drop_parents(&mut self, transaction: &Transaction<state::ReadyForSigning>) {
+        let parents = transaction
+            .inputs()
+            .map(|input| input.transaction_id())
+            .filter(|id| self.unconfirmed.contains(id))
+            .collect::<BTreeSet<_>>();
+        for parent in parents {
+            for id in self.unconfirmed.drop_with_descendants(&parent) {
+                log::warn!(
+                    "dropping tx {id}: built upon by tx {}, rejected on its inputs",
+                    transaction.id()
+                );
+                self.metrics.dropped += 1;
+            }
+        }
+    }
+
+    /// A chain view, as it will be once our unconfirmed txs are.
+    pub fn pending(&self, view: &Utxos) -> Utxos {
+        self.unconfirmed.apply(view)
+    }
+
+    /// Txs on-chain, but not yet as deep as required.
+    pub fn confirming(&self) -> Vec<Hash<32>> {
+        self.confirming.keys().copied().collect()
+    }
+
+    /// Follow up on a tx on-chain, given its depth, or the failure to get it. A tx rolled back
+    /// needs nothing more: the chain view has its inputs back, and the next sync builds anew.
+    pub fn settle(&mut self, id: &Hash<32>, depth: anyhow::Result<Option<u64>>, required: u64) {
+        let Some(since) = self.confirming.get(id) else {
+            return;
+        };
+        match depth {
+            Ok(Some(depth)) if depth >= required => {
+                log::info!("tx {id} confirmed, {depth} blocks deep");
+                self.confirming.remove(id);
+                self.metrics.confirmed += 1;
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                log::warn!("tx {id} rolled back");
+                self.confirming.remove(id);
+                self.metrics.rolled_back += 1;
+            }
+            Err(error) if since.elapsed() >= CONFIRMING_TTL => {
+                log::warn!("giving up on the depth of tx {id}: {error:#}");
+                self.confirming.remove(id);
+            }
+            Err(error) => log::warn!("failed to get the depth of tx {id}: {error:#}"),
+        }
+    }
+
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            unconfirmed: self.unconfirmed.len(),
+            confirming: self.confirming.len(),
+            ..self.metrics.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_rejections() {
+        let classify = |message: &str| Rejection::classify(&anyhow::anyhow!(message.to_string()));
+        assert_eq!(
+            classify("ApplyTxError [BadInputsUTxO (fromList [...]), ValueNotConservedUTxO (...)]"),
+            Rejection::InputsSpent
+        );
+        assert_eq!(
+            classify(
+                "ApplyTxError [OutsideValidityIntervalUTxO (ValidityInterval ...) (SlotNo 42)]"
+            ),
+            Rejection::OutsideValidity
+        );
+        assert_eq!(
+            classify("ApplyTxError [FeeTooSmallUTxO (Coin 180000) (Coin 170000)]"),
+            Rejection::FeeTooLow
+        );
+        assert_eq!(
+            classify("ApplyTxError [ValidationTagMismatch (IsValid True) ...]"),
+            Rejection::ScriptFailure
+        );
+        assert_eq!(
+            classify("status: Unavailable, message: \"transport error\""),
+            Rejection::Transient
+        );
+        assert_eq!(classify("MaxTxSizeUTxO 20000 16384"), Rejection::Other);
+        assert!(
+            Rejection::classify(
+                &anyhow::anyhow!("Connection refused").context("failed to submit to dolos")
+            )
+            .is_retriable()
+        );
+    }
+
+    #[test]
+    fn follows_txs_to_confirmation_depth() {
+        let id = Hash::<32>::from([1; 32]);
+        let mut submissions = Submissions::default();
+        submissions.confirming.insert(id, Instant::now());
+
+        submissions.settle(&id, Ok(Some(1)), 3);
+        submissions.settle(&id, Err(anyhow::anyhow!("unreachable")), 3);
+        assert_eq!(submissions.confirming(), vec![id]);
+
+        submissions.settle(&id, Ok(Some(3)), 3);
+        assert!(submissions.confirming().is_empty());
+        assert_eq!(submissions.metrics().confirmed, 1);
+
+        submissions.confirming.insert(id, Instant::now());
+        submissions.settle(&id, Ok(None), 3);
+        assert_eq!(submissions.metrics().rolled_back, 1);
+    }
+}
@@ -3,12 +3,12 @@ pub use args::CardanoArgs as Args;
 
 use cardano_connector::CardanoConnector;
 use cardano_sdk::{
-    Credential, Input, Network, Output, ProtocolParameters, Transaction, transaction::state,
+    Credential, Hash, Input, Network, Output, ProtocolParameters, Transaction, transaction::state,
 };
 use std::collections::BTreeMap;
 
 pub enum Cardano {
-    Blockfrost(cardano_connector_direct::Blockfrost),
+    Blockfrost(Box<cardano_connector_direct::Blockfrost>),
     UtxoRpc(Box<cardano_connector_utxorpc::UtxoRpc>),
 }
 
@@ -54,4 +54,11 @@ impl CardanoConnector for Cardano {
             Self::UtxoRpc(connector) => connector.submit(transaction).await,
         }
     }
+
+    async fn confirmations(&self, id: &Hash<32>) -> anyhow::Result<Option<u64>> {
+        match self {
+            Self::Blockfrost(connector) => connector.confirmations(id).await,
+            Self::UtxoRpc(connector) => connector.confirmations(id).await,
+        }
+    }
 }
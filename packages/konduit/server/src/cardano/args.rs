@@ -40,6 +40,14 @@ impl CardanoArgs {
     pub async fn build(&self) -> anyhow::Result<super::Cardano> {
         match self.backend {
             CardanoBackend::Blockfrost => {
+                if let Some(network) = self.network.filter(Network::is_custom) {
+                    return Err(anyhow!(
+                        "Cardano backend {} does not serve custom network {network}; use {}",
+                        CardanoBackend::Blockfrost.as_str(),
+                        CardanoBackend::Utxorpc.as_str()
+                    ));
+                }
+
                 let project_id = self.blockfrost_project_id()?;
                 let client = Blockfrost::new(project_id.to_owned());
                 client.health().await.with_context(|| {
@@ -49,7 +57,7 @@ impl CardanoArgs {
                     )
                 })?;
 
-                Ok(super::Cardano::Blockfrost(client))
+                Ok(super::Cardano::Blockfrost(Box::new(client)))
             }
             CardanoBackend::Utxorpc => {
                 let config = self.utxorpc_config()?;
@@ -208,6 +216,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn utxorpc_config_accepts_custom_network() {
+        #[derive(clap::Parser)]
+        struct Cli {
+            #[command(flatten)]
+            cardano: CardanoArgs,
+        }
+
+        let args = <Cli as clap::Parser>::try_parse_from([
+            "konduit-server",
+            "--backend",
+            "utxorpc",
+            "--utxorpc-uri",
+            "http://127.0.0.1:1337",
+            "--network",
+            "custom:42:1700000000:200",
+        ])
+        .expect("custom network should parse")
+        .cardano;
+
+        assert_eq!(
+            args.utxorpc_config()
+                .expect("complete UTxO RPC config should build")
+                .network(),
+            Network::Custom {
+                magic: 42,
+                system_start: 1_700_000_000,
+                slot_length: 200,
+            }
+        );
+    }
+
     #[test]
     fn blockfrost_project_id_rejects_whitespace_only_values() {
         let args = CardanoArgs {
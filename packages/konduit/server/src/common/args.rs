@@ -2,14 +2,24 @@ use crate::{common::metavar, env};
 use cardano_sdk::{Address, SigningKey, VerificationKey, address::kind};
 use konduit_data::{AdaptorInfo, ChannelParameters, Duration, TosInfo, TxHelp};
 use konduit_tx::KONDUIT_VALIDATOR;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, clap::Args)]
 pub struct CommonArgs {
     /// Adaptors signing key.
     /// Used for both channel params (verification key of)
-    /// and the wallet to administrate channels
-    #[arg(long, env = env::SIGNING_KEY, hide_env_values = true)]
-    pub signing_key: SigningKey,
+    /// and the wallet to administrate channels.
+    /// Exactly one of the signing key, keystore or remote signer is expected.
+    #[arg(long, env = env::SIGNING_KEY, hide_env_values = true, conflicts_with_all = ["keystore", "remote_signer"])]
+    pub signing_key: Option<SigningKey>,
+    /// Encrypted keystore holding the adaptor's signing key, as written by `konduit-signer encrypt`.
+    #[arg(long, env = env::KEYSTORE, requires = "keystore_password", conflicts_with = "remote_signer")]
+    pub keystore: Option<PathBuf>,
+    #[arg(long, env = env::KEYSTORE_PASSWORD, hide_env_values = true)]
+    pub keystore_password: Option<String>,
+    /// Unix socket of a `konduit-signer serve` daemon holding the adaptor's signing key.
+    #[arg(long, env = env::REMOTE_SIGNER)]
+    pub remote_signer: Option<PathBuf>,
     /// (Min) close period of channels.
     #[arg(long, env = env::CLOSE_PERIOD, value_name=metavar::DURATION, default_value="24h")]
     pub close_period: Duration,
@@ -24,32 +34,45 @@ pub struct CommonArgs {
     pub fee: u64,
 }
 
-impl From<CommonArgs> for ChannelParameters {
-    fn from(args: CommonArgs) -> Self {
-        let adaptor_key = VerificationKey::from(&args.signing_key);
-        Self {
+impl CommonArgs {
+    pub fn signer(&self) -> anyhow::Result<konduit_signer::Config> {
+        match (&self.signing_key, &self.keystore, &self.remote_signer) {
+            (Some(signing_key), None, None) => {
+                Ok(konduit_signer::Config::SigningKey(signing_key.clone()))
+            }
+            (None, Some(path), None) => Ok(konduit_signer::Config::Keystore {
+                path: path.clone(),
+                password: self.keystore_password.clone().unwrap_or_default(),
+            }),
+            (None, None, Some(socket)) => Ok(konduit_signer::Config::Remote {
+                socket: socket.clone(),
+            }),
+            _ => Err(anyhow::anyhow!(
+                "expected exactly one of a signing key, a keystore or a remote signer"
+            )),
+        }
+    }
+
+    pub fn channel_parameters(&self, adaptor_key: VerificationKey) -> ChannelParameters {
+        ChannelParameters {
             adaptor_key,
-            close_period: args.close_period,
-            tag_length: args.tag_length,
+            close_period: self.close_period,
+            tag_length: self.tag_length,
         }
     }
-}
 
-impl From<CommonArgs> for AdaptorInfo<TxHelp> {
-    fn from(args: CommonArgs) -> Self {
-        let tos = TosInfo { flat_fee: args.fee };
+    pub fn adaptor_info(&self, adaptor_key: VerificationKey) -> AdaptorInfo<TxHelp> {
+        let tos = TosInfo { flat_fee: self.fee };
 
         let tx_help = TxHelp {
-            host_address: args.host_address.clone(),
+            host_address: self.host_address.clone(),
             validator: KONDUIT_VALIDATOR.hash,
         };
 
-        let channel_parameters = args.into();
-
-        Self {
+        AdaptorInfo {
             tos,
             tx_help,
-            channel_parameters,
+            channel_parameters: self.channel_parameters(adaptor_key),
         }
     }
 }
@@ -2,6 +2,9 @@
 
 /// # Adaptor wallet
 pub const SIGNING_KEY: &str = "KONDUIT_SIGNING_KEY";
+pub const KEYSTORE: &str = "KONDUIT_KEYSTORE";
+pub const KEYSTORE_PASSWORD: &str = "KONDUIT_KEYSTORE_PASSWORD";
+pub const REMOTE_SIGNER: &str = "KONDUIT_REMOTE_SIGNER";
 
 /// # Cardano connect
 pub const CARDANO_BACKEND: &str = "KONDUIT_CARDANO_BACKEND";
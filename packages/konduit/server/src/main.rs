@@ -1,5 +1,4 @@
 use clap::Parser;
-use konduit_data::AdaptorInfo;
 use konduit_server::{admin, args, server};
 use konduit_tx::InsufficientTotalGain;
 use std::sync::Arc;
@@ -53,9 +52,13 @@ async fn main() -> anyhow::Result<()> {
         .map_err(|s| anyhow::anyhow!(s))?
         .build()?;
 
+    // SIGNER
+    let signer = args.common.signer()?.build().await?;
+    let adaptor_key = signer.verification_key();
+
     // ADMIN
     let admin_every = args.admin.admin_every;
-    let admin_config = admin::Config::from_args(args.common.clone(), args.admin);
+    let admin_config = admin::Config::from_args(&args.common, args.admin, signer);
     let admin = Arc::new(
         admin::Service::new(admin_config, bln.clone(), cardano.clone(), db.clone()).await?,
     );
@@ -82,7 +85,7 @@ async fn main() -> anyhow::Result<()> {
     });
 
     // INFO
-    let info = Arc::new(AdaptorInfo::from(args.common));
+    let info = Arc::new(args.common.adaptor_info(adaptor_key));
     let server_data = server::Data::new(bln, db, fx_state, info, admin);
     let server = server::Service::new(args.server, server_data);
 
@@ -62,6 +62,12 @@ pub async fn fx(data: Data) -> HttpResponse {
     HttpResponse::Ok().json(fx)
 }
 
+/// Counts of admin submission outcomes since startup: submitted, retried, rebuilt, rejected by
+/// category, on-chain, confirmed, rolled back and dropped.
+pub async fn metrics(data: Data) -> HttpResponse {
+    HttpResponse::Ok().json(data.admin().metrics())
+}
+
 pub async fn show(data: Data) -> Result<HttpResponse, HandlerError> {
     log::info!("SHOW");
     let results = data.db().get_all().await?;
@@ -45,7 +45,9 @@ impl Service {
                 .service(web::scope("/opt").route("/fx", web::get().to(handlers::fx)))
                 .service(
                     // THIS SHOULD BE EXPOSED ONLY TO TRUSTED SOURCES.
-                    web::scope("/admin").route("/show", web::get().to(handlers::show)),
+                    web::scope("/admin")
+                        .route("/show", web::get().to(handlers::show))
+                        .route("/metrics", web::get().to(handlers::metrics)),
                 )
         })
         .bind(self.bind_address)?
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use cardano_sdk::{Signature, SigningKey, Transaction, VerificationKey, transaction::state};
+
+/// Something able to sign transactions on behalf of a single key.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// The key whose signatures the signer produces.
+    fn verification_key(&self) -> VerificationKey;
+
+    /// Sign the transaction, or refuse to.
+    async fn sign(
+        &self,
+        transaction: &Transaction<state::ReadyForSigning>,
+    ) -> anyhow::Result<Signature>;
+
+    /// Sign the transaction, and add the resulting witness to it.
+    async fn witness(
+        &self,
+        transaction: &mut Transaction<state::ReadyForSigning>,
+    ) -> anyhow::Result<()> {
+        let signature = self.sign(transaction).await?;
+        transaction.add_witness(self.verification_key(), signature);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Signer for SigningKey {
+    fn verification_key(&self) -> VerificationKey {
+        self.to_verification_key()
+    }
+
+    async fn sign(
+        &self,
+        transaction: &Transaction<state::ReadyForSigning>,
+    ) -> anyhow::Result<Signature> {
+        Ok(SigningKey::sign(self, transaction.id()))
+    }
+}
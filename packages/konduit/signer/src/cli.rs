@@ -0,0 +1,6 @@
+mod args;
+pub use args::SignerArgs as Args;
+pub use args::{CardanoBackend, Command, EncryptArgs, ServeArgs};
+
+mod run;
+pub use run::run;
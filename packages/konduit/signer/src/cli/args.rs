@@ -0,0 +1,73 @@
+use crate::policy;
+use cardano_sdk::{Network, SigningKey};
+use std::path::PathBuf;
+
+/// Holds the adaptor's signing key, encrypted at rest, and signs its transactions for it.
+#[derive(Debug, clap::Parser)]
+#[command(author, version, about, long_about = None)]
+pub struct SignerArgs {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Encrypt a signing key into a new keystore.
+    Encrypt(EncryptArgs),
+    /// Serve signatures over a Unix socket, for the transactions the policy allows.
+    Serve(ServeArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct EncryptArgs {
+    /// The signing key to encrypt.
+    #[arg(long, env = "KONDUIT_SIGNING_KEY", hide_env_values = true)]
+    pub signing_key: SigningKey,
+    #[arg(long, env = "KONDUIT_KEYSTORE_PASSWORD", hide_env_values = true)]
+    pub password: String,
+    /// Where to write the keystore. Must not exist yet.
+    #[arg(long, env = "KONDUIT_KEYSTORE")]
+    pub keystore: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ServeArgs {
+    #[arg(long, env = "KONDUIT_KEYSTORE")]
+    pub keystore: PathBuf,
+    #[arg(long, env = "KONDUIT_KEYSTORE_PASSWORD", hide_env_values = true)]
+    pub password: String,
+    /// Path of the Unix socket to listen on. Replaced if it exists.
+    #[arg(long, env = "KONDUIT_REMOTE_SIGNER")]
+    pub socket: PathBuf,
+    /// The network of the transactions to sign.
+    #[arg(long, env = "KONDUIT_NETWORK")]
+    pub network: Network,
+    /// Where the UTxOs spent by the transactions to sign are looked up.
+    #[arg(long, env = "KONDUIT_CARDANO_BACKEND", default_value_t = CardanoBackend::Blockfrost)]
+    pub backend: CardanoBackend,
+    #[arg(long, env = "KONDUIT_BLOCKFROST_PROJECT_ID", hide_env_values = true)]
+    pub blockfrost_project_id: Option<String>,
+    #[arg(long, env = "KONDUIT_UTXORPC_URI")]
+    pub utxorpc_uri: Option<String>,
+    /// Fees above this many lovelace are refused.
+    #[arg(long, env = "KONDUIT_MAX_FEE", default_value_t = policy::MAX_FEE)]
+    pub max_fee: u64,
+    /// Collateral forfeited above this many lovelace, should a script fail, is refused.
+    #[arg(long, env = "KONDUIT_MAX_COLLATERAL", default_value_t = policy::MAX_COLLATERAL)]
+    pub max_collateral: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CardanoBackend {
+    Blockfrost,
+    Utxorpc,
+}
+
+impl std::fmt::Display for CardanoBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Blockfrost => f.write_str("blockfrost"),
+            Self::Utxorpc => f.write_str("utxorpc"),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use super::{CardanoBackend, Command, EncryptArgs, ServeArgs};
+use crate::{daemon::Daemon, keystore::Keystore, policy::Policy};
+use anyhow::{Context, anyhow};
+use cardano_connector::CardanoConnector;
+use cardano_connector_direct::Blockfrost;
+use cardano_connector_utxorpc::{Config, UtxoRpc, ensure_network_matches, live_network};
+use cardano_sdk::NetworkId;
+use std::{fs, os::unix::fs::PermissionsExt};
+use tokio::net::UnixListener;
+
+pub async fn run(command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Encrypt(args) => encrypt(args),
+        Command::Serve(args) => serve(args).await,
+    }
+}
+
+fn encrypt(args: EncryptArgs) -> anyhow::Result<()> {
+    let keystore = Keystore::encrypt(&args.signing_key, &args.password);
+    keystore.save(&args.keystore)?;
+    println!("{}", keystore.verification_key()?);
+    Ok(())
+}
+
+async fn serve(args: ServeArgs) -> anyhow::Result<()> {
+    let signing_key = Keystore::load(&args.keystore)?.decrypt(&args.password)?;
+    let policy = Policy::new(
+        NetworkId::from(args.network),
+        &signing_key.to_verification_key(),
+    )
+    .with_ceilings(args.max_fee, args.max_collateral);
+    if args.socket.exists() {
+        fs::remove_file(&args.socket)?;
+    }
+    let listener = UnixListener::bind(&args.socket)?;
+    // Only the owner may ask for signatures.
+    fs::set_permissions(&args.socket, fs::Permissions::from_mode(0o600))?;
+    log::info!(
+        "signing for {} on {}",
+        signing_key.to_verification_key(),
+        args.socket.display()
+    );
+    match args.backend {
+        CardanoBackend::Blockfrost => {
+            let project_id = args.blockfrost_project_id.ok_or_else(|| {
+                anyhow!("backend blockfrost requires KONDUIT_BLOCKFROST_PROJECT_ID")
+            })?;
+            let connector = Blockfrost::new(project_id);
+            connector
+                .health()
+                .await
+                .context("failed to reach backend blockfrost")?;
+            Daemon::new(signing_key, policy, connector)
+                .serve(listener)
+                .await
+        }
+        CardanoBackend::Utxorpc => {
+            let endpoint = args
+                .utxorpc_uri
+                .ok_or_else(|| anyhow!("backend utxorpc requires KONDUIT_UTXORPC_URI"))?;
+            let connector = UtxoRpc::connect(Config::new(endpoint.clone(), args.network))
+                .await
+                .with_context(|| format!("failed to initialize backend utxorpc at {endpoint}"))?;
+            let live = live_network(&endpoint).await?;
+            ensure_network_matches(args.network, live, &endpoint)?;
+            Daemon::new(signing_key, policy, connector)
+                .serve(listener)
+                .await
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{Signer, keystore::Keystore, remote::RemoteSigner};
+use cardano_sdk::SigningKey;
+use std::{path::PathBuf, sync::Arc};
+
+/// Where the signing key lives.
+#[derive(Debug, Clone)]
+pub enum Config {
+    /// In memory, as given.
+    SigningKey(SigningKey),
+    /// In an encrypted keystore on disk; decrypted into memory.
+    Keystore { path: PathBuf, password: String },
+    /// With a remote signer daemon listening on a Unix socket.
+    Remote { socket: PathBuf },
+}
+
+impl Config {
+    pub async fn build(self) -> anyhow::Result<Arc<dyn Signer>> {
+        match self {
+            Config::SigningKey(signing_key) => Ok(Arc::new(signing_key)),
+            Config::Keystore { path, password } => {
+                Ok(Arc::new(Keystore::load(&path)?.decrypt(&password)?))
+            }
+            Config::Remote { socket } => Ok(Arc::new(RemoteSigner::connect(socket).await?)),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! A remote signer: holds the adaptor's key, and signs the transactions its policy allows for
+//! whoever connects to its Unix socket. Access to the socket is left to file permissions; the
+//! UTxOs a transaction spends are resolved against the chain, through the given connector.
+
+use crate::{
+    policy::Policy,
+    protocol::{self, Request, Response},
+};
+use cardano_connector::CardanoConnector;
+use cardano_sdk::{SigningKey, Transaction, cbor::FromCbor, transaction::state};
+use std::rc::Rc;
+use tokio::{
+    io::BufReader,
+    net::{UnixListener, UnixStream},
+    task::LocalSet,
+};
+
+pub struct Daemon<Connector> {
+    signing_key: SigningKey,
+    policy: Policy,
+    connector: Connector,
+}
+
+impl<Connector: CardanoConnector + 'static> Daemon<Connector> {
+    pub fn new(signing_key: SigningKey, policy: Policy, connector: Connector) -> Self {
+        Self {
+            signing_key,
+            policy,
+            connector,
+        }
+    }
+
+    /// Serve connections until the listener fails. Connections are served on the current thread,
+    /// as connectors need not be sendable across.
+    pub async fn serve(self, listener: UnixListener) -> anyhow::Result<()> {
+        let daemon = Rc::new(self);
+        let connections = LocalSet::new();
+        connections
+            .run_until(async move {
+                loop {
+                    let (stream, _) = listener.accept().await?;
+                    let daemon = daemon.clone();
+                    tokio::task::spawn_local(async move {
+                        if let Err(error) = daemon.connection(stream).await {
+                            log::warn!("signer connection failed: {error:#}");
+                        }
+                    });
+                }
+            })
+            .await
+    }
+
+    async fn connection(&self, stream: UnixStream) -> anyhow::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        while let Some(request) = protocol::read::<Request>(&mut reader).await? {
+            protocol::write(&mut writer, &self.handle(request).await).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn handle(&self, request: Request) -> Response {
+        match request {
+            Request::VerificationKey => Response::VerificationKey {
+                verification_key: self.signing_key.to_verification_key().to_string(),
+            },
+            Request::Sign { transaction } => match self.sign(&transaction).await {
+                Ok(signature) => Response::Signature { signature },
+                Err(error) => {
+                    log::warn!("refused to sign: {error:#}");
+                    Response::Error {
+                        message: format!("{error:#}"),
+                    }
+                }
+            },
+        }
+    }
+
+    async fn sign(&self, transaction: &str) -> anyhow::Result<String> {
+        let bytes = hex::decode(transaction)?;
+        let transaction = Transaction::<state::ReadyForSigning>::from_cbor(&bytes)?;
+        let resolved = self.policy.resolve(&self.connector, &transaction).await?;
+        self.policy.check(&transaction, &resolved)?;
+        log::info!("signing transaction {}", transaction.id());
+        Ok(self.signing_key.sign(transaction.id()).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Signer, remote::RemoteSigner};
+    use cardano_sdk::{
+        Credential, Hash, Input, Network, NetworkId, Output, ProtocolParameters, Value,
+        cbor::ToCbor,
+    };
+    use konduit_tx::Utxos;
+    use std::collections::BTreeMap;
+
+    /// A chain holding the given UTxOs.
+    struct Chain(Utxos);
+
+    impl CardanoConnector for Chain {
+        fn network(&self) -> Network {
+            Network::Preview
+        }
+
+        async fn health(&self) -> anyhow::Result<String> {
+            Ok("ok".to_string())
+        }
+
+        async fn protocol_parameters(&self) -> anyhow::Result<ProtocolParameters> {
+            Ok(ProtocolParameters::preview())
+        }
+
+        async fn utxos_at(
+            &self,
+            payment: &Credential,
+            _delegation: Option<&Credential>,
+        ) -> anyhow::Result<BTreeMap<Input, Output>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|(_, output)| {
+                    output
+                        .address()
+                        .as_shelley()
+                        .is_some_and(|address| address.payment() == *payment)
+                })
+                .map(|(input, output)| (input.clone(), output.clone()))
+                .collect())
+        }
+
+        async fn submit(
+            &self,
+            _transaction: &Transaction<state::ReadyForSigning>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn output(to: &SigningKey) -> Output {
+        Output::new(
+            to.to_verification_key()
+                .to_address(NetworkId::TESTNET)
+                .into(),
+            Value::new(2_000_000),
+        )
+    }
+
+    fn transaction(input: Input, to: &SigningKey) -> Transaction<state::ReadyForSigning> {
+        let mut tx = Transaction::default();
+        tx.with_inputs([(input, None)]).with_outputs([output(to)]);
+        Transaction::from_cbor(&tx.to_cbor()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn signs_over_a_socket_what_the_policy_allows() {
+        let dir = std::env::temp_dir().join(format!("konduit-signer-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket = dir.join("signer.sock");
+        let _ = std::fs::remove_file(&socket);
+
+        let signing_key = SigningKey::from([5; 32]);
+        let policy = Policy::new(NetworkId::TESTNET, &signing_key.to_verification_key());
+        let own = Input::new(Hash::from([1; 32]), 0);
+        let chain = Chain(Utxos::from([(own.clone(), output(&signing_key))]));
+        let listener = UnixListener::bind(&socket).unwrap();
+        let daemon = LocalSet::new();
+        daemon.spawn_local(Daemon::new(signing_key.clone(), policy, chain).serve(listener));
+
+        daemon
+            .run_until(async {
+                let signer = RemoteSigner::connect(socket.clone()).await.unwrap();
+                assert_eq!(signer.verification_key(), signing_key.to_verification_key());
+
+                let mut tx = transaction(own.clone(), &signing_key);
+                signer.witness(&mut tx).await.unwrap();
+
+                let elsewhere = transaction(own, &SigningKey::from([6; 32]));
+                assert!(signer.sign(&elsewhere).await.is_err());
+
+                // Spends a UTxO the chain does not hold at the adaptor's address.
+                let unknown = transaction(Input::new(Hash::from([2; 32]), 0), &signing_key);
+                assert!(signer.sign(&unknown).await.is_err());
+            })
+            .await;
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
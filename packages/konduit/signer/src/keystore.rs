@@ -0,0 +1,198 @@
+//! An encrypted, on-disk, store of a single signing key.
+//!
+//! The key is encrypted with ChaCha20-Poly1305, under a key derived from a password with scrypt.
+//! The verification key is stored in clear, and authenticated as associated data, so that one can
+//! tell which key a keystore holds without the password.
+
+use anyhow::{Context, anyhow};
+use cardano_sdk::{SigningKey, VerificationKey};
+use cryptoxide::{
+    chacha20poly1305::ChaCha20Poly1305,
+    scrypt::{ScryptParams, scrypt},
+};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+const VERSION: u8 = 1;
+
+/// Parameters of the password-based key derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    version: u8,
+    #[serde(with = "hex")]
+    verification_key: Vec<u8>,
+    kdf: KdfParams,
+    #[serde(with = "hex")]
+    salt: Vec<u8>,
+    #[serde(with = "hex")]
+    nonce: Vec<u8>,
+    #[serde(with = "hex")]
+    ciphertext: Vec<u8>,
+    #[serde(with = "hex")]
+    tag: Vec<u8>,
+}
+
+impl Keystore {
+    pub fn encrypt(signing_key: &SigningKey, password: &str) -> Self {
+        Self::encrypt_with(signing_key, password, KdfParams::default())
+    }
+
+    pub fn encrypt_with(signing_key: &SigningKey, password: &str, kdf: KdfParams) -> Self {
+        let verification_key = signing_key.to_verification_key().as_slice().to_vec();
+        let mut salt = vec![0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+
+        let key = derive_key(password, &salt, kdf);
+        // The leaked bytes only live for as long as it takes to encrypt them.
+        let plaintext = unsafe { SigningKey::leak(signing_key.clone()) };
+        let mut ciphertext = vec![0u8; plaintext.len()];
+        let mut tag = vec![0u8; 16];
+        ChaCha20Poly1305::new(&key, &nonce, &verification_key).encrypt(
+            &plaintext,
+            &mut ciphertext,
+            &mut tag,
+        );
+
+        Self {
+            version: VERSION,
+            verification_key,
+            kdf,
+            salt,
+            nonce: nonce.to_vec(),
+            ciphertext,
+            tag,
+        }
+    }
+
+    /// The key held by the keystore.
+    pub fn verification_key(&self) -> anyhow::Result<VerificationKey> {
+        VerificationKey::try_from(self.verification_key.clone())
+            .map_err(|_| anyhow!("malformed keystore verification key"))
+    }
+
+    pub fn decrypt(&self, password: &str) -> anyhow::Result<SigningKey> {
+        if self.version != VERSION {
+            return Err(anyhow!("unsupported keystore version {}", self.version));
+        }
+        let nonce = <[u8; 12]>::try_from(self.nonce.as_slice())
+            .map_err(|_| anyhow!("malformed keystore nonce"))?;
+        let key = derive_key(password, &self.salt, self.kdf);
+        // Extended keys, as derived from mnemonics, are twice the size of standard ones.
+        let mut plaintext = vec![0u8; self.ciphertext.len()];
+        if ![SigningKey::SIZE, SigningKey::EXTENDED_SIZE].contains(&plaintext.len())
+            || !ChaCha20Poly1305::new(&key, &nonce, &self.verification_key).decrypt(
+                &self.ciphertext,
+                &mut plaintext,
+                &self.tag,
+            )
+        {
+            return Err(anyhow!("wrong password, or corrupted keystore"));
+        }
+        let signing_key = SigningKey::try_from(plaintext)?;
+        if signing_key.to_verification_key() != self.verification_key()? {
+            return Err(anyhow!("keystore holds another key than it claims"));
+        }
+        Ok(signing_key)
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read keystore at {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("malformed keystore at {}", path.display()))
+    }
+
+    /// Refuses to overwrite an existing file.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if path.exists() {
+            return Err(anyhow!("{} already exists", path.display()));
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write keystore at {}", path.display()))
+    }
+}
+
+fn derive_key(password: &str, salt: &[u8], kdf: KdfParams) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    scrypt(
+        password.as_bytes(),
+        salt,
+        &ScryptParams::new(kdf.log_n, kdf.r, kdf.p),
+        &mut key,
+    );
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano_sdk::{DerivationPath, ExtendedSigningKey, Mnemonic, crypto::bip32::Role};
+
+    const FAST: KdfParams = KdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn decrypts_with_the_right_password_only() {
+        let signing_key = SigningKey::from([7; 32]);
+        let keystore = Keystore::encrypt_with(&signing_key, "hunter2", FAST);
+        let keystore: Keystore =
+            serde_json::from_str(&serde_json::to_string(&keystore).unwrap()).unwrap();
+
+        assert_eq!(
+            keystore.verification_key().unwrap(),
+            signing_key.to_verification_key()
+        );
+        assert_eq!(
+            keystore.decrypt("hunter2").unwrap().to_verification_key(),
+            signing_key.to_verification_key()
+        );
+        assert!(keystore.decrypt("hunter3").is_err());
+    }
+
+    #[test]
+    fn holds_extended_keys() {
+        let signing_key = ExtendedSigningKey::from_mnemonic(&Mnemonic::new(), "")
+            .derive_path(&DerivationPath::cip1852(0, Role::External, 0))
+            .to_signing_key();
+        let keystore = Keystore::encrypt_with(&signing_key, "hunter2", FAST);
+        let decrypted = keystore.decrypt("hunter2").unwrap();
+        assert!(decrypted.is_extended());
+        assert_eq!(
+            decrypted.to_verification_key(),
+            signing_key.to_verification_key()
+        );
+    }
+
+    #[test]
+    fn rejects_a_swapped_verification_key() {
+        let mut keystore = Keystore::encrypt_with(&SigningKey::from([7; 32]), "hunter2", FAST);
+        keystore.verification_key = SigningKey::from([8; 32])
+            .to_verification_key()
+            .as_slice()
+            .to_vec();
+        assert!(keystore.decrypt("hunter2").is_err());
+    }
+}
@@ -0,0 +1,23 @@
+//! Signing of the adaptor's transactions, without the adaptor necessarily holding its key.
+//!
+//! Besides a plain in-memory key, the key can be held in an encrypted keystore on disk, or by a
+//! remote signer daemon reached over a Unix socket, which only signs transactions its policy
+//! allows.
+
+// 'async_trait' marks the futures it boxes as must-use, which they already are.
+#![allow(clippy::double_must_use)]
+
+mod api;
+pub use api::*;
+
+mod config;
+pub use config::Config;
+
+pub mod daemon;
+pub mod keystore;
+pub mod policy;
+pub mod protocol;
+pub mod remote;
+
+#[cfg(feature = "cli")]
+pub mod cli;
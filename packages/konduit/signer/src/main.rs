@@ -0,0 +1,15 @@
+#[cfg(feature = "cli")]
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    use clap::Parser;
+
+    dotenvy::dotenv().ok();
+    env_logger::init();
+    let args = konduit_signer::cli::Args::parse();
+    konduit_signer::cli::run(args.command).await
+}
+
+#[cfg(not(feature = "cli"))]
+fn main() {
+    panic!("This binary requires the 'cli' feature to be enabled.");
+}
@@ -0,0 +1,475 @@
+use cardano_connector::CardanoConnector;
+use cardano_sdk::{
+    Address, Credential, NetworkId, Output, Transaction, VerificationKey, address::kind,
+    transaction::state,
+};
+use konduit_tx::{Channel, KONDUIT_VALIDATORS, Utxos};
+
+/// What a remote signer is willing to sign: transactions of the adaptor's admin loop, which step
+/// the adaptor's channels, and send funds nowhere but back to those channels or to the adaptor's
+/// own address; and the upkeep of the adaptor's wallet, which moves funds between its own UTxOs.
+///
+/// Inputs are resolved against the chain, never taken from the requester: only the adaptor's own
+/// UTxOs and Konduit's may be spent. Outputs at Konduit must each continue a channel of the
+/// adaptor's that the transaction spends, at the same address and with no more funds, so that the
+/// adaptor's funds never make it into a channel. No certificate is published, as the admin loop
+/// has none to publish; rewards may only be withdrawn from the adaptor's own account, since they
+/// then go where outputs do.
+///
+/// Fees, and the collateral forfeited should a script fail, are capped: see 'MAX_FEE' and
+/// 'MAX_COLLATERAL'.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    network_id: NetworkId,
+    own_vkey: VerificationKey,
+    own: Credential,
+    konduit: Vec<Credential>,
+    max_fee: u64,
+    max_collateral: u64,
+}
+
+/// Default ceiling on fees, in lovelace. An admin transaction filling the default budget pays
+/// about 2.5 ADA.
+pub const MAX_FEE: u64 = 5_000_000;
+
+/// Default ceiling on the collateral forfeited, in lovelace: 150% of 'MAX_FEE', which is what the
+/// ledger requires at most of transactions within it.
+pub const MAX_COLLATERAL: u64 = 7_500_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PolicyError {
+    #[error("input #{0} is neither one of the adaptor's own UTxOs nor a Konduit UTxO")]
+    Unresolved(usize),
+    #[error("spends no channel, yet sends funds elsewhere than the adaptor's own address")]
+    NoScriptSpend,
+    #[error("carries redeemers for other purposes than spending")]
+    OtherRedeemers,
+    #[error("mints or burns tokens")]
+    Mint,
+    #[error("publishes certificates")]
+    Certificates,
+    #[error("withdrawal #{0} is from another reward account than the adaptor's own")]
+    Withdrawal(usize),
+    #[error("output #{0} is on another network")]
+    Network(usize),
+    #[error("output #{0} goes to neither Konduit nor the adaptor's own address")]
+    Output(usize),
+    #[error("output #{0} is not a channel of the adaptor's")]
+    Channel(usize),
+    #[error("output #{0} continues no channel spent, or holds more than it")]
+    Continuation(usize),
+    #[error("collateral return goes elsewhere than the adaptor's own address")]
+    CollateralReturn,
+    #[error("collateral #{0} is not one of the adaptor's own UTxOs")]
+    UnresolvedCollateral(usize),
+    #[error("pays {0} lovelace in fees, over the ceiling of {1}")]
+    Fee(u64, u64),
+    #[error("puts up {0} lovelace of collateral, over the ceiling of {1}")]
+    Collateral(u64, u64),
+}
+
+impl Policy {
+    pub fn new(network_id: NetworkId, own: &VerificationKey) -> Self {
+        Self {
+            network_id,
+            own_vkey: *own,
+            own: own.to_credential(),
+            konduit: KONDUIT_VALIDATORS.credentials().collect(),
+            max_fee: MAX_FEE,
+            max_collateral: MAX_COLLATERAL,
+        }
+    }
+
+    /// Cap fees and the collateral forfeited otherwise than by default, in lovelace.
+    pub fn with_ceilings(mut self, max_fee: u64, max_collateral: u64) -> Self {
+        self.max_fee = max_fee;
+        self.max_collateral = max_collateral;
+        self
+    }
+
+    /// The UTxOs the transaction spends, or puts up as collateral, among those the policy lets it
+    /// spend: the adaptor's own, and Konduit's. Any other input is left out, and fails the check.
+    pub async fn resolve(
+        &self,
+        connector: &impl CardanoConnector,
+        transaction: &Transaction<state::ReadyForSigning>,
+    ) -> anyhow::Result<Utxos> {
+        let mut utxos = connector.utxos_at(&self.own, None).await?;
+        for konduit in &self.konduit {
+            utxos.extend(connector.utxos_at(konduit, None).await?);
+        }
+        Ok(transaction
+            .inputs()
+            .chain(transaction.collaterals())
+            .filter_map(|input| utxos.get(&input).map(|output| (input, output.clone())))
+            .collect())
+    }
+
+    /// Check the transaction, given the UTxOs it spends; see 'Policy::resolve'.
+    pub fn check(
+        &self,
+        transaction: &Transaction<state::ReadyForSigning>,
+        resolved: &Utxos,
+    ) -> Result<(), PolicyError> {
+        let mut spent_channels = vec![];
+        for (ix, input) in transaction.inputs().enumerate() {
+            let Some(output) = resolved.get(&input) else {
+                return Err(PolicyError::Unresolved(ix));
+            };
+            let Some(address) = shelley(output) else {
+                return Err(PolicyError::Unresolved(ix));
+            };
+            let payment = address.payment();
+            if payment == self.own {
+                continue;
+            }
+            if !self.konduit.contains(&payment) {
+                return Err(PolicyError::Unresolved(ix));
+            }
+            if let Ok(channel) = Channel::try_from(output) {
+                spent_channels.push((output, channel));
+            }
+        }
+        let upkeep = spent_channels.is_empty();
+
+        if !transaction
+            .redeemers()
+            .all(|(pointer, _)| pointer.is_spend())
+        {
+            return Err(PolicyError::OtherRedeemers);
+        }
+        if !transaction.mint().is_empty() {
+            return Err(PolicyError::Mint);
+        }
+        if transaction.certificates().next().is_some() {
+            return Err(PolicyError::Certificates);
+        }
+        if let Some(ix) = transaction
+            .withdrawals()
+            .position(|(credential, _)| credential != self.own)
+        {
+            return Err(PolicyError::Withdrawal(ix));
+        }
+        for (ix, output) in transaction.outputs().enumerate() {
+            let Some(address) = shelley(&output) else {
+                return Err(PolicyError::Output(ix));
+            };
+            if address.network_id() != self.network_id {
+                return Err(PolicyError::Network(ix));
+            }
+            let payment = address.payment();
+            if payment == self.own {
+                continue;
+            }
+            if upkeep {
+                return Err(PolicyError::NoScriptSpend);
+            }
+            if !self.konduit.contains(&payment) {
+                return Err(PolicyError::Output(ix));
+            }
+            let channel = Channel::try_from(&output)
+                .ok()
+                .filter(|channel| channel.constants().sub_vkey == self.own_vkey)
+                .ok_or(PolicyError::Channel(ix))?;
+            let continued = spent_channels
+                .iter()
+                .position(|(spent_output, spent)| {
+                    spent_output.address() == output.address()
+                        && spent.constants() == channel.constants()
+                        && spent.amount() >= channel.amount()
+                        && spent_output.value().lovelace() >= output.value().lovelace()
+                })
+                .ok_or(PolicyError::Continuation(ix))?;
+            spent_channels.swap_remove(continued);
+        }
+        if let Some(output) = transaction.collateral_return()
+            && shelley(&output).is_none_or(|address| address.payment() != self.own)
+        {
+            return Err(PolicyError::CollateralReturn);
+        }
+
+        if transaction.fee() > self.max_fee {
+            return Err(PolicyError::Fee(transaction.fee(), self.max_fee));
+        }
+        // What the ledger takes should a script fail: the total collateral, i.e. the collateral
+        // inputs less the collateral return, whether or not the total is declared.
+        let mut collateral = 0;
+        for (ix, input) in transaction.collaterals().enumerate() {
+            let output = resolved
+                .get(&input)
+                .filter(|output| shelley(output).is_some_and(|a| a.payment() == self.own))
+                .ok_or(PolicyError::UnresolvedCollateral(ix))?;
+            collateral += output.value().lovelace();
+        }
+        let collateral = collateral.saturating_sub(
+            transaction
+                .collateral_return()
+                .map_or(0, |output| output.value().lovelace()),
+        );
+        let collateral = collateral.max(transaction.total_collateral());
+        if collateral > self.max_collateral {
+            return Err(PolicyError::Collateral(collateral, self.max_collateral));
+        }
+        Ok(())
+    }
+}
+
+fn shelley(output: &Output) -> Option<Address<kind::Shelley>> {
+    output.address().as_shelley()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano_sdk::{
+        Certificate, Hash, Input, PlutusData, SigningKey, Value,
+        cbor::{FromCbor, ToCbor},
+    };
+    use konduit_data::{Constants, Duration, Tag};
+    use konduit_tx::{KONDUIT_VALIDATOR, Open};
+
+    fn own() -> VerificationKey {
+        SigningKey::from([3; 32]).to_verification_key()
+    }
+
+    fn consumer() -> VerificationKey {
+        SigningKey::from([4; 32]).to_verification_key()
+    }
+
+    fn policy() -> Policy {
+        Policy::new(NetworkId::TESTNET, &own())
+    }
+
+    fn wallet_utxo() -> (Input, Output) {
+        (
+            Input::new(Hash::from([1; 32]), 0),
+            to(own().to_address(NetworkId::TESTNET)),
+        )
+    }
+
+    fn constants(sub_vkey: VerificationKey) -> Constants {
+        Constants {
+            tag: Tag::from(vec![1]),
+            add_vkey: consumer(),
+            sub_vkey,
+            close_period: Duration::from_secs(3600),
+        }
+    }
+
+    fn channel(sub_vkey: VerificationKey, amount: u64) -> Output {
+        Open::new(amount, constants(sub_vkey), None).output(NetworkId::TESTNET)
+    }
+
+    fn channel_utxo(amount: u64) -> (Input, Output) {
+        (Input::new(Hash::from([2; 32]), 0), channel(own(), amount))
+    }
+
+    /// A transaction spending the given UTxOs, the Konduit ones with a redeemer.
+    fn tx(spent: &Utxos, outputs: Vec<Output>) -> Transaction<state::ReadyForSigning> {
+        tx_with(spent, outputs, |_| {})
+    }
+
+    fn tx_with(
+        spent: &Utxos,
+        outputs: Vec<Output>,
+        with: impl FnOnce(&mut Transaction<state::InConstruction>),
+    ) -> Transaction<state::ReadyForSigning> {
+        let mut tx = Transaction::default();
+        tx.with_inputs(spent.iter().map(|(input, output)| {
+            let redeemer = (shelley(output).unwrap().payment() != own().to_credential())
+                .then(|| PlutusData::from(0));
+            (input.clone(), redeemer)
+        }))
+        .with_outputs(outputs);
+        with(&mut tx);
+        Transaction::from_cbor(&tx.to_cbor()).unwrap()
+    }
+
+    fn to(address: Address<kind::Shelley>) -> Output {
+        Output::new(address.into(), Value::new(2_000_000))
+    }
+
+    #[test]
+    fn signs_channel_steps_back_to_the_channel_or_self() {
+        let spent = Utxos::from([wallet_utxo(), channel_utxo(10_000_000)]);
+        let outputs = vec![
+            channel(own(), 7_000_000),
+            to(own().to_address(NetworkId::TESTNET)),
+        ];
+        assert_eq!(policy().check(&tx(&spent, outputs), &spent), Ok(()));
+    }
+
+    #[test]
+    fn signs_wallet_upkeep() {
+        let spent = Utxos::from([wallet_utxo()]);
+        let outputs = vec![to(own().to_address(NetworkId::TESTNET))];
+        assert_eq!(policy().check(&tx(&spent, outputs), &spent), Ok(()));
+    }
+
+    #[test]
+    fn refuses_to_fund_channels_out_of_the_wallet() {
+        let spent = Utxos::from([wallet_utxo(), channel_utxo(10_000_000)]);
+        let check = |outputs| policy().check(&tx(&spent, outputs), &spent);
+
+        // The channel spent, continued with more than it held.
+        assert_eq!(
+            check(vec![channel(own(), 50_000_000)]),
+            Err(PolicyError::Continuation(0))
+        );
+        // A new channel, next to the one continued.
+        assert_eq!(
+            check(vec![channel(own(), 10_000_000), channel(own(), 1_000_000)]),
+            Err(PolicyError::Continuation(1))
+        );
+        // A channel with another adaptor.
+        assert_eq!(
+            check(vec![channel(consumer(), 10_000_000)]),
+            Err(PolicyError::Channel(0))
+        );
+        // Anything at Konduit that is not a channel.
+        assert_eq!(
+            check(vec![
+                to(KONDUIT_VALIDATOR.address(NetworkId::TESTNET, None))
+            ]),
+            Err(PolicyError::Channel(0))
+        );
+        // A channel of the adaptor's, with different constants.
+        let other = Constants {
+            close_period: Duration::from_secs(1),
+            ..constants(own())
+        };
+        let output = Open::new(1_000_000, other, None).output(NetworkId::TESTNET);
+        assert_eq!(check(vec![output]), Err(PolicyError::Continuation(0)));
+    }
+
+    #[test]
+    fn refuses_spends_it_cannot_resolve() {
+        let spent = Utxos::from([wallet_utxo(), channel_utxo(10_000_000)]);
+        let tx = tx(&spent, vec![channel(own(), 10_000_000)]);
+        // Claimed to spend a channel, though the input is not found at Konduit.
+        assert_eq!(
+            policy().check(&tx, &Utxos::from([wallet_utxo()])),
+            Err(PolicyError::Unresolved(1))
+        );
+    }
+
+    #[test]
+    fn refuses_other_transactions() {
+        let elsewhere = SigningKey::from([5; 32]).to_verification_key();
+        let wallet = Utxos::from([wallet_utxo()]);
+        assert_eq!(
+            policy().check(&tx(&wallet, vec![channel(own(), 1_000_000)]), &wallet),
+            Err(PolicyError::NoScriptSpend)
+        );
+
+        let spent = Utxos::from([wallet_utxo(), channel_utxo(10_000_000)]);
+        assert_eq!(
+            policy().check(
+                &tx(&spent, vec![to(elsewhere.to_address(NetworkId::TESTNET))]),
+                &spent
+            ),
+            Err(PolicyError::Output(0))
+        );
+        assert_eq!(
+            policy().check(
+                &tx(&spent, vec![to(own().to_address(NetworkId::MAINNET))]),
+                &spent
+            ),
+            Err(PolicyError::Network(0))
+        );
+    }
+
+    #[test]
+    fn refuses_fees_over_the_ceiling() {
+        let spent = Utxos::from([wallet_utxo(), channel_utxo(10_000_000)]);
+        let check = |fee| {
+            let tx = tx_with(&spent, vec![channel(own(), 9_000_000)], |tx| {
+                tx.with_fee(fee);
+            });
+            policy().check(&tx, &spent)
+        };
+
+        assert_eq!(check(MAX_FEE), Ok(()));
+        // A requester draining the channel, or the wallet, into fees.
+        assert_eq!(
+            check(MAX_FEE + 1),
+            Err(PolicyError::Fee(MAX_FEE + 1, MAX_FEE))
+        );
+        assert_eq!(
+            policy().with_ceilings(1_000_000, MAX_COLLATERAL).check(
+                &tx_with(&spent, vec![], |tx| {
+                    tx.with_fee(1_000_001);
+                }),
+                &spent
+            ),
+            Err(PolicyError::Fee(1_000_001, 1_000_000))
+        );
+    }
+
+    #[test]
+    fn refuses_collateral_over_the_ceiling() {
+        let collateral = |lovelace| {
+            (
+                Input::new(Hash::from([6; 32]), 0),
+                Output::new(
+                    own().to_address(NetworkId::TESTNET).into(),
+                    Value::new(lovelace),
+                ),
+            )
+        };
+        let check = |(input, output): (Input, Output)| {
+            let spent = Utxos::from([wallet_utxo(), channel_utxo(10_000_000)]);
+            let tx = tx_with(&spent, vec![channel(own(), 10_000_000)], |tx| {
+                tx.with_collaterals([input.clone()]);
+            });
+            let mut resolved = spent.clone();
+            resolved.insert(input, output);
+            policy().check(&tx, &resolved)
+        };
+
+        assert_eq!(check(collateral(MAX_COLLATERAL)), Ok(()));
+        // With no collateral return, the whole of the collateral inputs is forfeited.
+        assert_eq!(
+            check(collateral(50_000_000)),
+            Err(PolicyError::Collateral(50_000_000, MAX_COLLATERAL))
+        );
+        // Collateral must come out of the adaptor's own UTxOs.
+        let (input, _) = collateral(MAX_COLLATERAL);
+        assert_eq!(
+            check((input, channel(own(), 1_000_000))),
+            Err(PolicyError::UnresolvedCollateral(0))
+        );
+    }
+
+    #[test]
+    fn refuses_certificates_and_withdrawals_from_others() {
+        let spent = Utxos::from([wallet_utxo(), channel_utxo(10_000_000)]);
+        let outputs = || vec![channel(own(), 10_000_000)];
+        let elsewhere = SigningKey::from([5; 32]).to_verification_key();
+
+        let tx = tx_with(&spent, outputs(), |tx| {
+            tx.with_withdrawals(
+                NetworkId::TESTNET,
+                [(own().to_credential(), 1_000_000, None)],
+            );
+        });
+        assert_eq!(policy().check(&tx, &spent), Ok(()));
+
+        let tx = tx_with(&spent, outputs(), |tx| {
+            tx.with_withdrawals(
+                NetworkId::TESTNET,
+                [(elsewhere.to_credential(), 1_000_000, None)],
+            );
+        });
+        assert_eq!(policy().check(&tx, &spent), Err(PolicyError::Withdrawal(0)));
+
+        // Even the adaptor's own stake is not delegated by way of the signer.
+        let tx = tx_with(&spent, outputs(), |tx| {
+            tx.with_certificates([(
+                Certificate::delegate(own().to_credential(), Hash::from([7; 28])),
+                None,
+            )]);
+        });
+        assert_eq!(policy().check(&tx, &spent), Err(PolicyError::Certificates));
+    }
+}
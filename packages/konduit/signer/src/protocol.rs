@@ -0,0 +1,49 @@
+//! The protocol spoken between a remote signer and its clients over a Unix socket.
+//!
+//! Messages are JSON objects, one per line. A client sends a request and reads back a single
+//! response, any number of times over the same connection.
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// Which key the signer signs with.
+    VerificationKey,
+    /// Sign a transaction, given as base16-encoded CBOR.
+    Sign { transaction: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    /// Base16-encoded verification key.
+    VerificationKey { verification_key: String },
+    /// Base16-encoded signature of the transaction id.
+    Signature { signature: String },
+    /// The request was malformed, or refused by the signer's policy.
+    Error { message: String },
+}
+
+/// Read the next message, or `None` once the peer hung up.
+pub async fn read<T: for<'de> Deserialize<'de>>(
+    reader: &mut (impl AsyncBufRead + Unpin),
+) -> anyhow::Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+pub async fn write<T: Serialize>(
+    writer: &mut (impl AsyncWrite + Unpin),
+    message: &T,
+) -> anyhow::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line).await?;
+    writer.flush().await?;
+    Ok(())
+}
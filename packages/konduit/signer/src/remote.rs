@@ -0,0 +1,75 @@
+use crate::{
+    Signer,
+    protocol::{self, Request, Response},
+};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use cardano_sdk::{Signature, Transaction, VerificationKey, cbor::ToCbor, transaction::state};
+use std::{path::PathBuf, str::FromStr};
+use tokio::{io::BufReader, net::UnixStream};
+
+/// A signer daemon, reached over a Unix socket; see 'crate::daemon'.
+///
+/// A connection is opened per request, so that the daemon may restart in between.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    socket: PathBuf,
+    verification_key: VerificationKey,
+}
+
+impl RemoteSigner {
+    /// Connect to the daemon, and ask which key it signs with.
+    pub async fn connect(socket: PathBuf) -> anyhow::Result<Self> {
+        let response = request(&socket, &Request::VerificationKey).await?;
+        let Response::VerificationKey { verification_key } = response else {
+            return Err(unexpected(response));
+        };
+        Ok(Self {
+            socket,
+            verification_key: VerificationKey::from_str(&verification_key)?,
+        })
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn verification_key(&self) -> VerificationKey {
+        self.verification_key
+    }
+
+    async fn sign(
+        &self,
+        transaction: &Transaction<state::ReadyForSigning>,
+    ) -> anyhow::Result<Signature> {
+        let sign = Request::Sign {
+            transaction: hex::encode(transaction.to_cbor()),
+        };
+        let response = request(&self.socket, &sign).await?;
+        let Response::Signature { signature } = response else {
+            return Err(unexpected(response));
+        };
+        let signature = Signature::from_str(&signature)?;
+        if !self.verification_key.verify(transaction.id(), &signature) {
+            return Err(anyhow!("remote signer returned an invalid signature"));
+        }
+        Ok(signature)
+    }
+}
+
+async fn request(socket: &PathBuf, request: &Request) -> anyhow::Result<Response> {
+    let stream = UnixStream::connect(socket).await.map_err(|e| {
+        anyhow!(e).context(format!("failed to reach signer at {}", socket.display()))
+    })?;
+    let (reader, mut writer) = stream.into_split();
+    protocol::write(&mut writer, request).await?;
+    protocol::read(&mut BufReader::new(reader))
+        .await?
+        .ok_or_else(|| anyhow!("signer hung up without responding"))
+}
+
+fn unexpected(response: Response) -> anyhow::Error {
+    match response {
+        Response::Error { message } => anyhow!("signer refused: {message}"),
+        response => anyhow!("unexpected response from signer: {response:?}"),
+    }
+}
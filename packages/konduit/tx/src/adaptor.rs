@@ -1,11 +1,14 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 
 use cardano_sdk::{
     Address, Transaction, VerificationKey, address::kind, transaction::state::ReadyForSigning,
 };
 use konduit_data::{Duration, Keytag, Receipt};
 
-use crate::{ChannelUtxo, NetworkParameters, SteppedUtxos, Utxos, find_reference_script};
+use crate::{
+    ChannelUtxo, NetworkParameters, SteppedUtxo, SteppedUtxos, TxBudget, TxCost, Utxos, batch,
+    find_reference_scripts, fuel,
+};
 
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("insufficient total gain: preferences.min_total = {min_total}, gain = {gain}")]
@@ -20,25 +23,29 @@ pub struct AdaptorPreferences {
     pub min_single: u64,
     // Prevents a transaction in which the total gain is too little
     pub min_total: u64,
+    // Limits each transaction must fit in; steps beyond are split across several transactions.
+    pub budget: TxBudget,
 }
 
-// WARNING :: This transaction does **not** verify that the resultant tx does not
+// WARNING :: These transactions do **not** verify that the resultant txs do not
 // violate the condition that if the channel is being treated as active,
 // then the retainer is not responded.
 // This must be handled elsewhere!
-pub fn tx(
+/// Step every channel that can be, in as many transactions as the budget requires. The most urgent
+/// steps, those whose cheques time out first, come first. Transactions are chained: each is fuelled
+/// by the change of the previous one, and they are to be submitted in order.
+pub fn txs(
     network_parameters: &NetworkParameters,
     preferences: &AdaptorPreferences,
     wallet: &VerificationKey,
     receipts: &BTreeMap<Keytag, Receipt>,
     utxos: &Utxos,
     upper: &Duration,
-) -> anyhow::Result<Transaction<ReadyForSigning>> {
-    let reference_utxo = find_reference_script(utxos);
-    if reference_utxo.is_none() {
+) -> anyhow::Result<Vec<Transaction<ReadyForSigning>>> {
+    let reference_utxos = find_reference_scripts(utxos);
+    if reference_utxos.is_empty() {
         return Err(anyhow::anyhow!("No konduit reference found"));
     };
-    let change_address = wallet.to_address(network_parameters.network_id);
     let steppeds = utxos
         .iter()
         .filter_map(|u| ChannelUtxo::try_from(u).ok())
@@ -50,33 +57,244 @@ pub fn tx(
         })
         .filter(|u| u.gain() >= preferences.min_single as i64)
         .collect::<Vec<_>>();
-    let steppeds = SteppedUtxos::from(steppeds);
 
-    if steppeds.gain() < preferences.min_total as i64 {
+    let wallet_address: Address<kind::Any> =
+        wallet.to_address(network_parameters.network_id).into();
+    let mut fuel = utxos
+        .iter()
+        .filter(|u| u.1.address() == &wallet_address)
+        .map(|u| (u.0.clone(), u.1.clone()))
+        .collect::<Utxos>();
+
+    let mut queue = by_urgency(steppeds);
+    let mut txs = vec![];
+    let mut best_gain = 0;
+    while let Some((batch, tx)) = batch::take(&mut queue, &preferences.budget, |batch| {
+        let tx = crate::tx::tx(
+            network_parameters,
+            &reference_utxos,
+            wallet_address.clone(),
+            SteppedUtxos::from(batch.to_vec()),
+            vec![],
+            &fuel,
+        )?;
+        let cost = TxCost::from(&tx);
+        Ok((tx, cost))
+    })? {
+        let gain = SteppedUtxos::from(batch).gain();
+        best_gain = best_gain.max(gain);
+        if gain < preferences.min_total as i64 {
+            continue;
+        }
+        fuel = fuel::after(&fuel, &tx, &wallet_address);
+        txs.push(tx);
+    }
+
+    if txs.is_empty() {
         return Err(InsufficientTotalGain {
             min_total: preferences.min_total,
-            gain: steppeds.gain(),
+            gain: best_gain,
         }
         .into());
     }
+    Ok(txs)
+}
 
-    let opens = vec![];
+/// Steps whose cheques time out first come first; steps with nothing to time out come last.
+fn by_urgency(mut steppeds: Vec<SteppedUtxo>) -> VecDeque<SteppedUtxo> {
+    steppeds.sort_by_key(|u| (u.data().urgency().is_none(), u.data().urgency()));
+    steppeds.into()
+}
 
-    let wallet_address: Address<kind::Any> =
-        wallet.to_address(network_parameters.network_id).into();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KONDUIT_VALIDATOR, Open, StepTo, Stepped, Variables};
+    use cardano_sdk::{
+        Hash, Input, NetworkId, Output, PlutusScript, PlutusVersion, ProtocolParameters, Signature,
+        SigningKey, Value,
+    };
+    use konduit_data::{
+        ChequeBody, Constants, Cont, Indexes, Lock, Secret, Squash, SquashBody, Stage, Tag,
+        Unlocked,
+    };
 
-    let fuel = utxos
-        .iter()
-        .filter(|u| u.1.address() == &wallet_address)
-        .map(|u| (u.0.clone(), u.1.clone()))
-        .to_owned()
-        .collect::<BTreeMap<_, _>>();
-    crate::tx::tx(
-        network_parameters,
-        reference_utxo.as_ref(),
-        change_address.into(),
-        steppeds,
-        opens,
-        &fuel,
-    )
+    fn adaptor() -> SigningKey {
+        SigningKey::from([2; 32])
+    }
+
+    fn consumer() -> SigningKey {
+        SigningKey::from([1; 32])
+    }
+
+    fn constants(i: u32) -> Constants {
+        Constants {
+            tag: Tag::from(i.to_be_bytes().to_vec()),
+            add_vkey: consumer().to_verification_key(),
+            sub_vkey: adaptor().to_verification_key(),
+            close_period: Duration::from_secs(3600),
+        }
+    }
+
+    fn network_parameters() -> NetworkParameters {
+        NetworkParameters {
+            network_id: NetworkId::TESTNET,
+            protocol_parameters: ProtocolParameters::preview(),
+        }
+    }
+
+    /// Hundreds of opened channels, each owing the adaptor a squash, along with the reference
+    /// script and the adaptor's fuel.
+    fn utxos(channels: u32) -> (Utxos, BTreeMap<Keytag, Receipt>) {
+        let wallet = adaptor()
+            .to_verification_key()
+            .to_address(NetworkId::TESTNET);
+        let mut utxos = Utxos::new();
+        utxos.insert(
+            Input::new(Hash::from([0xaa; 32]), 0),
+            Output::new(wallet.clone().into(), Value::new(10_000_000)).with_plutus_script(
+                PlutusScript::new(
+                    PlutusVersion::V3,
+                    KONDUIT_VALIDATOR.script.script().to_vec(),
+                ),
+            ),
+        );
+        utxos.insert(
+            Input::new(Hash::from([0xff; 32]), 0),
+            Output::new(wallet.into(), Value::new(1_000_000_000)),
+        );
+        let mut receipts = BTreeMap::new();
+        for i in 0..channels {
+            let constants = constants(i);
+            let keytag = Keytag::new(constants.add_vkey, constants.tag.clone());
+            let squash = Squash::make(
+                &consumer(),
+                &constants.tag,
+                SquashBody::new_no_verify(3_000_000, 0, Indexes::default()),
+            );
+            receipts.insert(keytag, Receipt::new(squash));
+            let output = Open::new(10_000_000, constants, None).output(NetworkId::TESTNET);
+            utxos.insert(Input::new(Hash::from([0x11; 32]), i as u64), output);
+        }
+        (utxos, receipts)
+    }
+
+    #[test]
+    fn splits_hundreds_of_subs_across_chained_transactions() {
+        let (utxos, receipts) = utxos(200);
+        // A budget a fifth of the ledger's keeps batches, and the test, short.
+        let preferences = AdaptorPreferences {
+            min_single: 1,
+            min_total: 1,
+            budget: TxBudget {
+                max_size: 3_000,
+                ..TxBudget::default()
+            },
+        };
+        let txs = txs(
+            &network_parameters(),
+            &preferences,
+            &adaptor().to_verification_key(),
+            &receipts,
+            &utxos,
+            &Duration::from_secs(1_800_000_000),
+        )
+        .unwrap();
+
+        assert!(txs.len() > 20, "200 channels should not fit a few txs");
+        let mut spent = std::collections::BTreeSet::new();
+        for (ix, tx) in txs.iter().enumerate() {
+            assert!(preferences.budget.fits(&TxCost::from(tx)));
+            assert!(
+                tx.message()
+                    .is_some_and(|lines| lines[0].starts_with("konduit: sub x"))
+            );
+            for collateral in tx.collaterals() {
+                assert!(
+                    !spent.contains(&collateral),
+                    "tx #{ix} is backed by a spent collateral"
+                );
+            }
+            for input in tx.inputs() {
+                assert!(spent.insert(input), "input spent twice");
+            }
+        }
+        let stepped = spent
+            .iter()
+            .filter(|input| input.transaction_id() == Hash::from([0x11; 32]))
+            .count();
+        assert_eq!(stepped, 200);
+    }
+
+    #[test]
+    fn batches_short_of_the_minimum_gain_are_dropped() {
+        let (utxos, receipts) = utxos(3);
+        let preferences = AdaptorPreferences {
+            min_single: 1,
+            min_total: 10_000_000,
+            budget: TxBudget::default(),
+        };
+        let error = txs(
+            &network_parameters(),
+            &preferences,
+            &adaptor().to_verification_key(),
+            &receipts,
+            &utxos,
+            &Duration::from_secs(1_800_000_000),
+        )
+        .unwrap_err();
+        let error = error.downcast::<InsufficientTotalGain>().unwrap();
+        assert_eq!(error.gain, 9_000_000);
+    }
+
+    fn stepped_sub(i: u32, timeouts: &[u64]) -> SteppedUtxo {
+        let channel =
+            crate::Channel::new(constants(i), Variables::new(10, Stage::Opened(0, vec![])));
+        let unlockeds = timeouts
+            .iter()
+            .map(|timeout| {
+                let secret = Secret([i as u8; 32]);
+                Unlocked::new_no_verify(
+                    ChequeBody::new(0, 1, Duration::from_secs(*timeout), Lock::from(&secret)),
+                    Signature::from([0; 64]),
+                    secret,
+                )
+            })
+            .collect();
+        let step_to = StepTo::cont(
+            Cont::Sub(
+                Squash::new(
+                    SquashBody::new_no_verify(0, 0, Indexes::default()),
+                    Signature::from([0; 64]),
+                ),
+                unlockeds,
+            ),
+            Variables::new(9, Stage::Opened(1, vec![])),
+        );
+        SteppedUtxo::new(
+            (
+                Input::new(Hash::from([0x22; 32]), i as u64),
+                Output::new(
+                    KONDUIT_VALIDATOR.address(NetworkId::TESTNET, None).into(),
+                    Value::new(10),
+                ),
+            ),
+            Stepped::new(channel, step_to, Default::default()),
+        )
+    }
+
+    #[test]
+    fn most_urgent_steps_come_first() {
+        let queue = by_urgency(vec![
+            stepped_sub(0, &[]),
+            stepped_sub(1, &[300, 500]),
+            stepped_sub(2, &[200]),
+            stepped_sub(3, &[400]),
+        ]);
+        let order = queue
+            .iter()
+            .map(|u| u.input().output_index())
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec![2, 1, 3, 0]);
+    }
 }
@@ -3,7 +3,17 @@ use cardano_sdk::{
     transaction::state::ReadyForSigning,
 };
 
-use crate::Utxos;
+use crate::{
+    Lovelace, Utxos,
+    coin_selection::{self, COLLATERAL},
+    fuel,
+};
+
+/// Lovelace left over for fees and change when splitting collaterals off.
+const SPLIT_BUFFER: Lovelace = 2_000_000;
+
+/// Pure-ADA UTxOs merged at most by a single consolidation, to stay within the tx size limit.
+const MAX_CONSOLIDATED: usize = 100;
 
 pub fn deploy(
     protocol_parameters: &ProtocolParameters,
@@ -43,3 +53,143 @@ pub fn send(
             .ok()
     })
 }
+
+/// The shape of UTxO set the adaptor keeps its wallet in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalletPolicy {
+    /// Dedicated collaterals to keep around. See `coin_selection::select`.
+    pub collaterals: usize,
+    /// Pure-ADA UTxOs, collaterals aside, beyond which they are consolidated.
+    pub max_fuel_utxos: usize,
+}
+
+impl Default for WalletPolicy {
+    fn default() -> Self {
+        Self {
+            collaterals: 1,
+            max_fuel_utxos: 20,
+        }
+    }
+}
+
+/// A tx bringing the wallet back in line with its policy, if it strays: splitting collaterals off
+/// when short of them, and consolidating pure-ADA UTxOs when there are too many. UTxOs holding
+/// assets or scripts are left alone.
+pub fn upkeep(
+    protocol_parameters: &ProtocolParameters,
+    utxos: &Utxos,
+    address: Address<address::kind::Any>,
+    policy: &WalletPolicy,
+) -> anyhow::Result<Option<Transaction<ReadyForSigning>>> {
+    let mut pure_ada = utxos
+        .iter()
+        .filter(|(_, output)| coin_selection::is_pure_ada(output))
+        .map(|(input, output)| (input.clone(), output.clone()))
+        .collect::<Vec<_>>();
+    pure_ada.sort_by_key(|(_, output)| output.value().lovelace());
+    // The smallest collaterals are kept as such, larger ones are just as good as fuel.
+    let (collaterals, fuel): (Vec<_>, Vec<_>) = pure_ada.into_iter().partition({
+        let mut kept = 0;
+        move |(_, output)| {
+            let keep = kept < policy.collaterals && coin_selection::is_collateral(output);
+            kept += keep as usize;
+            keep
+        }
+    });
+    let missing = policy.collaterals - collaterals.len();
+    let consolidate = fuel.len() > policy.max_fuel_utxos;
+    if missing == 0 && !consolidate {
+        return Ok(None);
+    }
+
+    let fuel = fuel.into_iter().collect::<Utxos>();
+    let inputs = if consolidate {
+        let mut smallest = fuel.iter().collect::<Vec<_>>();
+        smallest.sort_by_key(|(_, output)| output.value().lovelace());
+        smallest
+            .into_iter()
+            .take(MAX_CONSOLIDATED)
+            .map(|(input, _)| input.clone())
+            .collect()
+    } else {
+        fuel::select(&fuel, missing as Lovelace * COLLATERAL + SPLIT_BUFFER).map_err(|_| {
+            anyhow::anyhow!("insufficient pure-ADA funds to set aside {missing} collateral(s)")
+        })?
+    };
+    let available = inputs
+        .iter()
+        .map(|input| fuel[input].value().lovelace())
+        .sum::<Lovelace>();
+    let splits = missing.min((available.saturating_sub(SPLIT_BUFFER) / COLLATERAL) as usize);
+    let outputs = (0..splits)
+        .map(|_| Output::new(address.clone(), Value::new(COLLATERAL)))
+        .collect::<Vec<_>>();
+
+    Transaction::build(protocol_parameters, &fuel, |tx| {
+        tx.with_inputs(inputs.iter().map(|input| (input.clone(), None)))
+            .with_outputs(outputs.to_owned())
+            .with_change_strategy(ChangeStrategy::as_last_output(address.to_owned()))
+            .with_message(["konduit: upkeep"])
+            .ok()
+    })
+    .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano_sdk::{Hash, Input, NetworkId, SigningKey};
+
+    fn address() -> Address<address::kind::Any> {
+        SigningKey::from([1; 32])
+            .to_verification_key()
+            .to_address(NetworkId::TESTNET)
+            .into()
+    }
+
+    fn wallet(lovelaces: impl IntoIterator<Item = Lovelace>) -> Utxos {
+        lovelaces
+            .into_iter()
+            .enumerate()
+            .map(|(ix, lovelace)| {
+                (
+                    Input::new(Hash::from([ix as u8; 32]), 0),
+                    Output::new(address(), Value::new(lovelace)),
+                )
+            })
+            .collect()
+    }
+
+    fn upkeep(utxos: &Utxos) -> Option<Transaction<ReadyForSigning>> {
+        super::upkeep(
+            &ProtocolParameters::preview(),
+            utxos,
+            address(),
+            &WalletPolicy::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn splits_a_collateral_off_when_short_of_one() {
+        let tx = upkeep(&wallet([2_000_000, 100_000_000])).unwrap();
+        assert!(
+            tx.outputs()
+                .any(|output| coin_selection::is_collateral(&output)
+                    && output.value().lovelace() == COLLATERAL)
+        );
+        assert!(upkeep(&wallet([COLLATERAL, 2_000_000])).is_none());
+    }
+
+    #[test]
+    fn consolidates_many_small_utxos() {
+        let utxos = wallet((0..30).map(|ix| 2_000_000 + ix).chain([COLLATERAL]));
+        let tx = upkeep(&utxos).unwrap();
+        assert_eq!(tx.inputs().count(), 30);
+        assert!(
+            !tx.inputs()
+                .any(|input| utxos[&input].value().lovelace() == COLLATERAL)
+        );
+        assert_eq!(tx.outputs().count(), 1);
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::VecDeque;
+
+use cardano_sdk::{Transaction, cbor::ToCbor, transaction::state::ReadyForSigning};
+
+/// Limits a single transaction must fit in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxBudget {
+    /// In bytes, of the transaction before it is signed.
+    pub max_size: u64,
+    pub max_mem: u64,
+    pub max_cpu: u64,
+}
+
+impl Default for TxBudget {
+    /// The ledger limits of all public networks, less some room for the key witnesses added once
+    /// built.
+    fn default() -> Self {
+        Self {
+            max_size: 16_384 - 1_024,
+            max_mem: 14_000_000,
+            max_cpu: 10_000_000_000,
+        }
+    }
+}
+
+impl TxBudget {
+    pub fn fits(&self, cost: &TxCost) -> bool {
+        cost.size <= self.max_size && cost.mem <= self.max_mem && cost.cpu <= self.max_cpu
+    }
+
+    /// How many more items would fit, given the cost of the last 'added' ones, on the tightest of
+    /// the limits.
+    fn marginal_room(&self, before: &TxCost, after: &TxCost, added: usize) -> usize {
+        let room = |max: u64, before: u64, after: u64| {
+            let per_item = after.saturating_sub(before) as f64 / added as f64;
+            if per_item == 0.0 {
+                usize::MAX
+            } else {
+                (max.saturating_sub(after) as f64 / per_item) as usize
+            }
+        };
+        room(self.max_size, before.size, after.size)
+            .min(room(self.max_mem, before.mem, after.mem))
+            .min(room(self.max_cpu, before.cpu, after.cpu))
+    }
+
+    /// How many times over the cost would still fit, on the tightest of the limits.
+    fn room(&self, cost: &TxCost) -> f64 {
+        let ratio = |max: u64, used: u64| {
+            if used == 0 {
+                f64::INFINITY
+            } else {
+                max as f64 / used as f64
+            }
+        };
+        ratio(self.max_size, cost.size)
+            .min(ratio(self.max_mem, cost.mem))
+            .min(ratio(self.max_cpu, cost.cpu))
+    }
+}
+
+/// What a transaction claims of a 'TxBudget', as evaluated by the builder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TxCost {
+    pub size: u64,
+    pub mem: u64,
+    pub cpu: u64,
+}
+
+impl From<&Transaction<ReadyForSigning>> for TxCost {
+    fn from(tx: &Transaction<ReadyForSigning>) -> Self {
+        let execution_units = tx.total_execution_units();
+        Self {
+            size: tx.to_cbor().len() as u64,
+            mem: execution_units.mem(),
+            cpu: execution_units.cpu(),
+        }
+    }
+}
+
+/// Take from the front of the queue as many items as can be built into a single transaction
+/// within budget, and return them along with what they were built into.
+///
+/// Costs are those evaluated by the builder rather than estimated. As building is costly, the
+/// batch grows by as many items as the room left in the budget is projected to take, and is
+/// narrowed down by bisection when overshooting. An item that does not fit on its own is dropped,
+/// with a warning.
+/// Build failures on a single item are returned, as they are not due to the size of the batch.
+pub fn take<T: Clone, R>(
+    queue: &mut VecDeque<T>,
+    budget: &TxBudget,
+    mut build: impl FnMut(&[T]) -> anyhow::Result<(R, TxCost)>,
+) -> anyhow::Result<Option<(Vec<T>, R)>> {
+    loop {
+        if queue.is_empty() {
+            return Ok(None);
+        }
+        // Largest batch known to fit, with its cost and what it was built into.
+        let mut fit: Option<(usize, TxCost, R)> = None;
+        // Smallest batch known, or projected, not to fit.
+        let mut unfit = queue.len() + 1;
+        // What the first item costs, should it not fit on its own.
+        let mut alone = None;
+        let mut size = 1;
+        while size < unfit {
+            let batch = queue.iter().take(size).cloned().collect::<Vec<_>>();
+            let built = match build(&batch) {
+                Ok((result, cost)) if budget.fits(&cost) => Some((result, cost)),
+                Ok((_, cost)) => {
+                    if size == 1 {
+                        alone = Some(cost);
+                    }
+                    None
+                }
+                Err(error) if size == 1 => return Err(error),
+                Err(_) => None,
+            };
+            let known = fit.as_ref().map_or(0, |(n, _, _)| *n);
+            match built {
+                Some((result, cost)) => {
+                    let projected = match &fit {
+                        Some((n, previous, _)) => {
+                            size.saturating_add(budget.marginal_room(previous, &cost, size - n))
+                        }
+                        None => (size as f64 * budget.room(&cost)) as usize,
+                    };
+                    fit = Some((size, cost, result));
+                    size = if projected > size {
+                        projected.min(unfit - 1)
+                    } else {
+                        // Projections are only estimates: check that one more item does not fit.
+                        size + 1
+                    };
+                }
+                None => {
+                    unfit = size;
+                    size = (known + size).div_ceil(2);
+                }
+            }
+        }
+        match fit {
+            Some((size, _, result)) => {
+                let batch = queue.drain(..size).collect();
+                return Ok(Some((batch, result)));
+            }
+            // Too large on its own.
+            None => {
+                queue.pop_front();
+                log::warn!(
+                    "dropping an item too large for a transaction on its own: costs {:?}, over {budget:?}",
+                    alone.unwrap_or_default(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET: TxBudget = TxBudget {
+        max_size: 1_000,
+        max_mem: 10_000,
+        max_cpu: 1_000_000,
+    };
+
+    /// Items cost their own value in bytes, and a tenth of it in memory.
+    fn build(items: &[u64]) -> anyhow::Result<(u64, TxCost)> {
+        let size = 200 + items.iter().sum::<u64>();
+        Ok((
+            items.iter().sum(),
+            TxCost {
+                size,
+                mem: size * 10,
+                cpu: 0,
+            },
+        ))
+    }
+
+    #[test]
+    fn packs_hundreds_of_items_within_budget() {
+        let mut queue = (0..500).map(|i| 1 + i % 37).collect::<VecDeque<u64>>();
+        let total = queue.iter().sum::<u64>();
+        let mut packed = 0;
+        let mut batches = 0;
+        while let Some((batch, sum)) = take(&mut queue, &BUDGET, build).unwrap() {
+            assert!(BUDGET.fits(&build(&batch).unwrap().1));
+            if let Some(next) = queue.front() {
+                let more = batch.iter().chain([next]).copied().collect::<Vec<_>>();
+                assert!(!BUDGET.fits(&build(&more).unwrap().1), "batch is not full");
+            }
+            assert_eq!(batch.iter().sum::<u64>(), sum);
+            packed += sum;
+            batches += 1;
+        }
+        assert_eq!(packed, total);
+        assert!(batches > 1);
+    }
+
+    #[test]
+    fn drops_items_too_large_on_their_own() {
+        let mut queue = VecDeque::from([10, 900, 10]);
+        let (batch, _) = take(&mut queue, &BUDGET, build).unwrap().unwrap();
+        assert_eq!(batch, vec![10]);
+        let (batch, _) = take(&mut queue, &BUDGET, build).unwrap().unwrap();
+        assert_eq!(batch, vec![10]);
+        assert!(queue.is_empty());
+    }
+}
@@ -1,13 +1,14 @@
 use std::{cmp, collections::BTreeMap};
 
-use cardano_sdk::{Output, Value};
+use cardano_sdk::{Address, Credential, Hash, NetworkId, Output, PlutusData, Value, address::kind};
 use konduit_data::{
-    Constants, Cont, Datum, Duration, Eol, Keytag, Lock, Pending, Receipt, Secret, Stage, Tag,
-    Unpend,
+    Constants, Cont, Datum, Duration, Eol, Keytag, Lock, Pending, ProtocolVersion, Receipt,
+    Redeemer, Secret, Stage, Tag, Unpend,
 };
 
 use crate::{
-    Bounds, KONDUIT_VALIDATOR, MIN_ADA_BUFFER, StepError, StepTo, Stepped, variables::Variables,
+    Bounds, KONDUIT_VALIDATOR, KONDUIT_VALIDATORS, KonduitValidator, KonduitValidators,
+    MIN_ADA_BUFFER, StepError, StepTo, Stepped, variables::Variables,
 };
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -32,15 +33,22 @@ impl TryFrom<&Output> for Channel {
     type Error = Error;
 
     fn try_from(output: &Output) -> Result<Self, Self::Error> {
+        Self::try_from_output(output, &KONDUIT_VALIDATORS)
+    }
+}
+
+impl Channel {
+    /// Parse a channel held at any of the given validators.
+    pub fn try_from_output(output: &Output, validators: &KonduitValidators) -> Result<Self, Error> {
         let Some(address) = output.address().as_shelley() else {
             return Err(Error::ShelleyAddress);
         };
         let Some(hash) = address.payment().as_script() else {
             return Err(Error::ScriptCredential);
         };
-        if hash != KONDUIT_VALIDATOR.hash {
+        let Some(validator) = validators.get(&hash) else {
             return Err(Error::KonduitCredential);
-        }
+        };
         let Some(datum) = output.datum() else {
             return Err(Error::Datum);
         };
@@ -51,13 +59,12 @@ impl TryFrom<&Output> for Channel {
             own_hash,
             constants,
             stage,
-        } = Datum::try_from(data).map_err(|_| Error::ParseDatum)?;
-        if own_hash != KONDUIT_VALIDATOR.hash {
-            return Err(Error::OwnHash);
-        }
+        } = validator.parse_datum(data)?;
         let amount = debuffer_amount(output.value());
         let variables = Variables::new(amount, stage);
         Ok(Self {
+            own_hash,
+            version: validator.version,
             constants,
             variables,
         })
@@ -71,6 +78,10 @@ pub fn debuffer_amount(value: &cardano_sdk::Value<u64>) -> u64 {
 /// Data obtained from parsing a channel
 #[derive(Debug, Clone)]
 pub struct Channel {
+    /// Hash of the validator holding the channel.
+    own_hash: Hash<28>,
+    /// Encoding of the data the validator holding the channel expects.
+    version: ProtocolVersion,
     constants: Constants,
     variables: Variables,
 }
@@ -78,13 +89,69 @@ pub struct Channel {
 pub type SteppedElseChannel = Result<Stepped, (Box<Channel>, StepError)>;
 
 impl Channel {
+    /// A channel at the latest validator.
     pub fn new(constants: Constants, variables: Variables) -> Self {
+        Self::new_at(&KONDUIT_VALIDATOR, constants, variables)
+    }
+
+    /// A channel at the given validator.
+    pub fn new_at(
+        validator: &KonduitValidator,
+        constants: Constants,
+        variables: Variables,
+    ) -> Self {
         Self {
+            own_hash: validator.hash,
+            version: validator.version,
             constants,
             variables,
         }
     }
 
+    pub fn own_hash(&self) -> &Hash<28> {
+        &self.own_hash
+    }
+
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    /// The channel, continued with the given variables at the same validator.
+    pub fn with_variables(&self, variables: Variables) -> Self {
+        Self {
+            variables,
+            ..self.clone()
+        }
+    }
+
+    /// Payment credential of the validator holding the channel.
+    pub fn credential(&self) -> Credential {
+        Credential::from_script(self.own_hash)
+    }
+
+    /// Address of the validator holding the channel.
+    pub fn address(
+        &self,
+        network_id: NetworkId,
+        delegation: Option<&Credential>,
+    ) -> Address<kind::Shelley> {
+        let address = Address::new(network_id, self.credential());
+        match delegation {
+            Some(delegation) => address.with_delegation(delegation.clone()),
+            None => address,
+        }
+    }
+
+    /// The redeemer, encoded for the validator holding the channel.
+    pub fn redeemer(&self, redeemer: Redeemer) -> PlutusData<'static> {
+        redeemer.to_plutus_data(self.version)
+    }
+
+    /// Whether the channel is held at an older validator version, and ought to be migrated.
+    pub fn is_outdated(&self) -> bool {
+        !KONDUIT_VALIDATORS.is_latest(&self.own_hash)
+    }
+
     pub fn tag(&self) -> &Tag {
         &self.constants().tag
     }
@@ -122,7 +189,7 @@ impl Channel {
     /// As datum
     pub fn datum(&self) -> Datum {
         Datum {
-            own_hash: KONDUIT_VALIDATOR.hash,
+            own_hash: self.own_hash,
             constants: self.constants.clone(),
             stage: self.stage().clone(),
         }
@@ -309,3 +376,97 @@ impl Channel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KonduitValidator, Open, SteppedUtxo, SteppedUtxos};
+    use cardano_sdk::{Input, NetworkId, PlutusData, PlutusScript, PlutusVersion, SigningKey};
+    use konduit_data::{ProtocolVersion, Tag};
+    use std::collections::BTreeSet;
+
+    fn constants() -> Constants {
+        Constants {
+            tag: Tag::from(vec![1]),
+            add_vkey: SigningKey::from([1; 32]).to_verification_key(),
+            sub_vkey: SigningKey::from([2; 32]).to_verification_key(),
+            close_period: Duration::from_secs(3600),
+        }
+    }
+
+    /// A registry with an older version in front of the latest one.
+    fn validators() -> (KonduitValidator, KonduitValidators) {
+        let older = KonduitValidator::new(
+            ProtocolVersion::V0,
+            PlutusScript::new(PlutusVersion::V3, vec![0x01, 0x00]),
+        );
+        let validators = KonduitValidators::new(vec![older.clone(), (*KONDUIT_VALIDATOR).clone()]);
+        (older, validators)
+    }
+
+    fn output_at(validator: &KonduitValidator, own_hash: Hash<28>) -> Output {
+        let datum = Datum::new(own_hash, constants(), Stage::Opened(0, vec![]));
+        Output::new(
+            validator.address(NetworkId::TESTNET, None).into(),
+            Value::new(10_000_000 + MIN_ADA_BUFFER),
+        )
+        .with_datum(PlutusData::from(datum))
+    }
+
+    #[test]
+    fn channels_are_found_at_every_known_version() {
+        let (older, validators) = validators();
+
+        let channel =
+            Channel::try_from_output(&output_at(&older, older.hash), &validators).unwrap();
+        assert_eq!(channel.own_hash(), &older.hash);
+        assert_eq!(channel.datum().own_hash, older.hash);
+        assert_eq!(channel.amount(), 10_000_000);
+
+        let latest = Open::new(10_000_000, constants(), None).output(NetworkId::TESTNET);
+        let channel = Channel::try_from_output(&latest, &validators).unwrap();
+        assert_eq!(channel.own_hash(), &KONDUIT_VALIDATOR.hash);
+        assert!(!channel.is_outdated());
+    }
+
+    #[test]
+    fn channels_at_unknown_or_mismatching_validators_are_rejected() {
+        let (older, validators) = validators();
+
+        assert!(matches!(
+            Channel::try_from(&output_at(&older, older.hash)),
+            Err(Error::KonduitCredential)
+        ));
+        assert!(matches!(
+            Channel::try_from_output(&output_at(&older, KONDUIT_VALIDATOR.hash), &validators),
+            Err(Error::OwnHash)
+        ));
+    }
+
+    #[test]
+    fn channels_step_at_the_validator_they_were_found_at() {
+        let (older, validators) = validators();
+        let output = output_at(&older, older.hash);
+        let channel = Channel::try_from_output(&output, &validators).unwrap();
+        assert_eq!(channel.version(), older.version);
+        assert_eq!(
+            channel.address(NetworkId::TESTNET, None),
+            older.address(NetworkId::TESTNET, None)
+        );
+
+        // The registry the channel was found with is not the global one.
+        let input = Input::new(Hash::from([0; 32]), 0);
+        let stepped = channel.add(1_000_000).unwrap();
+        let steppeds = SteppedUtxos::from(vec![SteppedUtxo::new((input.clone(), output), stepped)]);
+        assert_eq!(steppeds.own_hashes(), BTreeSet::from([older.hash]));
+        assert_eq!(steppeds.inputs()[0].0, input);
+        assert_eq!(
+            steppeds.outputs()[0].datum(),
+            Some(&cardano_sdk::Datum::Inline(PlutusData::from(Datum::new(
+                older.hash,
+                constants(),
+                Stage::Opened(0, vec![])
+            ))))
+        );
+    }
+}
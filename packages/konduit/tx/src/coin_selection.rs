@@ -0,0 +1,254 @@
+//! Selection of the wallet UTxOs fuelling a transaction: covering fees and funding, and putting up
+//! collateral.
+
+use cardano_sdk::{Input, Output};
+
+use crate::{Lovelace, Utxos, fuel};
+
+/// Lovelace a dedicated collateral holds at least. Collateral must cover 150% of the fee of the
+/// transaction it backs, and a transaction filling the default budget pays about 2.5 ADA.
+pub const COLLATERAL: Lovelace = 5_000_000;
+
+/// Lovelace that inputs doubling as collateral cover at least, when there is no dedicated one.
+const SHARED_COLLATERAL: Lovelace = 3_000_000;
+
+/// Excess over the target that branch-and-bound settles for.
+const TOLERANCE: Lovelace = 1_000_000;
+
+/// Steps after which branch-and-bound gives up, and selection falls back to largest-first.
+const MAX_TRIES: usize = 100_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// Wallet UTxOs to spend.
+    pub inputs: Vec<Input>,
+    /// Wallet UTxOs put up as collateral. These are only spent if also among the inputs.
+    pub collaterals: Vec<Input>,
+}
+
+/// Pure ADA, at a key address, and holding no script: fit for fuel and collateral alike.
+pub fn is_pure_ada(output: &Output) -> bool {
+    output.value().assets().is_empty() && output.script().is_none() && is_key_locked(output)
+}
+
+/// Whether a UTxO can serve as dedicated collateral: large enough, though not so large that
+/// keeping it out of the inputs would leave much of the wallet idle.
+pub fn is_collateral(output: &Output) -> bool {
+    is_pure_ada(output) && (COLLATERAL..=2 * COLLATERAL).contains(&output.value().lovelace())
+}
+
+/// The smallest UTxO fit for dedicated collateral.
+pub fn collateral(utxos: &Utxos) -> Option<Input> {
+    utxos
+        .iter()
+        .filter(|(_, output)| is_collateral(output))
+        .min_by_key(|(_, output)| output.value().lovelace())
+        .map(|(input, _)| input.clone())
+}
+
+/// Select wallet UTxOs covering `lovelace`, along with collateral.
+///
+/// Collateral is dedicated when the wallet holds a UTxO fit for it, and enough besides it: that
+/// UTxO is then kept out of the inputs, so it outlives the transaction and may back the next one.
+/// Otherwise, the inputs double as collateral, as they always have.
+pub fn select(utxos: &Utxos, lovelace: Lovelace) -> anyhow::Result<Selection> {
+    if let Some(collateral) = collateral(utxos) {
+        let rest = utxos
+            .iter()
+            .filter(|(input, _)| *input != &collateral)
+            .map(|(input, output)| (input.clone(), output.clone()))
+            .collect::<Utxos>();
+        if let Ok(inputs) = select_inputs(&rest, lovelace) {
+            return Ok(Selection {
+                inputs,
+                collaterals: vec![collateral],
+            });
+        }
+    }
+    let collaterals = fuel::select(&spendable(utxos), lovelace.max(SHARED_COLLATERAL))?;
+    Ok(Selection {
+        inputs: collaterals.clone(),
+        collaterals,
+    })
+}
+
+/// Inputs covering the lovelace, by branch-and-bound for as little change as possible, or
+/// largest-first.
+fn select_inputs(utxos: &Utxos, lovelace: Lovelace) -> anyhow::Result<Vec<Input>> {
+    if lovelace == 0 {
+        return Ok(vec![]);
+    }
+    let candidates = spendable(utxos);
+    match branch_and_bound(&candidates, lovelace, TOLERANCE) {
+        Some(selected) => Ok(selected),
+        None => fuel::select(&candidates, lovelace),
+    }
+}
+
+/// UTxOs that may be spent for lovelace: those at key addresses, and holding no script, lest
+/// reference scripts be spent along the way.
+fn spendable(utxos: &Utxos) -> Utxos {
+    utxos
+        .iter()
+        .filter(|(_, output)| output.script().is_none() && is_key_locked(output))
+        .map(|(input, output)| (input.clone(), output.clone()))
+        .collect()
+}
+
+fn is_key_locked(output: &Output) -> bool {
+    output
+        .address()
+        .as_shelley()
+        .is_some_and(|address| address.payment().as_key().is_some())
+}
+
+/// The subset of `utxos` whose lovelace falls within `[target, target + tolerance]` with the
+/// least excess, if any is found within `MAX_TRIES` steps.
+fn branch_and_bound(utxos: &Utxos, target: Lovelace, tolerance: Lovelace) -> Option<Vec<Input>> {
+    let mut candidates = utxos
+        .iter()
+        .map(|(input, output)| (input, output.value().lovelace()))
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(_, lovelace)| std::cmp::Reverse(*lovelace));
+    let values = candidates.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+    let mut search = Search {
+        remaining: values
+            .iter()
+            .rev()
+            .scan(0, |sum: &mut Lovelace, v| {
+                *sum = sum.saturating_add(*v);
+                Some(*sum)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect(),
+        values,
+        target,
+        upper: target.saturating_add(tolerance),
+        tries: MAX_TRIES,
+        best: None,
+    };
+    search.explore(0, 0, &mut vec![]);
+    search.best.map(|(_, picked)| {
+        picked
+            .into_iter()
+            .map(|ix| candidates[ix].0.clone())
+            .collect()
+    })
+}
+
+struct Search {
+    /// Lovelace of candidates, largest first.
+    values: Vec<Lovelace>,
+    /// Lovelace of candidates from each index on.
+    remaining: Vec<Lovelace>,
+    target: Lovelace,
+    upper: Lovelace,
+    tries: usize,
+    best: Option<(Lovelace, Vec<usize>)>,
+}
+
+impl Search {
+    fn explore(&mut self, ix: usize, sum: Lovelace, picked: &mut Vec<usize>) {
+        if self.tries == 0 || self.best.as_ref().is_some_and(|(excess, _)| *excess == 0) {
+            return;
+        }
+        self.tries -= 1;
+        if sum >= self.target {
+            let excess = sum - self.target;
+            if sum <= self.upper && self.best.as_ref().is_none_or(|(best, _)| excess < *best) {
+                self.best = Some((excess, picked.clone()));
+            }
+            return;
+        }
+        if ix == self.values.len() || sum.saturating_add(self.remaining[ix]) < self.target {
+            return;
+        }
+        picked.push(ix);
+        self.explore(ix + 1, sum.saturating_add(self.values[ix]), picked);
+        picked.pop();
+        // Leaving out a candidate, then including an equal one, would explore the same sums.
+        let mut next = ix + 1;
+        while next < self.values.len() && self.values[next] == self.values[ix] {
+            next += 1;
+        }
+        self.explore(next, sum, picked);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano_sdk::{Hash, NetworkId, PlutusScript, PlutusVersion, SigningKey, Value};
+
+    fn wallet(lovelaces: &[Lovelace]) -> Utxos {
+        let address = SigningKey::from([1; 32])
+            .to_verification_key()
+            .to_address(NetworkId::TESTNET);
+        lovelaces
+            .iter()
+            .enumerate()
+            .map(|(ix, lovelace)| {
+                (
+                    Input::new(Hash::from([ix as u8; 32]), 0),
+                    Output::new(address.clone().into(), Value::new(*lovelace)),
+                )
+            })
+            .collect()
+    }
+
+    fn lovelace(utxos: &Utxos, inputs: &[Input]) -> Lovelace {
+        inputs
+            .iter()
+            .map(|input| utxos[input].value().lovelace())
+            .sum()
+    }
+
+    #[test]
+    fn sets_aside_the_smallest_fit_collateral() {
+        let utxos = wallet(&[2_000_000, 6_000_000, 50_000_000, 9_000_000, 8_000_000]);
+        let selection = select(&utxos, 8_500_000).unwrap();
+        assert_eq!(
+            selection.collaterals,
+            vec![Input::new(Hash::from([1; 32]), 0)]
+        );
+        assert!(!selection.inputs.contains(&selection.collaterals[0]));
+        // 9 ADA is within tolerance of 8.5: 50 ADA is left alone.
+        assert_eq!(lovelace(&utxos, &selection.inputs), 9_000_000);
+    }
+
+    #[test]
+    fn needs_no_inputs_when_nothing_is_owed() {
+        let utxos = wallet(&[10_000_000]);
+        let selection = select(&utxos, 0).unwrap();
+        assert!(selection.inputs.is_empty());
+        assert_eq!(selection.collaterals.len(), 1);
+    }
+
+    #[test]
+    fn inputs_double_as_collateral_in_a_lone_utxo_wallet() {
+        let utxos = wallet(&[10_000_000]);
+        let selection = select(&utxos, 3_000_000).unwrap();
+        assert_eq!(selection.inputs, selection.collaterals);
+    }
+
+    #[test]
+    fn never_spends_reference_scripts() {
+        let mut utxos = wallet(&[10_000_000]);
+        let (input, output) = utxos.pop_first().unwrap();
+        utxos.insert(
+            input,
+            output.with_plutus_script(PlutusScript::new(PlutusVersion::V3, vec![])),
+        );
+        assert!(select(&utxos, 3_000_000).is_err());
+    }
+
+    #[test]
+    fn branch_and_bound_finds_the_closest_sum() {
+        let utxos = wallet(&[7, 5, 4, 3, 1]);
+        let picked = branch_and_bound(&utxos, 9, 0).unwrap();
+        assert_eq!(lovelace(&utxos, &picked), 9);
+        assert!(branch_and_bound(&utxos, 21, 0).is_none());
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{Bounds, ChannelUtxo, NetworkParameters, SteppedUtxos, Utxos, find_reference_script};
+use crate::{
+    Bounds, Channel, ChannelUtxo, NetworkParameters, SteppedUtxos, Utxos, find_reference_scripts,
+};
+use anyhow::anyhow;
 use cardano_sdk::{
-    Address, Transaction, VerificationKey, address::kind, transaction::state::ReadyForSigning,
+    Address, Credential, Transaction, VerificationKey, address::kind,
+    transaction::state::ReadyForSigning,
 };
 use konduit_data::{Constants, Duration, Stage, Tag};
 use std::collections::BTreeMap;
@@ -28,22 +32,106 @@ pub enum Intent {
     Close,
 }
 
+/// Move a channel held at an older validator version to the latest one: the channel is closed,
+/// and one with the same adaptor, close period and amount is opened under a new tag. The new tag
+/// prevents cheques issued on the old channel from being replayed on the new one.
+///
+/// The funds of the old channel return to the wallet once it is elapsed or ended.
+pub fn migrate(channel: &Channel, tag: Tag) -> anyhow::Result<(OpenIntent, Intent)> {
+    if !channel.is_outdated() {
+        return Err(anyhow!("channel is already at the latest validator"));
+    }
+    if !channel.stage().is_opened() {
+        return Err(anyhow!("only opened channels can be migrated"));
+    }
+    if &tag == channel.tag() {
+        return Err(anyhow!("migrated channel requires a new tag"));
+    }
+    let constants = channel.constants();
+    let open = OpenIntent {
+        tag,
+        sub_vkey: constants.sub_vkey,
+        close_period: constants.close_period,
+        amount: channel.amount(),
+    };
+    Ok((open, Intent::Close))
+}
+
+/// Fuel for a consumer transaction from a wallet other than the consumer's own, such as a
+/// CIP-30 browser wallet. That wallet must then witness the transaction along with the consumer.
+pub struct Funding {
+    /// Spendable outputs of the funding wallet; only those needed are selected.
+    pub utxos: Utxos,
+    /// Where the change goes back to, as is.
+    pub change_address: Address<kind::Any>,
+}
+
+/// Build a transaction stepping the consumer's channels as per the intents, and opening new ones.
+/// Opened channels and change are delegated to the given stake credential, if any; adds and
+/// other continuing outputs keep whatever delegation their channel already has.
 pub fn tx(
     network_parameters: &NetworkParameters,
     wallet: &VerificationKey,
+    delegation: Option<&Credential>,
     opens: Vec<OpenIntent>,
     intents: BTreeMap<Tag, Intent>,
     utxos: &Utxos,
     bounds: Bounds,
 ) -> anyhow::Result<Transaction<ReadyForSigning>> {
-    let reference_utxo = find_reference_script(utxos);
+    let mut change_address = wallet.to_address(network_parameters.network_id);
+    if let Some(delegation) = delegation {
+        change_address = change_address.with_delegation(delegation.clone());
+    }
+
+    let wallet_credential = Credential::from(wallet);
+
+    let utxos_wallet = utxos
+        .iter()
+        .filter(|u| {
+            u.1.address()
+                .as_shelley()
+                .is_some_and(|address| address.payment() == wallet_credential)
+        })
+        .map(|u| (u.0.clone(), u.1.clone()))
+        .collect::<BTreeMap<_, _>>();
 
-    let change_address = wallet.to_address(network_parameters.network_id);
+    let funding = Funding {
+        utxos: utxos_wallet,
+        change_address: change_address.into(),
+    };
+
+    funded_tx(
+        network_parameters,
+        wallet,
+        delegation,
+        opens,
+        intents,
+        utxos,
+        &funding,
+        bounds,
+    )
+}
+
+/// Like [`tx`], but paying fees and new deposits from the given funding rather than from the
+/// consumer's wallet. Channels remain those of the consumer, and opened ones are still delegated
+/// to the given stake credential, if any; change isn't, since it belongs to the funding wallet.
+#[allow(clippy::too_many_arguments)]
+pub fn funded_tx(
+    network_parameters: &NetworkParameters,
+    consumer: &VerificationKey,
+    delegation: Option<&Credential>,
+    opens: Vec<OpenIntent>,
+    intents: BTreeMap<Tag, Intent>,
+    utxos: &Utxos,
+    funding: &Funding,
+    bounds: Bounds,
+) -> anyhow::Result<Transaction<ReadyForSigning>> {
+    let reference_utxos = find_reference_scripts(utxos);
 
     let consumer_channels = utxos
         .iter()
         .filter_map(|u| ChannelUtxo::try_from(u).ok())
-        .filter(|u| u.data().constants().add_vkey == *wallet);
+        .filter(|u| u.data().constants().add_vkey == *consumer);
 
     let steppeds = consumer_channels
         .filter_map(|u| match u.data().stage() {
@@ -67,25 +155,122 @@ pub fn tx(
 
     let opens = opens
         .into_iter()
-        .map(|o| crate::Open::new(o.amount, o.constant(*wallet), None))
+        .map(|o| crate::Open::new(o.amount, o.constant(*consumer), delegation.cloned()))
         .collect::<Vec<_>>();
 
-    let wallet_address: Address<kind::Any> =
-        wallet.to_address(network_parameters.network_id).into();
-
-    let fuel = utxos
-        .iter()
-        .filter(|u| u.1.address() == &wallet_address)
-        .map(|u| (u.0.clone(), u.1.clone()))
-        .to_owned()
-        .collect::<BTreeMap<_, _>>();
-
     crate::tx::tx(
         network_parameters,
-        reference_utxo.as_ref(),
-        change_address.into(),
+        &reference_utxos,
+        funding.change_address.clone(),
         steppeds,
         opens,
-        &fuel,
+        &funding.utxos,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KONDUIT_VALIDATOR, Open};
+    use cardano_sdk::{
+        Hash, Input, NetworkId, Output, PlutusScript, PlutusVersion, ProtocolParameters,
+        SigningKey, Value,
+    };
+
+    fn consumer() -> SigningKey {
+        SigningKey::from([1; 32])
+    }
+
+    fn adaptor() -> SigningKey {
+        SigningKey::from([2; 32])
+    }
+
+    /// A browser wallet, funding the consumer's transactions.
+    fn browser() -> SigningKey {
+        SigningKey::from([3; 32])
+    }
+
+    fn network_parameters() -> NetworkParameters {
+        NetworkParameters {
+            network_id: NetworkId::TESTNET,
+            protocol_parameters: ProtocolParameters::preview(),
+        }
+    }
+
+    fn open_intent(tag: u8) -> OpenIntent {
+        OpenIntent {
+            tag: Tag::from(vec![tag]),
+            sub_vkey: adaptor().to_verification_key(),
+            close_period: Duration::from_secs(3600),
+            amount: 5_000_000,
+        }
+    }
+
+    #[test]
+    fn funds_from_another_wallet_and_merges_its_witnesses() {
+        let consumer_vkey = consumer().to_verification_key();
+        let browser_address = browser()
+            .to_verification_key()
+            .to_address(NetworkId::TESTNET);
+        let reference = Output::new(
+            adaptor()
+                .to_verification_key()
+                .to_address(NetworkId::TESTNET)
+                .into(),
+            Value::new(10_000_000),
+        )
+        .with_plutus_script(PlutusScript::new(
+            PlutusVersion::V3,
+            KONDUIT_VALIDATOR.script.script().to_vec(),
+        ));
+        let channel = Open::new(10_000_000, open_intent(1).constant(consumer_vkey), None)
+            .output(NetworkId::TESTNET);
+        // The consumer's own wallet is empty: only the browser wallet can pay.
+        let utxos = Utxos::from([
+            (Input::new(Hash::from([0xaa; 32]), 0), reference),
+            (Input::new(Hash::from([0x11; 32]), 0), channel),
+        ]);
+        let funding = Funding {
+            utxos: Utxos::from([(
+                Input::new(Hash::from([0xff; 32]), 0),
+                Output::new(browser_address.clone().into(), Value::new(100_000_000)),
+            )]),
+            change_address: browser_address.into(),
+        };
+
+        let tx = funded_tx(
+            &network_parameters(),
+            &consumer_vkey,
+            None,
+            vec![open_intent(2)],
+            BTreeMap::from([(Tag::from(vec![1]), Intent::Add(2_000_000))]),
+            &utxos,
+            &funding,
+            Bounds::default(),
+        )
+        .unwrap();
+
+        let inputs = tx.inputs().collect::<Vec<_>>();
+        assert!(inputs.contains(&Input::new(Hash::from([0x11; 32]), 0)));
+        assert!(inputs.contains(&Input::new(Hash::from([0xff; 32]), 0)));
+        assert!(
+            tx.outputs()
+                .any(|output| output.address() == &funding.change_address),
+            "change goes back to the funding wallet"
+        );
+
+        // The consumer signs its copy; the browser wallet returns its witnesses to merge.
+        let mut signed = tx.clone();
+        signed.sign(&consumer());
+        let mut witnessed = tx;
+        witnessed.sign(&browser());
+        signed.merge_witness_set(&witnessed.witness_set()).unwrap();
+        let signers = signed
+            .vkey_witnesses()
+            .map(|(vkey, _)| vkey)
+            .collect::<Vec<_>>();
+        assert_eq!(signers.len(), 2);
+        assert!(signers.contains(&consumer_vkey));
+        assert!(signers.contains(&browser().to_verification_key()));
+    }
+}
@@ -1,4 +1,6 @@
-use cardano_sdk::{Input, Output};
+use cardano_sdk::{
+    Address, Input, Output, Transaction, address::kind, transaction::state::ReadyForSigning,
+};
 
 use crate::{Lovelace, Utxos};
 
@@ -46,3 +48,24 @@ pub fn select_no_script(utxos: &Utxos, amount: Lovelace) -> anyhow::Result<Vec<I
         .collect();
     select(&utxos, amount)
 }
+
+/// The fuel left once a transaction goes through: its inputs spent, and its outputs back to the
+/// wallet made available, so that further transactions can be chained before it is confirmed.
+pub fn after(
+    fuel: &Utxos,
+    tx: &Transaction<ReadyForSigning>,
+    wallet: &Address<kind::Any>,
+) -> Utxos {
+    let spent = tx.inputs().collect::<Vec<_>>();
+    let id = tx.id();
+    fuel.iter()
+        .filter(|(input, _)| !spent.contains(input))
+        .map(|(input, output)| (input.clone(), output.clone()))
+        .chain(
+            tx.outputs()
+                .enumerate()
+                .filter(|(_, output)| output.address() == wallet)
+                .map(|(ix, output)| (Input::new(id, ix as u64), output)),
+        )
+        .collect()
+}
@@ -0,0 +1,840 @@
+use std::{collections::BTreeMap, fmt};
+
+use cardano_sdk::{
+    Address, Hash, Input, Output, ProtocolParameters, Transaction, VerificationKey, address::kind,
+    transaction::state::ReadyForSigning,
+};
+use konduit_data::{
+    Cheque, Cont, Eol, L1Channel, ProtocolVersion, Redeemer, Squash, Stage, Step, Tag, Unpend,
+};
+use serde::{Serialize, Serializer};
+
+use crate::{Channel, Utxos};
+
+/// A transaction told in Konduit terms: the channels it steps and how, those it opens, and whether
+/// the squashes, cheques and signatures it carries check out. For operators and tooling to make
+/// sense of a transaction that failed, or looks odd.
+///
+/// Channels are only recognised among the inputs that the given utxos resolve; others are listed
+/// as unresolved.
+pub fn inspect(
+    transaction: &Transaction<ReadyForSigning>,
+    utxos: &Utxos,
+    protocol_parameters: &ProtocolParameters,
+) -> Inspection {
+    let id = transaction.id();
+    let slot_time = |slot| SlotTime {
+        slot,
+        posix: protocol_parameters.slot_to_posix(slot).as_millis() as u64,
+    };
+
+    let spent = spent_channels(transaction, utxos);
+    let mut produced = transaction
+        .outputs()
+        .filter_map(|output| Channel::try_from(&output).ok())
+        .map(Some)
+        .collect::<Vec<_>>();
+
+    let spent = spent
+        .into_iter()
+        .map(|(input, channel, redeemer, step)| {
+            // Continuing outputs come in the order of the channels they continue.
+            let after = matches!(step, Some(Step::Cont(_)))
+                .then(|| {
+                    produced.iter_mut().find_map(|candidate| {
+                        candidate.take_if(|c| {
+                            c.own_hash() == channel.own_hash() && c.tag() == channel.tag()
+                        })
+                    })
+                })
+                .flatten()
+                .map(|after| L1Channel {
+                    amount: after.amount(),
+                    stage: after.stage().clone(),
+                });
+            SpentChannel {
+                input,
+                tag: channel.tag().clone(),
+                version: channel.version(),
+                outdated: channel.is_outdated(),
+                redeemer,
+                step: step.map(|step| StepInspection::new(step, &channel)),
+                change: after.as_ref().map_or(0, |after| after.amount as i64)
+                    - channel.amount() as i64,
+                before: L1Channel {
+                    amount: channel.amount(),
+                    stage: channel.stage().clone(),
+                },
+                after,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let opened = produced
+        .into_iter()
+        .flatten()
+        .map(|channel| OpenedChannel {
+            tag: channel.tag().clone(),
+            version: channel.version(),
+            channel: L1Channel {
+                amount: channel.amount(),
+                stage: channel.stage().clone(),
+            },
+        })
+        .collect();
+
+    let inputs = transaction
+        .inputs()
+        .filter(|input| spent.iter().all(|channel| &channel.input != input))
+        .map(|input| OtherInput {
+            output: utxos.get(&input).map(OtherOutput::from),
+            input,
+        })
+        .collect();
+
+    let outputs = transaction
+        .outputs()
+        .filter(|output| Channel::try_from(output).is_err())
+        .map(|output| OtherOutput::from(&output))
+        .collect();
+
+    let signatures = transaction
+        .vkey_witnesses()
+        .map(|(verification_key, signature)| Signatory {
+            valid: verification_key.verify(id, &signature),
+            verification_key,
+        })
+        .collect::<Vec<_>>();
+
+    let unsigned = transaction
+        .specified_signatories()
+        .filter(|hash| {
+            signatures
+                .iter()
+                .all(|signatory| &Hash::<28>::new(signatory.verification_key) != hash)
+        })
+        .collect();
+
+    Inspection {
+        id,
+        fee: transaction.fee(),
+        validity: Validity {
+            lower: transaction.validity_start().map(slot_time),
+            upper: transaction.validity_end().map(slot_time),
+        },
+        message: transaction.message().map(|chunks| chunks.concat()),
+        spent,
+        opened,
+        inputs,
+        outputs,
+        signatures,
+        unsigned,
+    }
+}
+
+/// Channels spent, in order, with the redeemer each carries and the step it takes. Each validator
+/// version runs once: its first input carries the steps of all its channels, the others defer.
+fn spent_channels(
+    transaction: &Transaction<ReadyForSigning>,
+    utxos: &Utxos,
+) -> Vec<(Input, Channel, RedeemerKind, Option<Step>)> {
+    let inputs = transaction.inputs().collect::<Vec<_>>();
+    let channels = inputs
+        .iter()
+        .filter_map(|input| {
+            let channel = Channel::try_from(utxos.get(input)?).ok()?;
+            Some((input.clone(), channel))
+        })
+        .collect::<Vec<_>>();
+
+    let mut redeemers = BTreeMap::new();
+    let mut steps = BTreeMap::new();
+    for (pointer, data) in transaction.redeemers() {
+        if !pointer.is_spend() {
+            continue;
+        }
+        let Some(input) = inputs.get(pointer.as_index() as usize) else {
+            continue;
+        };
+        let Some((_, channel)) = channels.iter().find(|(i, _)| i == input) else {
+            continue;
+        };
+        let redeemer = match Redeemer::from_plutus_data(&data, channel.version()) {
+            Ok(Redeemer::Defer) => RedeemerKind::Defer,
+            Ok(Redeemer::Mutual) => RedeemerKind::Mutual,
+            Ok(Redeemer::Main(main)) => {
+                let group = channels
+                    .iter()
+                    .filter(|(_, other)| other.own_hash() == channel.own_hash());
+                steps.extend(group.map(|(input, _)| input.clone()).zip(main));
+                RedeemerKind::Main
+            }
+            Err(_) => RedeemerKind::Malformed,
+        };
+        redeemers.insert(input.clone(), redeemer);
+    }
+
+    channels
+        .into_iter()
+        .map(|(input, channel)| {
+            let redeemer = redeemers.remove(&input).unwrap_or(RedeemerKind::Missing);
+            let step = steps.remove(&input);
+            (input, channel, redeemer, step)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Inspection {
+    #[serde(serialize_with = "display")]
+    pub id: Hash<32>,
+    pub fee: u64,
+    pub validity: Validity,
+    /// The CIP-20 message, if any; Konduit transactions name the steps they take there.
+    pub message: Option<String>,
+    /// Channels spent, in input order.
+    pub spent: Vec<SpentChannel>,
+    /// Channels produced that continue none of those spent.
+    pub opened: Vec<OpenedChannel>,
+    /// Inputs other than channels.
+    pub inputs: Vec<OtherInput>,
+    /// Outputs other than channels.
+    pub outputs: Vec<OtherOutput>,
+    pub signatures: Vec<Signatory>,
+    /// Specified signatories that have not signed yet.
+    #[serde(serialize_with = "display_all")]
+    pub unsigned: Vec<Hash<28>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Validity {
+    /// First slot the transaction is valid in.
+    pub lower: Option<SlotTime>,
+    /// First slot the transaction is no longer valid in.
+    pub upper: Option<SlotTime>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SlotTime {
+    pub slot: u64,
+    /// Start of the slot, in milliseconds since the epoch.
+    pub posix: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpentChannel {
+    #[serde(serialize_with = "out_ref")]
+    pub input: Input,
+    #[serde(serialize_with = "display")]
+    pub tag: Tag,
+    #[serde(serialize_with = "debug")]
+    pub version: ProtocolVersion,
+    /// Whether the channel is held at an older validator version.
+    pub outdated: bool,
+    pub redeemer: RedeemerKind,
+    /// The step taken, as per the redeemer of the first channel at the same validator.
+    pub step: Option<StepInspection>,
+    pub before: L1Channel,
+    /// The continuing channel, if any.
+    pub after: Option<L1Channel>,
+    /// Change in amount: what's added when positive; what's subbed, or otherwise leaves the
+    /// channel, when negative.
+    pub change: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenedChannel {
+    #[serde(serialize_with = "display")]
+    pub tag: Tag,
+    #[serde(serialize_with = "debug")]
+    pub version: ProtocolVersion,
+    #[serde(flatten)]
+    pub channel: L1Channel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedeemerKind {
+    Defer,
+    Main,
+    Mutual,
+    /// No redeemer points at the input.
+    Missing,
+    /// The redeemer isn't one the validator knows of.
+    Malformed,
+}
+
+impl RedeemerKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Defer => "defer",
+            Self::Main => "main",
+            Self::Mutual => "mutual",
+            Self::Missing => "missing",
+            Self::Malformed => "malformed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StepInspection {
+    Add,
+    Sub {
+        squash: SquashInspection,
+        unlocked: Vec<ChequeInspection>,
+    },
+    Close,
+    Respond {
+        squash: SquashInspection,
+        cheques: Vec<ChequeInspection>,
+    },
+    Unlock {
+        unpends: Vec<UnpendKind>,
+    },
+    Expire {
+        unpends: Vec<UnpendKind>,
+    },
+    End,
+    Elapse,
+}
+
+impl StepInspection {
+    /// Squashes and cheques are checked against the consumer's key, which signs them.
+    fn new(step: Step, channel: &Channel) -> Self {
+        let squash = |squash: &Squash| SquashInspection::new(squash, channel);
+        let unpends = |unpends: Vec<Unpend>| unpends.iter().map(UnpendKind::from).collect();
+        match step {
+            Step::Cont(Cont::Add) => Self::Add,
+            Step::Cont(Cont::Sub(s, unlocked)) => Self::Sub {
+                squash: squash(&s),
+                unlocked: unlocked
+                    .into_iter()
+                    .map(|unlocked| ChequeInspection::new(&Cheque::from(unlocked), channel))
+                    .collect(),
+            },
+            Step::Cont(Cont::Close) => Self::Close,
+            Step::Cont(Cont::Respond(s, cheques)) => Self::Respond {
+                squash: squash(&s),
+                cheques: cheques
+                    .iter()
+                    .map(|cheque| ChequeInspection::new(cheque, channel))
+                    .collect(),
+            },
+            Step::Cont(Cont::Unlock(u)) => Self::Unlock {
+                unpends: unpends(u),
+            },
+            Step::Cont(Cont::Expire(u)) => Self::Expire {
+                unpends: unpends(u),
+            },
+            Step::Eol(Eol::End) => Self::End,
+            Step::Eol(Eol::Elapse) => Self::Elapse,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Sub { .. } => "sub",
+            Self::Close => "close",
+            Self::Respond { .. } => "respond",
+            Self::Unlock { .. } => "unlock",
+            Self::Expire { .. } => "expire",
+            Self::End => "end",
+            Self::Elapse => "elapse",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SquashInspection {
+    pub amount: u64,
+    pub index: u64,
+    pub exclude: Vec<u64>,
+    pub valid: bool,
+}
+
+impl SquashInspection {
+    fn new(squash: &Squash, channel: &Channel) -> Self {
+        Self {
+            amount: squash.amount(),
+            index: squash.index(),
+            exclude: squash.body.exclude.0.clone(),
+            valid: squash.verify(&channel.constants().add_vkey, channel.tag()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChequeInspection {
+    pub index: u64,
+    pub amount: u64,
+    /// In milliseconds since the epoch.
+    pub timeout: u64,
+    /// Whether the cheque comes with its secret.
+    pub unlocked: bool,
+    pub valid: bool,
+}
+
+impl ChequeInspection {
+    fn new(cheque: &Cheque, channel: &Channel) -> Self {
+        Self {
+            index: cheque.index(),
+            amount: cheque.amount(),
+            timeout: cheque.timeout().as_millis() as u64,
+            unlocked: matches!(cheque, Cheque::Unlocked(_)),
+            valid: cheque.verify(&channel.constants().add_vkey, channel.tag()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnpendKind {
+    Continue,
+    Expire,
+    Unlock,
+}
+
+impl From<&Unpend> for UnpendKind {
+    fn from(unpend: &Unpend) -> Self {
+        match unpend {
+            Unpend::Continue => Self::Continue,
+            Unpend::Expire => Self::Expire,
+            Unpend::Unlock(_) => Self::Unlock,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OtherInput {
+    #[serde(serialize_with = "out_ref")]
+    pub input: Input,
+    /// The output spent, when resolved.
+    pub output: Option<OtherOutput>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OtherOutput {
+    #[serde(serialize_with = "display")]
+    pub address: Address<kind::Any>,
+    pub lovelace: u64,
+    /// Quantities, by `<policy>.<asset name>` in hex.
+    pub assets: BTreeMap<String, u64>,
+}
+
+impl From<&Output> for OtherOutput {
+    fn from(output: &Output) -> Self {
+        let value = output.value();
+        Self {
+            address: output.address().clone(),
+            lovelace: value.lovelace(),
+            assets: value
+                .assets()
+                .iter()
+                .flat_map(|(policy, assets)| {
+                    assets.iter().map(move |(asset_name, quantity)| {
+                        (format!("{policy}.{}", hex::encode(asset_name)), *quantity)
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Signatory {
+    #[serde(serialize_with = "display")]
+    pub verification_key: VerificationKey,
+    /// Whether the signature is that of the transaction id.
+    pub valid: bool,
+}
+
+// ---- Rendering
+
+impl fmt::Display for Inspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "transaction {}", self.id)?;
+        writeln!(f, "  fee: {} lovelace", self.fee)?;
+        if self.validity.lower.is_some() || self.validity.upper.is_some() {
+            let bound = |bound: Option<SlotTime>, unbounded: &str| match bound {
+                Some(bound) => bound.to_string(),
+                None => unbounded.to_string(),
+            };
+            writeln!(
+                f,
+                "  validity: [{}; {}[",
+                bound(self.validity.lower, "-∞"),
+                bound(self.validity.upper, "+∞"),
+            )?;
+        }
+        if let Some(message) = &self.message {
+            writeln!(f, "  message: {message}")?;
+        }
+
+        if !self.spent.is_empty() {
+            writeln!(f, "channels spent:")?;
+            for channel in &self.spent {
+                channel.fmt(f)?;
+            }
+        }
+
+        if !self.opened.is_empty() {
+            writeln!(f, "channels opened:")?;
+            for channel in &self.opened {
+                writeln!(
+                    f,
+                    "  {} ({:?}): {}",
+                    channel.tag,
+                    channel.version,
+                    ChannelLine(&channel.channel),
+                )?;
+            }
+        }
+
+        writeln!(f, "other inputs:")?;
+        for OtherInput { input, output } in &self.inputs {
+            match output {
+                Some(output) => writeln!(f, "  {}: {output}", OutRef(input))?,
+                None => writeln!(f, "  {}: unresolved", OutRef(input))?,
+            }
+        }
+
+        writeln!(f, "other outputs:")?;
+        for output in &self.outputs {
+            writeln!(f, "  {output}")?;
+        }
+
+        if self.signatures.is_empty() {
+            writeln!(f, "signatures: none")?;
+        } else {
+            writeln!(f, "signatures:")?;
+            for signatory in &self.signatures {
+                write!(f, "  {}", signatory.verification_key)?;
+                writeln!(f, "{}", if signatory.valid { "" } else { " INVALID" })?;
+            }
+        }
+        for hash in &self.unsigned {
+            writeln!(f, "  missing: {hash}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for SpentChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  {} ({:?}{}) at {}",
+            self.tag,
+            self.version,
+            if self.outdated { ", outdated" } else { "" },
+            OutRef(&self.input),
+        )?;
+        match &self.step {
+            Some(step) => writeln!(f, "    {} -> {}", self.redeemer.name(), step.name())?,
+            None => writeln!(f, "    {} -> (no step)", self.redeemer.name())?,
+        }
+        writeln!(f, "    before: {}", ChannelLine(&self.before))?;
+        if let Some(after) = &self.after {
+            writeln!(f, "    after: {}", ChannelLine(after))?;
+        }
+        writeln!(f, "    change: {:+} lovelace", self.change)?;
+        match &self.step {
+            Some(StepInspection::Sub { squash, unlocked }) => {
+                writeln!(f, "    {squash}")?;
+                for cheque in unlocked {
+                    writeln!(f, "    {cheque}")?;
+                }
+            }
+            Some(StepInspection::Respond { squash, cheques }) => {
+                writeln!(f, "    {squash}")?;
+                for cheque in cheques {
+                    writeln!(f, "    {cheque}")?;
+                }
+            }
+            Some(StepInspection::Unlock { unpends } | StepInspection::Expire { unpends }) => {
+                writeln!(f, "    unpends: {unpends:?}")?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// An input as `<transaction id>#<output index>`.
+struct OutRef<'a>(&'a Input);
+
+impl fmt::Display for OutRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.0.transaction_id(), self.0.output_index())
+    }
+}
+
+struct ChannelLine<'a>(&'a L1Channel);
+
+impl fmt::Display for ChannelLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let L1Channel { amount, stage } = self.0;
+        write!(f, "{amount} lovelace, {}", stage.label().to_lowercase())?;
+        match stage {
+            Stage::Opened(subbed, useds) => {
+                write!(f, " (subbed {subbed}, {} used)", useds.len())
+            }
+            Stage::Closed(subbed, useds, elapse_at) => write!(
+                f,
+                " (subbed {subbed}, {} used, elapses at {})",
+                useds.len(),
+                posix(elapse_at.as_millis() as u64),
+            ),
+            Stage::Responded(pendings_amount, pendings) => {
+                write!(f, " ({pendings_amount} pending in {})", pendings.len())
+            }
+        }
+    }
+}
+
+impl fmt::Display for SlotTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "slot {} ({})", self.slot, posix(self.posix))
+    }
+}
+
+impl fmt::Display for SquashInspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "squash: {} up to #{}", self.amount, self.index)?;
+        if !self.exclude.is_empty() {
+            write!(f, " but {:?}", self.exclude)?;
+        }
+        write!(f, ", {}", validity(self.valid))
+    }
+}
+
+impl fmt::Display for ChequeInspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cheque #{}: {}, {}, until {}, {}",
+            self.index,
+            self.amount,
+            if self.unlocked { "unlocked" } else { "locked" },
+            posix(self.timeout),
+            validity(self.valid),
+        )
+    }
+}
+
+impl fmt::Display for OtherOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} lovelace", self.lovelace)?;
+        for (asset, quantity) in &self.assets {
+            write!(f, " + {quantity} {asset}")?;
+        }
+        write!(f, ", at {}", self.address)
+    }
+}
+
+fn validity(valid: bool) -> &'static str {
+    if valid {
+        "signature valid"
+    } else {
+        "signature INVALID"
+    }
+}
+
+/// A posix time in milliseconds, as a UTC date.
+fn posix(millis: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis as i64).map_or_else(
+        || format!("{millis}ms"),
+        |time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+    )
+}
+
+// ---- Serializing
+
+fn display<T: fmt::Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn display_all<T: fmt::Display, S: Serializer>(
+    values: &[T],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(values.iter().map(ToString::to_string))
+}
+
+fn out_ref<S: Serializer>(input: &Input, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&OutRef(input))
+}
+
+fn debug<T: fmt::Debug, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{value:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        KONDUIT_VALIDATOR, NetworkParameters, Open, TxBudget,
+        adaptor::{self, AdaptorPreferences},
+    };
+    use cardano_sdk::{NetworkId, PlutusScript, PlutusVersion, SigningKey, Value};
+    use konduit_data::{Constants, Duration, Indexes, Keytag, Receipt, SquashBody};
+
+    fn adaptor() -> SigningKey {
+        SigningKey::from([2; 32])
+    }
+
+    fn consumer() -> SigningKey {
+        SigningKey::from([1; 32])
+    }
+
+    fn constants(i: u8, consumer: &SigningKey) -> Constants {
+        Constants {
+            tag: Tag::from(vec![i]),
+            add_vkey: consumer.to_verification_key(),
+            sub_vkey: adaptor().to_verification_key(),
+            close_period: Duration::from_secs(3600),
+        }
+    }
+
+    fn channel(i: u8, consumer: &SigningKey) -> (Input, Output) {
+        (
+            Input::new(Hash::from([0x11; 32]), i as u64),
+            Open::new(10_000_000, constants(i, consumer), None).output(NetworkId::TESTNET),
+        )
+    }
+
+    /// Two opened channels, each owing the adaptor a squash, along with the reference script and
+    /// the adaptor's fuel.
+    fn utxos() -> (Utxos, BTreeMap<Keytag, Receipt>) {
+        let wallet = adaptor()
+            .to_verification_key()
+            .to_address(NetworkId::TESTNET);
+        let mut utxos = Utxos::from([
+            (
+                Input::new(Hash::from([0xaa; 32]), 0),
+                Output::new(wallet.clone().into(), Value::new(10_000_000)).with_plutus_script(
+                    PlutusScript::new(
+                        PlutusVersion::V3,
+                        KONDUIT_VALIDATOR.script.script().to_vec(),
+                    ),
+                ),
+            ),
+            (
+                Input::new(Hash::from([0xff; 32]), 0),
+                Output::new(wallet.into(), Value::new(1_000_000_000)),
+            ),
+        ]);
+        let mut receipts = BTreeMap::new();
+        for i in 0..2 {
+            let constants = constants(i, &consumer());
+            let squash = Squash::make(
+                &consumer(),
+                &constants.tag,
+                SquashBody::new_no_verify(3_000_000, 1, Indexes::default()),
+            );
+            receipts.insert(
+                Keytag::new(constants.add_vkey, constants.tag.clone()),
+                Receipt::new(squash),
+            );
+            utxos.extend([channel(i, &consumer())]);
+        }
+        (utxos, receipts)
+    }
+
+    fn sub() -> (Transaction<ReadyForSigning>, Utxos) {
+        let (utxos, receipts) = utxos();
+        let mut txs = adaptor::txs(
+            &NetworkParameters {
+                network_id: NetworkId::TESTNET,
+                protocol_parameters: ProtocolParameters::preview(),
+            },
+            &AdaptorPreferences {
+                min_single: 1,
+                min_total: 1,
+                budget: TxBudget::default(),
+            },
+            &adaptor().to_verification_key(),
+            &receipts,
+            &utxos,
+            &Duration::from_secs(1_800_000_000),
+        )
+        .unwrap();
+        assert_eq!(txs.len(), 1);
+        (txs.remove(0), utxos)
+    }
+
+    #[test]
+    fn tells_the_steps_of_each_channel() {
+        let (mut transaction, mut utxos) = sub();
+        transaction.sign(&adaptor());
+        // As if the second channel were someone else's, whom its squash isn't from.
+        utxos.extend([channel(1, &SigningKey::from([3; 32]))]);
+
+        let inspection = inspect(&transaction, &utxos, &ProtocolParameters::preview());
+
+        assert_eq!(inspection.spent.len(), 2);
+        assert_eq!(
+            inspection
+                .spent
+                .iter()
+                .map(|channel| channel.redeemer)
+                .collect::<Vec<_>>(),
+            vec![RedeemerKind::Main, RedeemerKind::Defer],
+        );
+        for (channel, valid) in inspection.spent.iter().zip([true, false]) {
+            let Some(StepInspection::Sub { squash, unlocked }) = &channel.step else {
+                panic!("expected a sub, got {:?}", channel.step);
+            };
+            assert_eq!(squash.amount, 3_000_000);
+            assert_eq!(squash.valid, valid);
+            assert!(unlocked.is_empty());
+            assert_eq!(channel.before.amount, 10_000_000);
+            assert_eq!(
+                channel.after.as_ref().map(|after| after.amount),
+                Some(7_000_000)
+            );
+            assert_eq!(channel.change, -3_000_000);
+        }
+        assert!(inspection.opened.is_empty());
+        assert!(
+            inspection
+                .signatures
+                .iter()
+                .all(|signatory| signatory.valid)
+        );
+        assert!(inspection.unsigned.is_empty());
+
+        let upper = inspection.validity.upper.expect("subs have an upper bound");
+        assert_eq!(
+            ProtocolParameters::preview()
+                .posix_to_slot(std::time::Duration::from_millis(upper.posix)),
+            upper.slot
+        );
+
+        let text = inspection.to_string();
+        assert!(text.contains("main -> sub"), "{text}");
+        assert!(text.contains("signature INVALID"), "{text}");
+    }
+
+    #[test]
+    fn lists_unresolved_inputs_and_missing_signatures() {
+        let (transaction, _) = sub();
+
+        let inspection = inspect(&transaction, &Utxos::new(), &ProtocolParameters::preview());
+
+        assert!(inspection.spent.is_empty());
+        assert!(inspection.inputs.iter().all(|input| input.output.is_none()));
+        assert_eq!(
+            inspection.unsigned,
+            vec![Hash::<28>::new(adaptor().to_verification_key())]
+        );
+
+        let json = serde_json::to_value(&inspection).unwrap();
+        assert_eq!(json["id"], transaction.id().to_string());
+        assert_eq!(json["spent"], serde_json::json!([]));
+        assert!(json["inputs"][0]["output"].is_null());
+    }
+}
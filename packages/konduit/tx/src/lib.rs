@@ -43,12 +43,25 @@ pub use variables::Variables;
 mod network_parameters;
 pub use network_parameters::NetworkParameters;
 
+pub mod coin_selection;
 /// Fuel / wallet utxos
 pub mod fuel;
 
 /// Tx
 pub mod tx;
 
+/// Splitting of steps across transactions
+pub mod batch;
+pub use batch::{TxBudget, TxCost};
+
+/// Decoding of transactions back into Konduit terms
+pub mod inspect;
+pub use inspect::{Inspection, inspect};
+
+/// Transactions submitted, and built upon, before being confirmed
+pub mod unconfirmed;
+pub use unconfirmed::Unconfirmed;
+
 pub mod adaptor;
 pub use adaptor::InsufficientTotalGain;
 pub mod admin;
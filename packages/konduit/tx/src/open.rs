@@ -1,7 +1,7 @@
 use cardano_sdk::{Address, Credential, NetworkId, Output, PlutusData, address::kind};
 use konduit_data::{Constants, Stage};
 
-use crate::{Channel, Variables, konduit_address};
+use crate::{Channel, Variables};
 
 #[derive()]
 pub struct Open(Channel, Option<Credential>);
@@ -30,7 +30,7 @@ impl Open {
     }
 
     pub fn address(&self, network_id: NetworkId) -> Address<kind::Shelley> {
-        konduit_address(network_id, self.delegation())
+        self.data().address(network_id, self.delegation())
     }
 
     pub fn output(&self, network_id: NetworkId) -> Output {
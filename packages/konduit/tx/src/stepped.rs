@@ -1,3 +1,5 @@
+use konduit_data::{Cont, Duration, Stage};
+
 use crate::{Bounds, Channel, StepTo};
 
 /// Encapsulate the current channel data, together with a valid stepping.
@@ -32,11 +34,28 @@ impl Stepped {
     pub fn cont_data(&self) -> Option<Channel> {
         self.step_to
             .variables()
-            .map(|v| Channel::new(self.channel.constants().clone(), v.clone()))
+            .map(|v| self.channel.with_variables(v.clone()))
     }
 
     pub fn gain(&self) -> i64 {
         let cont_amount = self.step_to.variables().map_or(0, |v| v.amount());
         self.channel().amount() as i64 - cont_amount as i64
     }
+
+    /// The earliest timeout among the cheques the step settles: past it, the adaptor can no longer
+    /// claim them. `None` for steps whose gain does not expire.
+    pub fn urgency(&self) -> Option<Duration> {
+        let StepTo::Cont(cont, _) = &self.step_to else {
+            return None;
+        };
+        match cont {
+            Cont::Sub(_, unlockeds) => unlockeds.iter().map(|u| *u.timeout()).min(),
+            Cont::Respond(_, cheques) => cheques.iter().map(|c| *c.timeout()).min(),
+            Cont::Unlock(_) => match self.channel.stage() {
+                Stage::Responded(_, pendings) => pendings.iter().map(|p| p.timeout).min(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
@@ -1,6 +1,6 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use cardano_sdk::{Hash, Input, Output, VerificationKey};
+use cardano_sdk::{Hash, Input, Output, PlutusData, VerificationKey};
 use konduit_data::{Redeemer, Step};
 
 use crate::{Bounds, SteppedUtxo};
@@ -17,17 +17,27 @@ impl From<Vec<SteppedUtxo>> for SteppedUtxos {
 }
 
 impl SteppedUtxos {
-    pub fn inputs(&self) -> Vec<(Input, Redeemer)> {
+    /// Inputs with their redeemers, encoded for the validator holding them.
+    pub fn inputs(&self) -> Vec<(Input, PlutusData<'static>)> {
         self.0
             .iter()
             .enumerate()
-            .map(|(i, val)| (val.input().clone(), self.redeemer(i)))
+            .map(|(i, val)| {
+                let channel = val.data().channel();
+                (val.input().clone(), channel.redeemer(self.redeemer(i)))
+            })
             .collect::<Vec<_>>()
     }
 
+    /// Each validator version runs on its own: the first of its inputs carries all of its steps,
+    /// the others defer to it.
     fn redeemer(&self, index: usize) -> Redeemer {
-        if index == 0 {
-            Redeemer::Main(self.steps())
+        let own_hash = self.0[index].data().channel().own_hash();
+        let is_first = self.0[..index]
+            .iter()
+            .all(|x| x.data().channel().own_hash() != own_hash);
+        if is_first {
+            Redeemer::Main(self.steps_at(own_hash))
         } else {
             Redeemer::Defer
         }
@@ -40,6 +50,23 @@ impl SteppedUtxos {
             .collect::<Vec<_>>()
     }
 
+    /// Steps of the channels held at the given validator.
+    pub fn steps_at(&self, own_hash: &Hash<28>) -> Vec<Step> {
+        self.0
+            .iter()
+            .filter(|x| x.data().channel().own_hash() == own_hash)
+            .map(|x| x.data().step_to().step())
+            .collect::<Vec<_>>()
+    }
+
+    /// Hashes of the validators holding the channels stepped.
+    pub fn own_hashes(&self) -> BTreeSet<Hash<28>> {
+        self.0
+            .iter()
+            .map(|x| *x.data().channel().own_hash())
+            .collect()
+    }
+
     pub fn utxos(&self) -> BTreeMap<Input, Output> {
         self.0
             .iter()
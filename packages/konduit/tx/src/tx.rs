@@ -1,45 +1,56 @@
-use std::{cmp, collections::BTreeMap};
+use std::collections::{BTreeMap, BTreeSet};
 
 use cardano_sdk::{
-    Address, ChangeStrategy, PlutusData, SlotBound, Transaction, address::kind,
+    Address, ChangeStrategy, SlotBound, Transaction, address::kind,
     transaction::state::ReadyForSigning,
 };
-use konduit_data::Duration;
+use konduit_data::{Cont, Duration, Eol, Step};
 
-use crate::{Lovelace, NetworkParameters, Open, SteppedUtxos, Utxo, Utxos, fuel};
+use crate::{
+    Lovelace, NetworkParameters, Open, SteppedUtxos, Utxo, Utxos, coin_selection,
+    find_reference_script_of,
+};
 
 pub const FEE_BUFFER: Lovelace = 3_000_000;
 
 pub fn tx(
     network_parameters: &NetworkParameters,
-    reference_utxo: Option<&Utxo>,
+    reference_utxos: &Utxos,
     change_address: Address<kind::Any>,
     steppeds: SteppedUtxos,
     opens: Vec<Open>,
     fuel: &Utxos,
 ) -> anyhow::Result<Transaction<ReadyForSigning>> {
     let network_id = network_parameters.network_id;
-    let reference_inputs: Vec<_> = reference_utxo.iter().map(|x| x.0.clone()).collect();
-    if !steppeds.inputs().is_empty() && reference_inputs.is_empty() {
-        return Err(anyhow::anyhow!(
-            "Reference script required when stepping channels"
-        ));
-    }
+    let reference_utxos = steppeds
+        .own_hashes()
+        .into_iter()
+        .map(|hash| {
+            find_reference_script_of(&hash, reference_utxos).ok_or_else(|| {
+                anyhow::anyhow!("Reference script required when stepping channels (hash={hash})")
+            })
+        })
+        .collect::<anyhow::Result<Vec<Utxo>>>()?;
+    let reference_inputs: Vec<_> = reference_utxos.iter().map(|x| x.0.clone()).collect();
     let gain = steppeds.gain() - opens.iter().map(|x| x.buffered_amount()).sum::<u64>() as i64;
-    let fuel_amount = cmp::max(FEE_BUFFER, FEE_BUFFER.saturating_sub_signed(gain));
-    let fuel_inputs = fuel::select(fuel, fuel_amount)?;
-    let inputs = steppeds
-        .inputs()
-        .iter()
-        .map(|i| (i.0.clone(), Some(PlutusData::from(i.1.clone()))))
-        .chain(fuel_inputs.iter().map(|i| (i.clone(), None)))
-        .collect::<Vec<_>>();
     let outputs: Vec<_> = steppeds
         .outputs()
         .into_iter()
         .chain(opens.iter().map(|o| o.output(network_id)))
         .collect();
-    let collaterals = fuel_inputs.clone();
+    // Fees are paid out of the gain, when there is enough of it; collateral is set aside anyway.
+    let coin_selection::Selection {
+        inputs: fuel_inputs,
+        collaterals,
+    } = coin_selection::select(fuel, FEE_BUFFER.saturating_sub_signed(gain))?;
+    let fuel_inputs = fuel_inputs.into_iter().collect::<BTreeSet<_>>();
+    let inputs = steppeds
+        .inputs()
+        .iter()
+        .map(|i| (i.0.clone(), Some(i.1.clone())))
+        .chain(fuel_inputs.iter().map(|i| (i.clone(), None)))
+        .collect::<Vec<_>>();
+    let message = message(&steppeds.steps(), opens.len());
     let specified_signatories = steppeds.specified_signatories();
     let bounds = steppeds.bounds();
 
@@ -57,7 +68,7 @@ pub fn tx(
         .iter()
         .chain(fuel.iter())
         .map(|t| (t.0.clone(), t.1.clone()))
-        .chain(reference_utxo.iter().map(|i| (i.0.clone(), i.1.clone())))
+        .chain(reference_utxos.iter().map(|i| (i.0.clone(), i.1.clone())))
         .collect::<BTreeMap<_, _>>();
     Transaction::build(
         &network_parameters.protocol_parameters,
@@ -70,8 +81,55 @@ pub fn tx(
                 .with_outputs(outputs.clone())
                 .with_specified_signatories(specified_signatories.clone())
                 .with_validity_interval(lower_bound, upper_bound)
+                .with_message([message.as_str()])
                 .with_change_strategy(ChangeStrategy::as_last_output(change_address.clone()))
                 .ok()
         },
     )
 }
+
+/// A CIP-20 message naming what a tx does to channels, e.g. "konduit: open, sub x2", for
+/// explorers and the app's activity view to tell Konduit txs apart.
+pub fn message(steps: &[Step], opens: usize) -> String {
+    let mut counts: Vec<(&str, usize)> = vec![];
+    let names = std::iter::repeat_n("open", opens).chain(steps.iter().map(|step| match step {
+        Step::Cont(Cont::Add) => "add",
+        Step::Cont(Cont::Sub(..)) => "sub",
+        Step::Cont(Cont::Close) => "close",
+        Step::Cont(Cont::Respond(..)) => "respond",
+        Step::Cont(Cont::Unlock(..)) => "unlock",
+        Step::Cont(Cont::Expire(..)) => "expire",
+        Step::Eol(Eol::End) => "end",
+        Step::Eol(Eol::Elapse) => "elapse",
+    }));
+    for name in names {
+        match counts.iter_mut().find(|(counted, _)| *counted == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name, 1)),
+        }
+    }
+    let steps = counts
+        .into_iter()
+        .map(|(name, count)| match count {
+            1 => name.to_string(),
+            _ => format!("{name} x{count}"),
+        })
+        .collect::<Vec<_>>();
+    format!("konduit: {}", steps.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_counts_steps_in_order() {
+        let steps = [
+            Step::Cont(Cont::Add),
+            Step::Eol(Eol::End),
+            Step::Cont(Cont::Add),
+        ];
+        assert_eq!(message(&steps, 1), "konduit: open, add x2, end");
+        assert_eq!(message(&[], 3), "konduit: open x3");
+    }
+}
@@ -0,0 +1,282 @@
+use crate::Utxos;
+use cardano_sdk::{Hash, Input, Transaction, transaction::state::ReadyForSigning};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+use web_time::Instant;
+
+/// Transactions submitted but not yet confirmed. Follow-up transactions are built against the
+/// chain as it will be once these are: the outputs they spend left out, those they produce added;
+/// rather than waiting for them to make it on-chain, or spending their inputs twice.
+///
+/// A transaction is forgotten once confirmed. It is dropped, along with all those built on it,
+/// once it cannot be anymore: one of its inputs is spent by another, or it has been pending for
+/// too long.
+#[derive(Debug, Clone, Default)]
+pub struct Unconfirmed(BTreeMap<Hash<32>, Pending>);
+
+#[derive(Debug, Clone)]
+struct Pending {
+    transaction: Transaction<ReadyForSigning>,
+    submitted_at: Instant,
+}
+
+/// What a chain view tells of unconfirmed transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reconciled {
+    pub confirmed: Vec<Hash<32>>,
+    pub dropped: Vec<Hash<32>>,
+}
+
+impl Unconfirmed {
+    pub fn record(&mut self, transaction: &Transaction<ReadyForSigning>) {
+        self.0.insert(
+            transaction.id(),
+            Pending {
+                transaction: transaction.clone(),
+                submitted_at: Instant::now(),
+            },
+        );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn contains(&self, id: &Hash<32>) -> bool {
+        self.0.contains_key(id)
+    }
+
+    /// The chain view as it will be once all transactions are confirmed.
+    pub fn apply(&self, utxos: &Utxos) -> Utxos {
+        let spent = self.spent();
+        utxos
+            .iter()
+            .map(|(input, output)| (input.clone(), output.clone()))
+            .chain(self.0.iter().flat_map(|(id, pending)| {
+                pending
+                    .transaction
+                    .outputs()
+                    .enumerate()
+                    .map(|(ix, output)| (Input::new(*id, ix as u64), output))
+            }))
+            .filter(|(input, _)| !spent.contains(input))
+            .collect()
+    }
+
+    /// Catch up with a chain view, which must cover all addresses the transactions spend from.
+    ///
+    /// A transaction is confirmed when one of its outputs shows in the view, and so are those it
+    /// spends outputs of. Outputs spent since by someone else's transaction never show, so the
+    /// transaction producing them is then told dropped, though harmlessly so.
+    ///
+    /// A transaction is dropped when one of its inputs is neither in the view nor produced by
+    /// another pending transaction: it was rejected, or conflicts with one that made it first;
+    /// or when pending for longer than `ttl`. Either way, those built on it are dropped too. An
+    /// ancestor rolled back is the same as one rejected: its outputs are gone from the view.
+    pub fn reconcile(&mut self, view: &Utxos, ttl: Duration) -> Reconciled {
+        let mut ancestors = self
+            .0
+            .iter()
+            .filter(|(id, pending)| {
+                (0..pending.transaction.outputs().count())
+                    .any(|ix| view.contains_key(&Input::new(**id, ix as u64)))
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        let mut confirmed = vec![];
+        while let Some(id) = ancestors.pop() {
+            let Some(pending) = self.0.remove(&id) else {
+                continue;
+            };
+            confirmed.push(id);
+            ancestors.extend(
+                pending
+                    .transaction
+                    .inputs()
+                    .map(|input| input.transaction_id()),
+            );
+        }
+
+        let dead = self
+            .0
+            .iter()
+            .filter(|(_, pending)| {
+                pending.submitted_at.elapsed() >= ttl
+                    || pending.transaction.inputs().any(|input| {
+                        !view.contains_key(&input) && !self.0.contains_key(&input.transaction_id())
+                    })
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        let mut dropped = vec![];
+        for id in dead {
+            dropped.extend(self.drop_with_descendants(&id));
+        }
+
+        Reconciled { confirmed, dropped }
+    }
+
+    /// Drop a transaction, along with all those spending its outputs, directly or not; for when
+    /// it is known not to make it on-chain. Returns the ids dropped, the transaction's first.
+    pub fn drop_with_descendants(&mut self, id: &Hash<32>) -> Vec<Hash<32>> {
+        let mut dropped = vec![];
+        let mut queue = vec![*id];
+        while let Some(id) = queue.pop() {
+            if self.0.remove(&id).is_none() {
+                continue;
+            }
+            dropped.push(id);
+            queue.extend(
+                self.0
+                    .iter()
+                    .filter(|(_, pending)| {
+                        pending
+                            .transaction
+                            .inputs()
+                            .any(|input| input.transaction_id() == id)
+                    })
+                    .map(|(child, _)| *child),
+            );
+        }
+        dropped
+    }
+
+    fn spent(&self) -> BTreeSet<Input> {
+        self.0
+            .values()
+            .flat_map(|pending| pending.transaction.inputs())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano_sdk::{
+        Output, Value,
+        cbor::{FromCbor, ToCbor},
+    };
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn output() -> Output {
+        let address = cardano_sdk::SigningKey::from([1; 32])
+            .to_verification_key()
+            .to_address(cardano_sdk::NetworkId::TESTNET);
+        Output::new(address.into(), Value::new(2_000_000))
+    }
+
+    fn utxo(ix: u64) -> (Input, Output) {
+        (Input::new(Hash::from([1; 32]), ix), output())
+    }
+
+    fn tx(inputs: impl IntoIterator<Item = Input>) -> Transaction<ReadyForSigning> {
+        let mut tx = Transaction::default();
+        tx.with_inputs(inputs.into_iter().map(|input| (input, None)))
+            .with_outputs([output()]);
+        Transaction::from_cbor(&tx.to_cbor()).unwrap()
+    }
+
+    fn change(tx: &Transaction<ReadyForSigning>) -> (Input, Output) {
+        (Input::new(tx.id(), 0), output())
+    }
+
+    #[test]
+    fn hides_inputs_spent_until_confirmed() {
+        let tx = tx([utxo(0).0]);
+        let mut unconfirmed = Unconfirmed::default();
+        unconfirmed.record(&tx);
+
+        let view = Utxos::from([utxo(0), utxo(1)]);
+        assert_eq!(unconfirmed.reconcile(&view, TTL), Reconciled::default());
+        assert_eq!(
+            unconfirmed
+                .apply(&view)
+                .into_keys()
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from([utxo(1).0, change(&tx).0])
+        );
+
+        // Confirmed: its output shows in the view.
+        let view = Utxos::from([utxo(1), change(&tx)]);
+        assert_eq!(unconfirmed.reconcile(&view, TTL).confirmed, vec![tx.id()]);
+        assert!(unconfirmed.is_empty());
+        assert_eq!(unconfirmed.apply(&view), view);
+    }
+
+    #[test]
+    fn chains_on_unconfirmed_outputs() {
+        let parent = tx([utxo(0).0]);
+        let child = tx([change(&parent).0, utxo(1).0]);
+        let mut unconfirmed = Unconfirmed::default();
+        unconfirmed.record(&parent);
+        unconfirmed.record(&child);
+
+        let view = Utxos::from([utxo(0), utxo(1)]);
+        assert_eq!(unconfirmed.reconcile(&view, TTL), Reconciled::default());
+        assert_eq!(
+            unconfirmed
+                .apply(&view)
+                .into_keys()
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from([change(&child).0])
+        );
+
+        // The child confirmed, and so is the parent, though its output is spent already.
+        let view = Utxos::from([change(&child)]);
+        let reconciled = unconfirmed.reconcile(&view, TTL);
+        assert_eq!(reconciled.confirmed.len(), 2);
+        assert!(reconciled.dropped.is_empty());
+        assert!(unconfirmed.is_empty());
+    }
+
+    #[test]
+    fn drops_descendants_of_a_rejected_transaction() {
+        let parent = tx([utxo(0).0]);
+        let child = tx([change(&parent).0]);
+        let grandchild = tx([change(&child).0, utxo(1).0]);
+        let sibling = tx([utxo(2).0]);
+        let mut unconfirmed = Unconfirmed::default();
+        for tx in [&parent, &child, &grandchild, &sibling] {
+            unconfirmed.record(tx);
+        }
+
+        // The parent's input was spent by another tx.
+        let view = Utxos::from([utxo(1), utxo(2)]);
+        let reconciled = unconfirmed.reconcile(&view, TTL);
+        assert!(reconciled.confirmed.is_empty());
+        assert_eq!(
+            reconciled.dropped,
+            vec![parent.id(), child.id(), grandchild.id()]
+        );
+        assert!(unconfirmed.contains(&sibling.id()));
+        assert_eq!(
+            unconfirmed
+                .apply(&view)
+                .into_keys()
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from([utxo(1).0, change(&sibling).0])
+        );
+    }
+
+    #[test]
+    fn drops_expired_transactions() {
+        let parent = tx([utxo(0).0]);
+        let child = tx([change(&parent).0]);
+        let mut unconfirmed = Unconfirmed::default();
+        unconfirmed.record(&parent);
+        unconfirmed.record(&child);
+
+        let view = Utxos::from([utxo(0)]);
+        let reconciled = unconfirmed.reconcile(&view, Duration::ZERO);
+        assert_eq!(reconciled.dropped.len(), 2);
+        assert!(unconfirmed.is_empty());
+        assert_eq!(unconfirmed.apply(&view), view);
+    }
+}
@@ -1,45 +1,186 @@
 use anyhow::anyhow;
 use cardano_sdk::{
-    Address, Credential, Hash, Input, NetworkId, Output, PlutusScript, PlutusVersion, address::kind,
+    Address, Credential, Hash, Input, NetworkId, Output, PlutusData, PlutusScript, PlutusVersion,
+    address::kind,
 };
+use konduit_data::{Constants, Datum, ProtocolVersion, Redeemer, Stage};
 use std::{collections::BTreeMap, sync::LazyLock};
 
-use crate::Utxos;
+use crate::{Utxos, channel};
 
 pub type Lovelace = u64;
 pub const MIN_ADA_BUFFER: Lovelace = 2_000_000;
 
+/// Address of channels opened at the latest validator.
 pub fn konduit_address(
     network_id: NetworkId,
     delegation: Option<&Credential>,
 ) -> Address<kind::Shelley> {
-    match delegation {
-        Some(delegation) => Address::new(network_id, KONDUIT_VALIDATOR.to_credential())
-            .with_delegation(delegation.clone()),
-        None => Address::new(network_id, KONDUIT_VALIDATOR.to_credential()),
-    }
+    KONDUIT_VALIDATOR.address(network_id, delegation)
 }
 
+/// Reference script of the latest validator, if any.
 pub fn find_reference_script(utxos: &Utxos) -> Option<(Input, Output)> {
+    KONDUIT_VALIDATOR.find_reference_script(utxos)
+}
+
+/// Reference script of the validator of the given hash, if any.
+pub fn find_reference_script_of(hash: &Hash<28>, utxos: &Utxos) -> Option<(Input, Output)> {
+    utxos
+        .iter()
+        .find(|(_i, o)| o.script().is_some_and(|s| Hash::<28>::from(s) == *hash))
+        .map(|(i, o)| (i.clone(), o.clone()))
+}
+
+/// Reference scripts of any known validator version.
+pub fn find_reference_scripts(utxos: &Utxos) -> Utxos {
     utxos
         .iter()
-        .find(|(_i, o)| {
+        .filter(|(_, o)| {
             o.script()
-                .is_some_and(|s| Hash::<28>::from(s) == KONDUIT_VALIDATOR.hash)
+                .is_some_and(|s| KONDUIT_VALIDATORS.get(&Hash::<28>::from(s)).is_some())
         })
         .map(|(i, o)| (i.clone(), o.clone()))
+        .collect()
 }
 
 // TODO: embed the whole blueprint? blueprint_json
+#[derive(Debug, Clone)]
 pub struct KonduitValidator {
+    /// Encoding of the data the validator expects.
+    pub version: ProtocolVersion,
     pub hash: Hash<28>,
     pub script: PlutusScript,
 }
 
 impl KonduitValidator {
+    pub fn new(version: ProtocolVersion, script: PlutusScript) -> Self {
+        Self {
+            version,
+            hash: Hash::<28>::from(&script),
+            script,
+        }
+    }
+
+    /// Extract the konduit validator from a blueprint.
+    pub fn from_blueprint(version: ProtocolVersion, blueprint: &str) -> anyhow::Result<Self> {
+        let blueprint: BTreeMap<String, serde_json::Value> = serde_json::from_str(blueprint)
+            .map_err(|e| anyhow!("failed to parse blueprint: {e}"))?;
+
+        let validator = blueprint
+            .get("validators")
+            .and_then(|value| value.as_array())
+            .and_then(|validators| {
+                validators.iter().find(|validator| {
+                    validator
+                        .get("title")
+                        .and_then(|value| value.as_str())
+                        .map(|title| title == VALIDATOR_NAME)
+                        .unwrap_or(false)
+                })
+            })
+            .ok_or_else(|| anyhow!("validator `{VALIDATOR_NAME}` not found in blueprint"))?;
+
+        let hash = validator
+            .get("hash")
+            .and_then(|value| value.as_str())
+            .and_then(|s| Hash::try_from(s).ok())
+            .ok_or_else(|| anyhow!("failed to extract validator's hash from blueprint"))?;
+
+        let plutus_version = blueprint
+            .get("preamble")
+            .ok_or_else(|| anyhow!("failed to extract preamble from blueprint"))?
+            .get("plutusVersion")
+            .and_then(|value| value.as_str())
+            .and_then(|s| plutus_version_from_str(s).ok())
+            .ok_or_else(|| anyhow!("failed to extract plutus version from blueprint"))?;
+
+        // We should decode hex into Vec<u8>
+        let script_bytes = validator
+            .get("compiledCode")
+            .and_then(|value| value.as_str())
+            .and_then(|s| hex::decode(s).ok())
+            .ok_or_else(|| anyhow!("failed to extract validator's compiled code from blueprint"))?;
+        let script = PlutusScript::new(plutus_version, script_bytes);
+
+        Ok(Self {
+            version,
+            hash,
+            script,
+        })
+    }
+
     pub fn to_credential(&self) -> Credential {
         Credential::from_script(self.hash)
     }
+
+    pub fn address(
+        &self,
+        network_id: NetworkId,
+        delegation: Option<&Credential>,
+    ) -> Address<kind::Shelley> {
+        match delegation {
+            Some(delegation) => {
+                Address::new(network_id, self.to_credential()).with_delegation(delegation.clone())
+            }
+            None => Address::new(network_id, self.to_credential()),
+        }
+    }
+
+    pub fn find_reference_script(&self, utxos: &Utxos) -> Option<(Input, Output)> {
+        find_reference_script_of(&self.hash, utxos)
+    }
+
+    /// Datum of a channel held at this validator.
+    pub fn datum(&self, constants: Constants, stage: Stage) -> Datum {
+        Datum::new(self.hash, constants, stage)
+    }
+
+    /// Parse the datum of a channel held at this validator; fails on datums claiming another one.
+    pub fn parse_datum(&self, data: &PlutusData) -> Result<Datum, channel::Error> {
+        let datum = Datum::try_from(data).map_err(|_| channel::Error::ParseDatum)?;
+        if datum.own_hash != self.hash {
+            return Err(channel::Error::OwnHash);
+        }
+        Ok(datum)
+    }
+
+    pub fn redeemer(&self, redeemer: Redeemer) -> PlutusData<'static> {
+        redeemer.to_plutus_data(self.version)
+    }
+}
+
+/// The validator versions known to this build, oldest first. Channels are only ever opened at the
+/// latest one; older ones remain known so that existing channels can be stepped to their end.
+#[derive(Debug, Clone)]
+pub struct KonduitValidators(Vec<KonduitValidator>);
+
+impl KonduitValidators {
+    /// Panics when given no validators.
+    pub fn new(validators: Vec<KonduitValidator>) -> Self {
+        assert!(!validators.is_empty(), "no konduit validator");
+        Self(validators)
+    }
+
+    pub fn latest(&self) -> &KonduitValidator {
+        self.0.last().expect("at least one validator")
+    }
+
+    pub fn get(&self, hash: &Hash<28>) -> Option<&KonduitValidator> {
+        self.0.iter().find(|validator| validator.hash == *hash)
+    }
+
+    pub fn is_latest(&self, hash: &Hash<28>) -> bool {
+        self.latest().hash == *hash
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &KonduitValidator> {
+        self.0.iter()
+    }
+
+    pub fn credentials(&self) -> impl Iterator<Item = Credential> {
+        self.iter().map(KonduitValidator::to_credential)
+    }
 }
 
 pub fn plutus_version_from_str(s: &str) -> anyhow::Result<PlutusVersion> {
@@ -55,47 +196,17 @@ pub fn plutus_version_from_str(s: &str) -> anyhow::Result<PlutusVersion> {
 
 const VALIDATOR_NAME: &str = "konduit.konduit.spend";
 
-/// Get the validator blueprint at compile-time, and make the validator hash available on-demand.
-pub static KONDUIT_VALIDATOR: LazyLock<KonduitValidator> = LazyLock::new(|| {
-    let blueprint: BTreeMap<String, serde_json::Value> = serde_json::from_str(include_str!(
-        concat!(std::env!("CARGO_MANIFEST_DIR"), "/plutus.json")
-    ))
-    .unwrap_or_else(|e| panic!("failed to parse blueprint: {e}"));
-
-    let validator = blueprint
-        .get("validators")
-        .and_then(|value| value.as_array())
-        .and_then(|validators| {
-            validators.iter().find(|validator| {
-                validator
-                    .get("title")
-                    .and_then(|value| value.as_str())
-                    .map(|title| title == VALIDATOR_NAME)
-                    .unwrap_or(false)
-            })
-        })
-        .unwrap_or_else(|| panic!("validator `{VALIDATOR_NAME}` not found in blueprint"));
-
-    let hash = validator
-        .get("hash")
-        .and_then(|value| value.as_str())
-        .and_then(|s| Hash::try_from(s).ok())
-        .unwrap_or_else(|| panic!("failed to extract validator's hash from blueprint"));
-
-    let plutus_version = blueprint
-        .get("preamble")
-        .unwrap_or_else(|| panic!("failed to extract preamble from blueprint"))
-        .get("plutusVersion")
-        .and_then(|value| value.as_str())
-        .and_then(|s| plutus_version_from_str(s).ok())
-        .unwrap_or_else(|| panic!("failed to extract plutus version from blueprint"));
-
-    // We should decode hex into Vec<u8>
-    let script_bytes = validator
-        .get("compiledCode")
-        .and_then(|value| value.as_str())
-        .and_then(|s| hex::decode(s).ok())
-        .unwrap_or_else(|| panic!("failed to extract validator's compiled code from blueprint"));
-    let script = PlutusScript::new(plutus_version, script_bytes);
-    KonduitValidator { hash, script }
+/// All validator versions, from the blueprints embedded at compile-time.
+pub static KONDUIT_VALIDATORS: LazyLock<KonduitValidators> = LazyLock::new(|| {
+    KonduitValidators::new(vec![
+        KonduitValidator::from_blueprint(
+            ProtocolVersion::V0,
+            include_str!(concat!(std::env!("CARGO_MANIFEST_DIR"), "/plutus.json")),
+        )
+        .unwrap_or_else(|e| panic!("{e}")),
+    ])
 });
+
+/// The latest validator version, at which channels are opened.
+pub static KONDUIT_VALIDATOR: LazyLock<&KonduitValidator> =
+    LazyLock::new(|| KONDUIT_VALIDATORS.latest());
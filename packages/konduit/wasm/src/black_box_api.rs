@@ -1,12 +1,13 @@
 use crate::{
-    Adaptor, Connector, core, l1, l2,
+    Adaptor, Connector, core, l1, l2, router,
     wasm::{
-        self, AdaptorInfo, ChannelOutput, Hash32, Invoice, Lock, Lockeds, NetworkId, Quote,
-        ShelleyAddress, SigningKey, Tag, Wallet,
+        self, AdaptorInfo, ChannelOutput, Cip30Wallet, Hash32, Invoice, Lock, Lockeds, Mnemonic,
+        NetworkId, Quote, ShelleyAddress, SigningKey, Tag, Wallet,
     },
 };
 use anyhow::anyhow;
-use std::rc::Rc;
+use futures::future::join_all;
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
 use wasm_bindgen::prelude::*;
 use web_time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -30,11 +31,24 @@ pub struct Konduit {
     // Rudimentary wallet holding the consumer's credentials.
     wallet: Wallet,
 
+    // External wallet funding opens and adds in place of the embedded one, if any.
+    funding_wallet: Option<Cip30Wallet>,
+
     // Connection to a connector, as a result since it could initially be missing.
     connector: wasm::Result<Rc<Connector>>,
 
-    // Connection to an adaptor, as a result since it could initially be missing.
-    adaptor: wasm::Result<Adaptor>,
+    // Channels known to the consumer, by tag. Each comes with its own adaptor (possibly the same
+    // server for several channels), authenticated for that channel.
+    registry: BTreeMap<core::Tag, Registered>,
+}
+
+/// A channel registered with the instance.
+struct Registered {
+    adaptor: Adaptor,
+
+    // Known locks, updated on every sync with the adaptor. Held behind a RefCell so that
+    // (async) operations can update it from a shared reference.
+    lockeds: RefCell<Lockeds>,
 }
 
 impl Konduit {
@@ -45,13 +59,126 @@ impl Konduit {
         ))
     }
 
-    fn l2_client(&self) -> wasm::Result<l2::Client<'_>> {
+    /// Execute intents funded by the funding wallet if set, or by the embedded one otherwise.
+    /// Either way, channels remain those of the embedded wallet's key.
+    async fn execute_funded(
+        &self,
+        opens: Vec<core::consumer::OpenIntent>,
+        intents: BTreeMap<core::Tag, core::consumer::Intent>,
+    ) -> wasm::Result<Hash32> {
+        let client = self.l1_client()?;
+        let stake_credential = self.wallet.stake_credential();
+
+        let Some(funding_wallet) = &self.funding_wallet else {
+            return Ok(client
+                .execute(
+                    self.wallet.signing_key(),
+                    stake_credential.as_ref(),
+                    opens,
+                    intents,
+                    &self.script_deployment_address,
+                )
+                .await?
+                .into());
+        };
+
+        let funding = funding_wallet.funding().await?;
+        let mut tx = client
+            .prepare_funded(
+                &funding,
+                stake_credential.as_ref(),
+                opens,
+                intents,
+                &self.script_deployment_address,
+            )
+            .await?;
+        funding_wallet.witness(&mut tx).await?;
+
+        Ok(client.submit(&tx).await?.into())
+    }
+
+    fn registered(&self, tag: &core::Tag) -> wasm::Result<&Registered> {
+        self.registry
+            .get(tag)
+            .ok_or_else(|| anyhow!("no channel registered with tag={tag}").into())
+    }
+
+    fn l2_client(&self, tag: &core::Tag) -> wasm::Result<l2::Client<'_>> {
         Ok(l2::Client::new(
-            self.adaptor.as_ref()?,
+            &self.registered(tag)?.adaptor,
             self.wallet.signing_key(),
         ))
     }
 
+    /// On-chain channels belonging to us, and that are registered with the instance.
+    async fn registered_channels(&self) -> wasm::Result<BTreeMap<core::Tag, core::Channel>> {
+        Ok(self
+            .l1_client()?
+            .channels(self.wallet.stake_credential().as_ref())
+            .await?
+            .filter(|channel| self.registry.contains_key(channel.tag()))
+            .map(|channel| (channel.tag().clone(), channel))
+            .collect())
+    }
+
+    /// Find, amongst registered channels, the one offering the cheapest quote for the invoice
+    /// while having enough capacity to cover it. Adaptors failing to quote are skipped.
+    async fn route(&self, invoice: &Invoice) -> wasm::Result<(core::Tag, core::Quote)> {
+        let routes = self
+            .registered_channels()
+            .await?
+            .into_values()
+            .map(|channel| router::Route {
+                // NOTE: channels are filtered on the registry, so the tag is necessarily registered.
+                adaptor: &self.registry[channel.tag()].adaptor,
+                channel,
+            })
+            .collect();
+
+        let ranking = router::Router::new(self.wallet.signing_key(), routes)
+            .rank(&core::QuoteBody::Bolt11((**invoice).clone()))
+            .await;
+
+        for (tag, rejection) in ranking.rejected.iter() {
+            log::warn!("route: tag={tag} rejected: {rejection}");
+        }
+
+        ranking
+            .best()
+            .map(|offer| (offer.tag.clone(), offer.quote.clone()))
+            .ok_or_else(|| anyhow!("no registered channel can route this payment").into())
+    }
+
+    async fn pay_with(
+        &self,
+        tag: &core::Tag,
+        invoice: &Invoice,
+        quote: &core::Quote,
+    ) -> wasm::Result<SyncStatus> {
+        log::debug!("pay: for tag={}, quote={:?}", tag, quote);
+        self.registered(tag)?
+            .lockeds
+            .borrow_mut()
+            .add(core::Lock(invoice.payment_hash));
+        let squash_status = self.l2_client(tag)?.pay(invoice, quote).await?;
+        self.sync_with(tag, squash_status).await
+    }
+
+    /// Settle a squash status with the adaptor of the given channel, keeping track of its lockeds.
+    async fn sync_with(
+        &self,
+        tag: &core::Tag,
+        squash_status: core::SquashStatus,
+    ) -> wasm::Result<SyncStatus> {
+        let registered = self.registered(tag)?;
+        let mut lockeds = registered.lockeds.borrow().clone();
+        let sync_status = self
+            .squash(self.l2_client(tag)?, tag, squash_status, &mut lockeds)
+            .await?;
+        registered.lockeds.replace(lockeds);
+        Ok(sync_status)
+    }
+
     async fn squash(
         &self,
         client: l2::Client<'_>,
@@ -107,7 +234,7 @@ impl Konduit {
 /// A 'black-box' API for Konduit L1 & L2 operations.
 #[wasm_bindgen]
 impl Konduit {
-    /// Restore an instance from a signing key. Everything else (connector, adaptor, ...) is
+    /// Restore an instance from a signing key. Everything else (connector, channels, ...) is
     /// initially NOT configured.
     ///
     /// Note that this take ownership of the signing key /!\, to prevent it from leaking elsewhere
@@ -122,17 +249,49 @@ impl Konduit {
             network_id: *network_id,
             script_deployment_address: script_deployment_address.clone(),
             wallet: Wallet::new((*network_id).into(), signing_key.into()),
+            funding_wallet: None,
             connector: Err(anyhow!("no available connector").into()),
-            adaptor: Err(anyhow!("no available adaptor").into()),
+            registry: BTreeMap::new(),
         }
     }
 
+    /// Restore an instance from a mnemonic, as any other Cardano wallet would: the wallet key is
+    /// the payment key of the given account (0 by default). See also 'new'.
+    #[wasm_bindgen(js_name = "fromMnemonic")]
+    pub fn from_mnemonic(
+        network_id: &NetworkId,
+        script_deployment_address: &ShelleyAddress,
+        mnemonic: Mnemonic,
+        passphrase: Option<String>,
+        account: Option<u32>,
+    ) -> wasm::Result<Self> {
+        let signing_key = SigningKey::_wasm_from_mnemonic(&mnemonic, passphrase, account)?;
+        Ok(Self::new(
+            network_id,
+            script_deployment_address,
+            signing_key,
+        ))
+    }
+
     /// A handle on the underlying wallet.
     #[wasm_bindgen(getter, js_name = "wallet")]
     pub fn wallet(&self) -> Wallet {
         self.wallet.clone()
     }
 
+    /// An external CIP-30 wallet to fund 'openChannel' and 'addToChannel' with, in place of the
+    /// embedded wallet; which still holds the channels. The wallet is asked to sign each such
+    /// transaction, partially. Set to undefined to fund from the embedded wallet again.
+    #[wasm_bindgen(getter, js_name = "fundingWallet")]
+    pub fn funding_wallet(&self) -> Option<Cip30Wallet> {
+        self.funding_wallet.clone()
+    }
+
+    #[wasm_bindgen(setter, js_name = "fundingWallet")]
+    pub fn set_funding_wallet(&mut self, funding_wallet: Option<Cip30Wallet>) {
+        self.funding_wallet = funding_wallet;
+    }
+
     /// Current network id for which the app is configured.
     #[wasm_bindgen(getter, js_name = "networkId")]
     pub fn network_id(&self) -> NetworkId {
@@ -156,46 +315,98 @@ impl Konduit {
     }
 }
 
-// Adaptor-related interface
+// Registry-related interface
 #[wasm_bindgen]
 impl Konduit {
-    /// Configure an (unauthenticated) adaptor, without a defined tag yet. Suitable to get the
-    /// adaptor info and other non-authenticated operations.
-    #[wasm_bindgen(getter, js_name = "adaptorInfo")]
-    pub fn adaptor_info(&self) -> wasm::Result<AdaptorInfo> {
-        Ok(self.adaptor.as_ref()?.info().clone().into())
-    }
-
-    /// Configure an (unauthenticated) adaptor, without a defined tag yet. Suitable to get the
-    /// adaptor info and other non-authenticated operations.
-    #[wasm_bindgen(setter, js_name = "adaptor")]
-    pub fn set_adaptor(&mut self, adaptor: wasm::Adaptor) {
-        self.adaptor = Ok(adaptor.into());
-    }
-
-    /// Recover a previously known tag, if any.
-    #[wasm_bindgen(js_name = "setChannelTag")]
-    pub fn set_channel_tag(&mut self, tag: &Tag) -> wasm::Result<()> {
-        if self.adaptor.as_ref()?.tag() != Some(tag) {
-            let tag: core::Tag = tag.clone().into();
-            let keytag = core::Keytag::new(self.wallet.verification_key(), tag.clone());
-            self.adaptor.as_mut()?.set_keytag(Some(&keytag));
-        }
+    /// Register a channel, new or previously known, with the adaptor it is (or will be) opened
+    /// with. Lockeds from a previous session can be restored alongside; see 'channelLockeds'.
+    ///
+    /// Registering an already registered tag replaces it. Note that this takes ownership of the
+    /// adaptor: several channels with the same adaptor each need their own.
+    #[wasm_bindgen(js_name = "registerChannel")]
+    pub fn register_channel(
+        &mut self,
+        adaptor: wasm::Adaptor,
+        tag: &Tag,
+        lockeds: Option<Lockeds>,
+    ) {
+        let tag: core::Tag = tag.clone().into();
+        let keytag = core::Keytag::new(self.wallet.verification_key(), tag.clone());
+
+        let mut adaptor: Adaptor = adaptor.into();
+        adaptor.set_keytag(Some(&keytag));
+
+        self.registry.insert(
+            tag,
+            Registered {
+                adaptor,
+                lockeds: RefCell::new(lockeds.unwrap_or_else(Lockeds::_wasm_new)),
+            },
+        );
+    }
+
+    /// Forget about a registered channel. Returns whether the channel was registered.
+    #[wasm_bindgen(js_name = "unregisterChannel")]
+    pub fn unregister_channel(&mut self, tag: &Tag) -> bool {
+        self.registry.remove(tag).is_some()
+    }
+
+    /// All registered channels.
+    #[wasm_bindgen(getter, js_name = "registeredChannels")]
+    pub fn registered_channel_handles(&self) -> Vec<ChannelHandle> {
+        self.registry
+            .iter()
+            .map(|(tag, registered)| ChannelHandle {
+                tag: tag.clone().into(),
+                adaptor_url: registered.adaptor.base_url().to_string(),
+            })
+            .collect()
+    }
 
-        Ok(())
+    /// Current known lockeds of a registered channel, to be persisted across sessions.
+    #[wasm_bindgen(js_name = "channelLockeds")]
+    pub fn channel_lockeds(&self, tag: &Tag) -> wasm::Result<Lockeds> {
+        Ok(self.registered(tag)?.lockeds.borrow().clone())
     }
 
-    /// Remove any existing channel tag
-    #[wasm_bindgen(js_name = "resetChannelTag")]
-    pub fn reset_channel_tag(&mut self) -> wasm::Result<()> {
-        self.adaptor.as_mut()?.set_keytag(None);
-        Ok(())
+    /// Channel parameters and ToS of the adaptor behind a registered channel.
+    #[wasm_bindgen(js_name = "adaptorInfo")]
+    pub fn adaptor_info(&self, tag: &Tag) -> wasm::Result<AdaptorInfo> {
+        Ok(self.registered(tag)?.adaptor.info().clone().into())
     }
 
-    /// Get a quote for a given Bolt11 invoice from the adapator.
+    /// Get a quote for a given Bolt11 invoice from the adaptor of a registered channel.
     #[wasm_bindgen(js_name = "getQuoteFor")]
-    pub async fn get_quote_for(&self, invoice: &Invoice) -> wasm::Result<Quote> {
-        Ok(self.l2_client()?.quote(invoice).await?.into())
+    pub async fn get_quote_for(&self, tag: &Tag, invoice: &Invoice) -> wasm::Result<Quote> {
+        Ok(self.l2_client(tag)?.quote(invoice).await?.into())
+    }
+
+    /// Funds available for payments, in lovelace, per registered channel found on-chain and in
+    /// total.
+    #[wasm_bindgen(js_name = "balance")]
+    pub async fn balance(&self) -> wasm::Result<Balance> {
+        let channels = self.registered_channels().await?;
+
+        let balances = join_all(channels.iter().map(|(tag, channel)| async move {
+            let receipt = self.l2_client(tag)?.receipt().await?;
+            Ok::<_, wasm::Error>(ChannelBalance {
+                tag: tag.clone().into(),
+                amount: channel.amount(),
+                owed: receipt
+                    .as_ref()
+                    .map(|receipt| receipt.provably_owed(&self.wallet.verification_key(), tag))
+                    .unwrap_or_default(),
+                capacity: l2::capacity(channel, receipt.as_ref()),
+            })
+        }))
+        .await
+        .into_iter()
+        .collect::<wasm::Result<Vec<_>>>()?;
+
+        Ok(Balance {
+            capacity: balances.iter().map(|balance| balance.capacity).sum(),
+            channels: balances,
+        })
     }
 }
 
@@ -214,14 +425,15 @@ impl Konduit {
             .collect())
     }
 
-    /// Open a channel with the given tag and initial deposit.
+    /// Open a registered channel with the given initial deposit. Funded by the 'fundingWallet',
+    /// if any.
     #[wasm_bindgen(js_name = "openChannel")]
     pub async fn open_channel(&self, tag: &Tag, amount: u64) -> wasm::Result<Hash32> {
         let tag: core::Tag = tag.clone().into();
 
         log::debug!("open_channel: for tag = {}", tag);
 
-        let adaptor = self.adaptor.as_ref()?;
+        let adaptor = &self.registered(&tag)?.adaptor;
         let adaptor_key = adaptor.info().channel_parameters.adaptor_key;
         let close_period = adaptor.info().channel_parameters.close_period;
 
@@ -232,94 +444,60 @@ impl Konduit {
             amount,
         }];
 
-        let open_tx: Hash32 = self
-            .l1_client()?
-            .execute(
-                self.wallet.signing_key(),
-                self.wallet.stake_credential().as_ref(),
-                opens,
-                Default::default(),
-                &self.script_deployment_address,
-            )
-            .await?
-            .into();
-
-        Ok(open_tx)
+        self.execute_funded(opens, Default::default()).await
     }
 
-    /// Add funds to an existing channel.
+    /// Add funds to an existing channel. Funded by the 'fundingWallet', if any.
     #[wasm_bindgen(js_name = "addToChannel")]
-    pub async fn add_to_channel(&self, amount: u64) -> wasm::Result<Hash32> {
-        let tag: core::Tag = self
-            .adaptor
-            .as_ref()?
-            .tag()
-            .ok_or::<wasm::Error>(
-                anyhow!("add_to_channel: no tag set: attempting to add to non-existing channel?")
-                    .into(),
-            )?
-            .clone();
-
-        let add_tx: Hash32 = self
-            .l1_client()?
-            .execute(
-                self.wallet.signing_key(),
-                self.wallet.stake_credential().as_ref(),
-                vec![],
-                From::from([(tag, core::consumer::Intent::Add(amount))]),
-                &self.script_deployment_address.clone(),
-            )
-            .await?
-            .into();
+    pub async fn add_to_channel(&self, tag: &Tag, amount: u64) -> wasm::Result<Hash32> {
+        let tag: core::Tag = tag.clone().into();
 
-        Ok(add_tx)
+        self.execute_funded(
+            vec![],
+            From::from([(tag, core::consumer::Intent::Add(amount))]),
+        )
+        .await
     }
 
-    /// Synchronize the channel with the adaptor.
+    /// Synchronize a registered channel with its adaptor.
     #[wasm_bindgen(js_name = "syncChannel")]
-    pub async fn sync_channel(&self, lockeds: &mut Lockeds) -> wasm::Result<SyncStatus> {
-        if let Some(tag) = self.adaptor.as_ref()?.tag() {
-            log::debug!("sync_channel: for tag={}", tag);
-            let client = self.l2_client()?;
-            let squash_status = client.squash(core::SquashBody::default()).await?;
-            self.squash(client, tag, squash_status, lockeds).await
-        } else {
-            log::debug!("sync_channel: no tag set, assuming no channel to sync.");
-            Ok(SyncStatus::default())
-        }
+    pub async fn sync_channel(&self, tag: &Tag) -> wasm::Result<SyncStatus> {
+        log::debug!("sync_channel: for tag={}", **tag);
+        let squash_status = self
+            .l2_client(tag)?
+            .squash(core::SquashBody::default())
+            .await?;
+        self.sync_with(tag, squash_status).await
     }
 
-    /// Pay an invoice using a previously established quote.
+    /// Pay an invoice through whichever registered channel has enough capacity and offers the
+    /// best quote.
     #[wasm_bindgen(js_name = "pay")]
-    pub async fn pay(
+    pub async fn pay(&self, invoice: &Invoice) -> wasm::Result<PaymentStatus> {
+        let (tag, quote) = self.route(invoice).await?;
+        let sync = self.pay_with(&tag, invoice, &quote).await?;
+        Ok(PaymentStatus {
+            tag: tag.into(),
+            quote: quote.into(),
+            sync,
+        })
+    }
+
+    /// Pay an invoice through a given registered channel, using a previously established quote.
+    #[wasm_bindgen(js_name = "payWith")]
+    pub async fn pay_with_wasm(
         &self,
+        tag: &Tag,
         invoice: &Invoice,
         quote: &Quote,
-        lockeds: &mut Lockeds,
     ) -> wasm::Result<SyncStatus> {
-        if let Some(tag) = self.adaptor.as_ref()?.tag() {
-            log::debug!("pay: for tag={}, quote={:?}", tag, quote);
-            let client = self.l2_client()?;
-            lockeds.add(core::Lock(invoice.payment_hash));
-            let squash_status = client.pay(invoice, quote).await?;
-            self.squash(client, tag, squash_status, lockeds).await
-        } else {
-            Err(anyhow!("pay: no tag set; is the channel open?").into())
-        }
+        self.pay_with(tag, invoice, quote).await
     }
 
-    /// Close the currently active channel, if any.
+    /// Close a channel.
     #[wasm_bindgen(js_name = "closeChannel")]
-    pub async fn close_channel(&self) -> wasm::Result<Hash32> {
-        let tag: core::Tag = self
-            .adaptor
-            .as_ref()?
-            .tag()
-            .ok_or::<wasm::Error>(
-                anyhow!("close_channel: no tag set: attempting to close non-existing channel?")
-                    .into(),
-            )?
-            .clone();
+    pub async fn close_channel(&self, tag: &Tag) -> wasm::Result<Hash32> {
+        let tag: core::Tag = tag.clone().into();
 
         let close_tx: Hash32 = self
             .l1_client()?
@@ -335,6 +513,52 @@ impl Konduit {
 
         Ok(close_tx)
     }
+
+    /// Close a channel held at an older validator version, and open one with the same adaptor and
+    /// amount at the latest version, under a new tag. The new tag must be registered with its
+    /// adaptor before paying through it.
+    #[wasm_bindgen(js_name = "migrateChannel")]
+    pub async fn migrate_channel(&self, from: &Tag, to: &Tag) -> wasm::Result<Hash32> {
+        let from: core::Tag = from.clone().into();
+        let to: core::Tag = to.clone().into();
+
+        log::debug!("migrate_channel: from tag = {} to tag = {}", from, to);
+
+        let migrate_tx: Hash32 = self
+            .l1_client()?
+            .migrate(
+                self.wallet.signing_key(),
+                self.wallet.stake_credential().as_ref(),
+                &from,
+                to,
+                &self.script_deployment_address,
+            )
+            .await?
+            .into();
+
+        Ok(migrate_tx)
+    }
+
+    /// Check our channels for steps that we can take on our own (elapse, expire or end), and
+    /// submit a transaction for those that are due. Meant to be called periodically.
+    #[wasm_bindgen(js_name = "tick")]
+    pub async fn tick(&self) -> wasm::Result<TickStatus> {
+        let tick = self
+            .l1_client()?
+            .tick(
+                self.wallet.signing_key(),
+                self.wallet.stake_credential().as_ref(),
+                &self.script_deployment_address,
+            )
+            .await?;
+
+        log::debug!("tick: {:#?}", tick);
+
+        Ok(TickStatus {
+            next: tick.next().map(u64::from),
+            submitted: tick.submitted.map(Hash32::from),
+        })
+    }
 }
 
 fn get_current_time() -> Duration {
@@ -359,3 +583,109 @@ impl SyncStatus {
         self.squashed.clone()
     }
 }
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+#[doc = "A summary of a tick operation."]
+pub struct TickStatus {
+    /// Posix time (in milliseconds) at which a step is next due, if any.
+    pub next: Option<u64>,
+    // Transaction submitted to perform due steps, if any.
+    submitted: Option<Hash32>,
+}
+
+#[wasm_bindgen]
+impl TickStatus {
+    #[wasm_bindgen(getter, js_name = "submitted")]
+    pub fn _wasm_submitted(&self) -> Option<Hash32> {
+        self.submitted
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+#[doc = "A channel registered with a Konduit instance."]
+pub struct ChannelHandle {
+    tag: Tag,
+    adaptor_url: String,
+}
+
+#[wasm_bindgen]
+impl ChannelHandle {
+    #[wasm_bindgen(getter, js_name = "tag")]
+    pub fn _wasm_tag(&self) -> Tag {
+        self.tag.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "adaptorUrl")]
+    pub fn _wasm_adaptor_url(&self) -> String {
+        self.adaptor_url.clone()
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+#[doc = "Funds of a registered channel, in lovelace."]
+pub struct ChannelBalance {
+    tag: Tag,
+    /// Funds currently held in the channel, on-chain.
+    pub amount: u64,
+    /// Amount provably owed to the adaptor.
+    pub owed: u64,
+    /// Funds available for new payments.
+    pub capacity: u64,
+}
+
+#[wasm_bindgen]
+impl ChannelBalance {
+    #[wasm_bindgen(getter, js_name = "tag")]
+    pub fn _wasm_tag(&self) -> Tag {
+        self.tag.clone()
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+#[doc = "Aggregated funds across registered channels, in lovelace."]
+pub struct Balance {
+    /// Funds available for new payments, across all channels. Channels hold ADA only, so their
+    /// capacities add up.
+    pub capacity: u64,
+    channels: Vec<ChannelBalance>,
+}
+
+#[wasm_bindgen]
+impl Balance {
+    #[wasm_bindgen(getter, js_name = "channels")]
+    pub fn _wasm_channels(&self) -> Vec<ChannelBalance> {
+        self.channels.clone()
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+#[doc = "A summary of a pay operation."]
+pub struct PaymentStatus {
+    // Channel through which the payment was routed.
+    tag: Tag,
+    quote: Quote,
+    sync: SyncStatus,
+}
+
+#[wasm_bindgen]
+impl PaymentStatus {
+    #[wasm_bindgen(getter, js_name = "tag")]
+    pub fn _wasm_tag(&self) -> Tag {
+        self.tag.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "quote")]
+    pub fn _wasm_quote(&self) -> Quote {
+        self.quote.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "sync")]
+    pub fn _wasm_sync(&self) -> SyncStatus {
+        self.sync.clone()
+    }
+}
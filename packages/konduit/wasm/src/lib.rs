@@ -27,10 +27,18 @@ mod prelude {
 
     pub mod l2 {
         use http_client::transport;
+        pub use konduit_client::l2::capacity;
         #[cfg(feature = "black-box-api")]
         pub type Client<'a> = konduit_client::l2::Client<'a, transport::Gloo>;
     }
 
+    pub mod router {
+        #[cfg(feature = "black-box-api")]
+        pub use konduit_client::router::Route;
+        #[cfg(feature = "black-box-api")]
+        pub type Router<'a> = konduit_client::router::Router<'a, http_client::transport::Gloo>;
+    }
+
     pub mod core {
         pub use bln_sdk::types::Invoice;
         pub use cardano_connector_client::types::*;
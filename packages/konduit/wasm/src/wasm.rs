@@ -1,6 +1,7 @@
 mod adaptor;
 mod adaptor_info;
 mod channel_output;
+mod cip30_wallet;
 mod connector;
 mod credential;
 mod error;
@@ -12,6 +13,7 @@ mod invoice;
 mod lock;
 mod lockeds;
 mod log_level;
+mod mnemonic;
 mod network_id;
 mod output;
 mod output_summary;
@@ -28,6 +30,7 @@ mod wasm_proxy;
 pub use adaptor::*;
 pub use adaptor_info::*;
 pub use channel_output::*;
+pub use cip30_wallet::*;
 pub use connector::*;
 pub use credential::*;
 pub use error::*;
@@ -39,6 +42,7 @@ pub use invoice::*;
 pub use lock::*;
 pub use lockeds::*;
 pub use log_level::*;
+pub use mnemonic::*;
 pub use network_id::*;
 pub use output::*;
 pub use output_summary::*;
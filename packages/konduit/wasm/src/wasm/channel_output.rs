@@ -27,6 +27,12 @@ impl ChannelOutput {
         matches!(self.stage(), core::Stage::Responded { .. })
     }
 
+    /// Indicate whether the channel is held at an older validator version, and ought to be migrated
+    #[wasm_bindgen(getter, js_name = "isOutdated")]
+    pub fn _wasm_is_outdated(&self) -> bool {
+        self.is_outdated()
+    }
+
     /// Return the channel tag.
     #[wasm_bindgen(getter, js_name = "tag")]
     pub fn _wasm_tag(&self) -> Tag {
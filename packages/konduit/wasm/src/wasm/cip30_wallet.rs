@@ -0,0 +1,97 @@
+use crate::core::{
+    self, Address, Input, Output, Transaction, address::kind, cbor, consumer::Funding,
+    transaction::state::ReadyForSigning,
+};
+use anyhow::anyhow;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CIP30_WALLET: &'static str = r#"
+/**
+ * The part of a CIP-30 wallet API (as resolved by `window.cardano.<wallet>.enable()`) that
+ * funds and witnesses transactions.
+ */
+export interface Cip30Wallet {
+  getUtxos(): Promise<string[] | null | undefined>;
+  getChangeAddress(): Promise<string>;
+  signTx(tx: string, partialSign: boolean): Promise<string>;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A CIP-30 wallet API; see the eponymous TypeScript interface.
+    #[wasm_bindgen(typescript_type = "Cip30Wallet")]
+    #[derive(Debug, Clone)]
+    pub type Cip30Wallet;
+
+    #[wasm_bindgen(method, catch, js_name = "getUtxos")]
+    async fn get_utxos(this: &Cip30Wallet) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = "getChangeAddress")]
+    async fn get_change_address(this: &Cip30Wallet) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = "signTx")]
+    async fn sign_tx(this: &Cip30Wallet, tx: &str, partial_sign: bool) -> Result<JsValue, JsValue>;
+}
+
+impl Cip30Wallet {
+    /// All the wallet's UTxOs, and its change address, to fund a transaction with.
+    pub async fn funding(&self) -> anyhow::Result<Funding> {
+        let utxos = self.get_utxos().await.map_err(js_error("getUtxos"))?;
+        let utxos = if utxos.is_null() || utxos.is_undefined() {
+            core::Utxos::new()
+        } else {
+            js_sys::Array::from(&utxos)
+                .iter()
+                .map(|utxo| {
+                    let bytes = utxo
+                        .as_string()
+                        .and_then(|utxo| hex::decode(utxo).ok())
+                        .ok_or_else(|| anyhow!("getUtxos: expected hex-encoded utxos"))?;
+                    cbor::decode::<(Input, Output)>(&bytes)
+                        .map_err(|e| anyhow!(e).context("getUtxos: malformed utxo"))
+                })
+                .collect::<anyhow::Result<_>>()?
+        };
+
+        let change_address = self
+            .get_change_address()
+            .await
+            .map_err(js_error("getChangeAddress"))?
+            .as_string()
+            .and_then(|address| hex::decode(address).ok())
+            .ok_or_else(|| anyhow!("getChangeAddress: expected a hex-encoded address"))?;
+        let change_address = Address::<kind::Any>::try_from(change_address.as_slice())?;
+
+        Ok(Funding {
+            utxos,
+            change_address,
+        })
+    }
+
+    /// Have the wallet witness the inputs it funded the transaction with. Other witnesses are
+    /// preserved.
+    pub async fn witness(&self, tx: &mut Transaction<ReadyForSigning>) -> anyhow::Result<()> {
+        let witness_set = self
+            .sign_tx(&hex::encode(cbor::ToCbor::to_cbor(tx)), true)
+            .await
+            .map_err(js_error("signTx"))?
+            .as_string()
+            .and_then(|witness_set| hex::decode(witness_set).ok())
+            .ok_or_else(|| anyhow!("signTx: expected a hex-encoded witness set"))?;
+        tx.merge_witness_set(&witness_set)?;
+        Ok(())
+    }
+}
+
+/// Turn an exception thrown by the wallet into an error. CIP-30 errors are objects, hence the JSON.
+fn js_error(method: &'static str) -> impl FnOnce(JsValue) -> anyhow::Error {
+    move |e| {
+        let message = e
+            .as_string()
+            .or_else(|| js_sys::JSON::stringify(&e).ok().and_then(|e| e.as_string()))
+            .unwrap_or_default();
+        anyhow!("{method}: {message}")
+    }
+}
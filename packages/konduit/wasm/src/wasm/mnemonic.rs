@@ -0,0 +1,30 @@
+use crate::{core, wasm, wasm_proxy};
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+wasm_proxy! {
+    #[derive(Debug, Clone)]
+    #[doc = "A BIP39 mnemonic, seeding an HD wallet as per CIP-1852."]
+    Mnemonic => core::Mnemonic
+}
+
+#[wasm_bindgen]
+impl Mnemonic {
+    /// Generate a new 24-word mnemonic.
+    #[wasm_bindgen(constructor)]
+    pub fn _wasm_new() -> Self {
+        core::Mnemonic::new().into()
+    }
+
+    /// Parse a phrase of 12 to 24 words from the English wordlist. Throws if a word is unknown,
+    /// or the checksum doesn't match.
+    #[wasm_bindgen(js_name = "tryParse")]
+    pub fn _wasm_try_parse(value: &str) -> wasm::Result<Self> {
+        Ok(core::Mnemonic::from_str(value)?.into())
+    }
+
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn _wasm_to_string(&self) -> String {
+        self.to_string()
+    }
+}
@@ -1,10 +1,14 @@
-use crate::{core, wasm, wasm_proxy};
+use crate::{
+    core,
+    wasm::{self, Mnemonic},
+    wasm_proxy,
+};
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 
 wasm_proxy! {
     #[derive(Debug, Clone)]
-    #[doc = "An Ed25519 signing key (non-extended)."]
+    #[doc = "An Ed25519 signing key: standard, or extended when derived from a mnemonic."]
     SigningKey => core::SigningKey
 }
 
@@ -15,6 +19,22 @@ impl SigningKey {
         core::SigningKey::new().into()
     }
 
+    /// The payment key of the given account of the wallet seeded by the mnemonic; that is, at
+    /// `m/1852'/1815'/<account>'/0/0`. The passphrase is empty unless explicitly set by the user.
+    #[wasm_bindgen(js_name = "fromMnemonic")]
+    pub fn _wasm_from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: Option<String>,
+        account: Option<u32>,
+    ) -> wasm::Result<Self> {
+        Ok(core::SigningKey::from_mnemonic(
+            mnemonic,
+            passphrase.as_deref().unwrap_or_default(),
+            account.unwrap_or_default(),
+        )?
+        .into())
+    }
+
     #[wasm_bindgen(js_name = "tryParse")]
     pub fn _wasm_parse(value: &str) -> wasm::Result<Self> {
         Ok(core::SigningKey::from_str(value)?.into())